@@ -0,0 +1,143 @@
+use color_wheel_lib::{canvas::Canvas, color_wheel_error::ColorWheelError};
+
+use crate::{
+    cli::{AnimationParameterType, Cli},
+    render_canvas::render_canvas,
+};
+
+// Renders each frame of the animation through the same pipeline as a still image.
+pub fn render_animation_frames(cli: &Cli) -> Result<Vec<Canvas>, ColorWheelError> {
+    animation_frame_clis(cli)
+        .iter()
+        .map(render_canvas)
+        .collect()
+}
+
+// Creates the options for each frame, with the animated parameter replaced by the frame's value.
+fn animation_frame_clis(cli: &Cli) -> Vec<Cli> {
+    let Some(parameter) = cli.animate else {
+        return vec![cli.clone()];
+    };
+
+    let domain = cli.color_wheel_type.domain;
+    let (default_from, default_to) = match parameter {
+        AnimationParameterType::Fixed => (domain.fixed.minimum, domain.fixed.maximum),
+        AnimationParameterType::Rotation => (0., 360.),
+        AnimationParameterType::AngularBuckets | AnimationParameterType::RadialBuckets => (2., 24.),
+    };
+    let from = cli.animate_from.unwrap_or(default_from);
+    let to = cli.animate_to.unwrap_or(default_to);
+
+    // A full rotation ends one step before the first frame so the animation loops smoothly.
+    let steps = match parameter {
+        AnimationParameterType::Rotation if (to - from).abs() % 360. == 0. => cli.frames,
+        _ => cli.frames - 1,
+    };
+
+    (0..cli.frames)
+        .map(|frame| {
+            let value = from + (to - from) * frame as f64 / steps as f64;
+            let mut frame_cli = cli.clone();
+            match parameter {
+                AnimationParameterType::Fixed => {
+                    frame_cli.fixed = vec![value];
+                    for ring in frame_cli.ring.iter_mut() {
+                        ring.fixed = value;
+                    }
+                }
+                AnimationParameterType::Rotation => frame_cli.rotation = value,
+                AnimationParameterType::AngularBuckets => {
+                    frame_cli.angular_buckets = value.round().max(0.) as u32
+                }
+                AnimationParameterType::RadialBuckets => {
+                    frame_cli.radial_buckets = value.round().max(0.) as u32
+                }
+            }
+            frame_cli
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use clap::Parser;
+
+    use super::*;
+
+    fn parse(args: &[&str]) -> Cli {
+        Cli::parse_from(
+            ["color-wheel-cli", "hsl-fixed-lightness"]
+                .iter()
+                .chain(args),
+        )
+    }
+
+    #[test]
+    fn when_not_animated_it_should_return_one_frame() {
+        let frames = animation_frame_clis(&parse(&[]));
+        assert_eq!(frames.len(), 1);
+    }
+
+    #[test]
+    fn it_should_sweep_fixed_values() {
+        let cli = parse(&["--animate", "fixed", "--frames", "5", "-f", "0.2"]);
+        let fixed = animation_frame_clis(&cli)
+            .iter()
+            .map(|frame| frame.fixed.clone())
+            .collect::<Vec<_>>();
+
+        assert_eq!(
+            fixed,
+            vec![vec![0.], vec![0.25], vec![0.5], vec![0.75], vec![1.]]
+        );
+    }
+
+    #[test]
+    fn it_should_sweep_the_valid_fixed_values_by_default() {
+        let cli = Cli::parse_from([
+            "color-wheel-cli",
+            "oklab-fixed-chroma",
+            "--animate",
+            "fixed",
+            "--frames",
+            "3",
+        ]);
+        let fixed = animation_frame_clis(&cli)
+            .iter()
+            .map(|frame| frame.fixed.clone())
+            .collect::<Vec<_>>();
+
+        assert_eq!(fixed, vec![vec![0.], vec![0.25], vec![0.5]]);
+    }
+
+    #[test]
+    fn it_should_loop_full_rotations() {
+        let cli = parse(&["--animate", "rotation", "--frames", "4"]);
+        let rotations = animation_frame_clis(&cli)
+            .iter()
+            .map(|frame| frame.rotation)
+            .collect::<Vec<_>>();
+
+        assert_eq!(rotations, vec![0., 90., 180., 270.]);
+    }
+
+    #[test]
+    fn it_should_round_bucket_counts() {
+        let cli = parse(&[
+            "--animate",
+            "angular-buckets",
+            "--frames",
+            "3",
+            "--animate-from",
+            "1",
+            "--animate-to",
+            "4",
+        ]);
+        let buckets = animation_frame_clis(&cli)
+            .iter()
+            .map(|frame| frame.angular_buckets)
+            .collect::<Vec<_>>();
+
+        assert_eq!(buckets, vec![1, 3, 4]);
+    }
+}
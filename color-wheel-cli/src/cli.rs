@@ -1,22 +1,75 @@
-use clap::{command, CommandFactory, Parser, ValueEnum};
-use std::{ffi::OsStr, path::PathBuf};
+use clap::{
+    builder::{PossibleValue, PossibleValuesParser, TypedValueParser},
+    CommandFactory, Parser, Subcommand, ValueEnum,
+};
+use color_wheel_lib::{
+    background::Background,
+    bucket::{BucketDirection, BucketLayout, BucketSpacing},
+    color_vision_deficiency::ColorVisionDeficiency,
+    color_wheel_projection::ColorWheelProjection,
+    color_wheel_spec::{
+        MAXIMUM_BUCKETS, MAXIMUM_DIAMETER, MAXIMUM_SUPERSAMPLING, MINIMUM_DIAMETER,
+    },
+    dither::DitherMethod,
+    pixel_generators::{PixelGeneratorRegistration, PixelGeneratorRegistry},
+    radial_mapping::RadialMapping,
+    resample::ResampleFilter,
+};
+use std::{ffi::OsStr, num::NonZeroUsize, path::PathBuf, str::FromStr};
 
-use crate::OUTPUT_FILE_EXTENSION;
+use crate::{
+    filter_spec::{parse_filter_spec, FilterSpec},
+    parse_background::parse_background,
+    ring_spec::{parse_ring_spec, RingSpec},
+    GIF_FILE_EXTENSION, OUTPUT_FILE_EXTENSION,
+};
 
-#[derive(Parser)]
+#[derive(Parser, Clone)]
 #[command(author, version, about, long_about = None)]
 pub struct Cli {
-    pub color_wheel_type: ColorWheelType,
+    #[arg(value_parser = color_wheel_type_parser())]
+    pub color_wheel_type: PixelGeneratorRegistration,
+
+    #[command(subcommand)]
+    pub command: Option<Command>,
 
     /// Number of angular buckets to divide colors into. Defaults to 0, which gives a smooth output.
-    #[arg(short, long, value_name = "COUNT", default_value_t = 0, value_parser = clap::value_parser!(u32).range(0..=1000))]
+    #[arg(short, long, value_name = "COUNT", default_value_t = 0, value_parser = clap::value_parser!(u32).range(0..=MAXIMUM_BUCKETS as i64))]
     pub angular_buckets: u32,
 
     /// Number of radial buckets to divide colors into. Defaults to 0, which gives a smooth output.
-    #[arg(short, long, value_name = "COUNT", default_value_t = 0, value_parser = clap::value_parser!(u32).range(0..=1000))]
+    #[arg(short, long, value_name = "COUNT", default_value_t = 0, value_parser = clap::value_parser!(u32).range(0..=MAXIMUM_BUCKETS as i64))]
     pub radial_buckets: u32,
 
-    /// Fixed values at which to render. Can be specified multiple times. Defaults to 0.5 for lightness or 1 for saturation and value.
+    /// How angular buckets are spaced. Perceptual spacing places bucket boundaries at equal perceptual differences in color rather than equal angles.
+    #[arg(long, value_name = "SPACING", default_value = "uniform")]
+    pub angular_bucket_spacing: BucketSpacingType,
+
+    /// Which point of each angular bucket gives the bucket's color. Defaults to floor.
+    #[arg(long, value_name = "SAMPLE")]
+    pub angular_bucket_sample: Option<BucketSampleType>,
+
+    /// Which point of each radial bucket gives the bucket's color. Defaults to ceiling, or floor if colors are reversed.
+    #[arg(long, value_name = "SAMPLE")]
+    pub radial_bucket_sample: Option<BucketSampleType>,
+
+    /// Comma separated angles in degrees, in ascending order, at which to divide angular buckets. Overrides the number of angular buckets. Can't be used with perceptual angular bucket spacing.
+    #[arg(long, value_name = "DEGREES", value_delimiter = ',')]
+    pub angular_bucket_boundaries: Vec<f64>,
+
+    /// Comma separated distances from the center, from 0 to 1 in ascending order, at which to divide radial buckets. Overrides the number of radial buckets.
+    #[arg(long, value_name = "NUMBER", value_delimiter = ',')]
+    pub radial_bucket_boundaries: Vec<f64>,
+
+    /// Comma separated angles in degrees at which to sample each angular bucket, overriding the angular bucket sample. Each angle must fall inside its own bucket. Can't be used with perceptual angular bucket spacing.
+    #[arg(long, value_name = "DEGREES", value_delimiter = ',')]
+    pub angular_bucket_samples: Vec<f64>,
+
+    /// Comma separated distances from the center, from 0 to 1, at which to sample each radial bucket, overriding the radial bucket sample. Each distance must fall inside its own bucket.
+    #[arg(long, value_name = "NUMBER", value_delimiter = ',')]
+    pub radial_bucket_samples: Vec<f64>,
+
+    /// Fixed values at which to render. Can be specified multiple times. Must be from 0 to 1, or 0 to 0.5 for chroma. Defaults to 0.5 for HSL lightness, 0.15 for chroma or 1 otherwise.
     #[arg(short, long, value_name = "NUMBER")]
     pub fixed: Vec<f64>,
 
@@ -25,17 +78,64 @@ pub struct Cli {
     pub output: Option<PathBuf>,
 
     /// Diameter of the color wheel in pixels.
-    #[arg(short, long, value_name = "PIXELS", default_value_t = 980, value_parser = clap::value_parser!(u32).range(5..))]
+    #[arg(short, long, value_name = "PIXELS", default_value_t = 980, value_parser = clap::value_parser!(u32).range(MINIMUM_DIAMETER as i64..=MAXIMUM_DIAMETER as i64))]
     pub diameter: u32,
 
     /// Size of margin around color wheel in pixels.
-    #[arg(short, long, value_name = "PIXELS", default_value_t = 10)]
+    #[arg(short, long, value_name = "PIXELS", default_value_t = 10, value_parser = clap::value_parser!(u32).range(0..=MAXIMUM_DIAMETER as i64))]
     pub margin: u32,
 
     /// Add additional wheels to outside of previous wheel.
     #[arg(short, long)]
     pub expand: bool,
 
+    /// How colors are laid out. The unrolled layouts place angle horizontally and radius vertically, so they show the same colors as the wheel rather than a color picker's region of a single hue.
+    #[arg(long, value_name = "PROJECTION", default_value = "polar")]
+    pub projection: ProjectionType,
+
+    /// Radius of the hole in the middle of the color wheel, as a fraction of the wheel's radius.
+    #[arg(long, value_name = "NUMBER", default_value_t = 0., value_parser = parse_fraction)]
+    pub inner_radius: f64,
+
+    /// Angle in degrees, clockwise from the top, at which the visible arc of the color wheel starts.
+    #[arg(
+        long,
+        value_name = "DEGREES",
+        default_value_t = 0.,
+        allow_negative_numbers = true
+    )]
+    pub start_angle: f64,
+
+    /// Angle in degrees, clockwise from the top, at which the visible arc of the color wheel ends. The arc can't be empty, so this must not equal the start angle, or differ from it by a multiple of 360 degrees less than a full turn.
+    #[arg(
+        long,
+        value_name = "DEGREES",
+        default_value_t = 360.,
+        allow_negative_numbers = true
+    )]
+    pub end_angle: f64,
+
+    /// Angle in degrees by which to rotate the colors clockwise around the color wheel.
+    #[arg(
+        long,
+        value_name = "DEGREES",
+        default_value_t = 0.,
+        allow_negative_numbers = true
+    )]
+    pub rotation: f64,
+
+    /// Size of the gap between expanded color wheels in pixels.
+    #[arg(long, value_name = "PIXELS", default_value_t = 0)]
+    pub ring_gap: u32,
+
+    /// How the distance from the center maps to colors. One of linear, sqrt (equal area per color), gamma:N or perceptual (equal perceptual difference per pixel).
+    #[arg(long, value_name = "MAPPING", default_value = "linear", value_parser = RadialMapping::from_str)]
+    pub radial_mapping: RadialMapping,
+
+    /// A ring of an expanded color wheel, in the form FIXED[:OPTION=VALUE,...]. Can be specified multiple times, from the center outwards. Options are weight (the ring's width relative to other rings), angular-buckets, radial-buckets, angular-sample and radial-sample.
+    #[arg(long, value_name = "RING", value_parser = parse_ring_spec, conflicts_with_all = ["fixed", "expand"])]
+    pub ring: Vec<RingSpec>,
+
     /// Reverses the order of colours from the center to edge of the wheel.
     #[arg(short = 'c', long)]
     pub reverse_radial_colors: bool,
@@ -45,41 +145,394 @@ pub struct Cli {
     pub reverse_radial_bucketing: bool,
 
     // How many times supersampled should the output be. Default is 2x supersampling.
-    #[arg(short, long, value_name = "COUNT", default_value_t = 4, value_parser = clap::value_parser!(u32).range(1..=8))]
+    #[arg(short, long, value_name = "COUNT", default_value_t = 4, value_parser = clap::value_parser!(u32).range(1..=MAXIMUM_SUPERSAMPLING as i64))]
     pub supersampling: u32,
+
+    /// Filter used to reduce the supersampled image to its final size. The image is filtered in linear light, so edges between saturated colors aren't darkened.
+    #[arg(long, value_name = "FILTER", default_value = "hamming")]
+    pub resample_filter: ResampleFilterType,
+
+    /// Number of threads used to render and resize the image. Defaults to one per CPU. The image is the same for any number of threads.
+    #[arg(long, value_name = "COUNT")]
+    pub threads: Option<NonZeroUsize>,
+
+    /// Background behind the color wheels. One of transparent, checkerboard[:SIZE] or a color in the form #rrggbb[aa].
+    #[arg(long, value_name = "BACKGROUND", default_value = "transparent", value_parser = parse_background)]
+    pub background: Background,
+
+    /// Dithers the output when reducing the full precision colors to 8 bits per channel, which hides banding in smooth gradients.
+    #[arg(long, value_name = "METHOD")]
+    pub dither: Option<DitherMethodType>,
+
+    /// Simulates how the color wheels appear with a color vision deficiency. Can be specified multiple times, in which case each simulation is rendered side by side.
+    #[arg(long, value_name = "DEFICIENCY")]
+    pub simulate: Vec<ColorVisionDeficiencyType>,
+
+    /// Severity of the simulated color vision deficiency, from 0 to 1.
+    #[arg(long, value_name = "NUMBER", default_value_t = 1., value_parser = parse_fraction)]
+    pub simulation_severity: f64,
+
+    /// Renders the unmodified color wheels to the left of the simulated color wheels.
+    #[arg(long, requires = "simulate")]
+    pub compare_simulations: bool,
+
+    /// Post-processing filter to apply to the output image. Can be specified multiple times, in which case filters are applied in order. One of background:COLOR, shadow[:X,Y,BLUR[,COLOR]], vignette[:STRENGTH], grayscale, gamma:GAMMA, dither[:LEVELS[,METHOD]] or cvd:DEFICIENCY[,SEVERITY].
+    #[arg(long, value_name = "FILTER", value_parser = parse_filter_spec)]
+    pub filter: Vec<FilterSpec>,
+
+    /// Renders an animation which sweeps a parameter across frames. An output file ending in png gives an animated PNG and one ending in gif gives an animated GIF. Animating the fixed value renders a single wheel, so it can't be used with more than one fixed value or ring.
+    #[arg(long, value_name = "PARAMETER")]
+    pub animate: Option<AnimationParameterType>,
+
+    /// Number of frames in the animation.
+    #[arg(long, value_name = "COUNT", default_value_t = 36, value_parser = clap::value_parser!(u32).range(2..=1000), requires = "animate")]
+    pub frames: u32,
+
+    /// Value of the animated parameter in the first frame. Defaults to the smallest valid fixed value, 0 for rotation, or 2 for bucket counts.
+    #[arg(
+        long,
+        value_name = "NUMBER",
+        requires = "animate",
+        allow_negative_numbers = true
+    )]
+    pub animate_from: Option<f64>,
+
+    /// Value of the animated parameter in the last frame. Defaults to the largest valid fixed value, 360 for rotation or 24 for bucket counts.
+    #[arg(
+        long,
+        value_name = "NUMBER",
+        requires = "animate",
+        allow_negative_numbers = true
+    )]
+    pub animate_to: Option<f64>,
+
+    /// Delay between animation frames in milliseconds.
+    #[arg(long, value_name = "MILLISECONDS", default_value_t = 50, value_parser = clap::value_parser!(u16).range(10..), requires = "animate")]
+    pub frame_delay: u16,
+
+    /// Writes each animation frame to its own numbered PNG file rather than a single animated file.
+    #[arg(long, requires = "animate")]
+    pub frame_sequence: bool,
+}
+
+#[derive(Clone, PartialEq, Eq, Debug, Subcommand)]
+pub enum Command {
+    /// Renders the color wheels in the terminal, with keyboard controls to adjust them.
+    Preview {
+        /// How to draw the image. Defaults to detecting Kitty or Sixel graphics support, falling back to colored text.
+        #[arg(long, value_name = "PROTOCOL", default_value = "auto")]
+        protocol: PreviewProtocolType,
+
+        /// Width of the preview in terminal columns.
+        #[arg(long, value_name = "COUNT", default_value_t = 40, value_parser = clap::value_parser!(u32).range(5..=500))]
+        columns: u32,
+    },
+
+    /// Runs an HTTP server which renders color wheels from query parameters, for example /wheel.png?type=oklab-fixed-lightness&fixed=0.7&a=12. Requests without a type use the color wheel type given before this subcommand.
+    Serve {
+        /// Address on which to listen for requests.
+        #[arg(long, value_name = "ADDRESS", default_value = "127.0.0.1:8080")]
+        address: String,
+
+        /// Largest diameter or margin in pixels which may be requested.
+        #[arg(long, value_name = "PIXELS", default_value_t = 2000)]
+        max_diameter: u32,
+
+        /// Number of rendered images to keep in memory.
+        #[arg(long, value_name = "COUNT", default_value_t = 100)]
+        cache_size: usize,
+
+        /// Number of requests handled at once. Any more are answered with 503 Service
+        /// Unavailable, so that concurrent renders can't exhaust memory.
+        #[arg(long, value_name = "COUNT", default_value_t = 4, value_parser = clap::value_parser!(u32).range(1..))]
+        max_connections: u32,
+    },
 }
 
-#[derive(Copy, Clone, PartialEq, Eq, PartialOrd, Ord, ValueEnum)]
-pub enum ColorWheelType {
-    HslFixedSaturation,
-    HslFixedLightness,
-    HsvFixedSaturation,
-    HsvFixedValue,
-    OklabFixedLightness,
-    OklabFixedChroma,
+#[derive(Copy, Clone, PartialEq, Eq, PartialOrd, Ord, Debug, ValueEnum)]
+pub enum PreviewProtocolType {
+    Auto,
+    HalfBlocks,
+    Sixel,
+    Kitty,
+}
+
+#[derive(Copy, Clone, PartialEq, Eq, PartialOrd, Ord, Debug, ValueEnum)]
+pub enum AnimationParameterType {
+    Fixed,
+    Rotation,
+    AngularBuckets,
+    RadialBuckets,
+}
+
+#[derive(Copy, Clone, PartialEq, Eq, PartialOrd, Ord, Debug, ValueEnum)]
+pub enum BucketSampleType {
+    Floor,
+    Ceiling,
+    Center,
+}
+
+impl From<BucketSampleType> for BucketDirection {
+    fn from(value: BucketSampleType) -> Self {
+        match value {
+            BucketSampleType::Floor => BucketDirection::Down,
+            BucketSampleType::Ceiling => BucketDirection::Up,
+            BucketSampleType::Center => BucketDirection::Center,
+        }
+    }
+}
+
+#[derive(Copy, Clone, PartialEq, Eq, PartialOrd, Ord, Debug, ValueEnum)]
+pub enum BucketSpacingType {
+    Uniform,
+    Perceptual,
+}
+
+impl From<BucketSpacingType> for BucketSpacing {
+    fn from(value: BucketSpacingType) -> Self {
+        match value {
+            BucketSpacingType::Uniform => BucketSpacing::Uniform,
+            BucketSpacingType::Perceptual => BucketSpacing::Perceptual,
+        }
+    }
+}
+
+#[derive(Copy, Clone, PartialEq, Eq, PartialOrd, Ord, Debug, ValueEnum)]
+pub enum ProjectionType {
+    Polar,
+    UnrolledSquare,
+    UnrolledStrip,
+    UnrolledTriangleInRing,
+}
+
+impl From<ProjectionType> for ColorWheelProjection {
+    fn from(value: ProjectionType) -> Self {
+        match value {
+            ProjectionType::Polar => ColorWheelProjection::Polar,
+            ProjectionType::UnrolledSquare => ColorWheelProjection::UnrolledSquare,
+            ProjectionType::UnrolledStrip => ColorWheelProjection::UnrolledStrip,
+            ProjectionType::UnrolledTriangleInRing => ColorWheelProjection::UnrolledTriangleInRing,
+        }
+    }
+}
+
+#[derive(Copy, Clone, PartialEq, Eq, PartialOrd, Ord, Debug, ValueEnum)]
+pub enum ColorVisionDeficiencyType {
+    Protanopia,
+    Deuteranopia,
+    Tritanopia,
+    Achromatopsia,
+}
+
+impl From<ColorVisionDeficiencyType> for ColorVisionDeficiency {
+    fn from(value: ColorVisionDeficiencyType) -> Self {
+        match value {
+            ColorVisionDeficiencyType::Protanopia => ColorVisionDeficiency::Protanopia,
+            ColorVisionDeficiencyType::Deuteranopia => ColorVisionDeficiency::Deuteranopia,
+            ColorVisionDeficiencyType::Tritanopia => ColorVisionDeficiency::Tritanopia,
+            ColorVisionDeficiencyType::Achromatopsia => ColorVisionDeficiency::Achromatopsia,
+        }
+    }
+}
+
+#[derive(Copy, Clone, PartialEq, Eq, PartialOrd, Ord, Debug, ValueEnum)]
+pub enum DitherMethodType {
+    Bayer,
+    BlueNoise,
+    FloydSteinberg,
+}
+
+impl From<DitherMethodType> for DitherMethod {
+    fn from(value: DitherMethodType) -> Self {
+        match value {
+            DitherMethodType::Bayer => DitherMethod::Bayer,
+            DitherMethodType::BlueNoise => DitherMethod::BlueNoise,
+            DitherMethodType::FloydSteinberg => DitherMethod::FloydSteinberg,
+        }
+    }
+}
+
+#[derive(Copy, Clone, PartialEq, Eq, PartialOrd, Ord, Debug, ValueEnum)]
+pub enum ResampleFilterType {
+    Box,
+    Hamming,
+    Lanczos3,
+    Mitchell,
+}
+
+impl From<ResampleFilterType> for ResampleFilter {
+    fn from(value: ResampleFilterType) -> Self {
+        match value {
+            ResampleFilterType::Box => ResampleFilter::Box,
+            ResampleFilterType::Hamming => ResampleFilter::Hamming,
+            ResampleFilterType::Lanczos3 => ResampleFilter::Lanczos3,
+            ResampleFilterType::Mitchell => ResampleFilter::Mitchell,
+        }
+    }
+}
+
+// Accepts the names of the registered pixel generators, listing their descriptions in the help.
+fn color_wheel_type_parser() -> impl TypedValueParser<Value = PixelGeneratorRegistration> {
+    let registry = PixelGeneratorRegistry::default();
+    let possible_values = registry
+        .registrations()
+        .iter()
+        .map(|registration| PossibleValue::new(registration.name).help(registration.description))
+        .collect::<Vec<_>>();
+
+    PossibleValuesParser::new(possible_values).map(move |name| {
+        *registry
+            .get(&name)
+            .expect("Parser only accepts registered names.")
+    })
+}
+
+fn parse_fraction(value: &str) -> Result<f64, String> {
+    let fraction: f64 = value
+        .parse()
+        .map_err(|_| format!("`{value}` isn't a number"))?;
+    if !(0. ..=1.).contains(&fraction) {
+        return Err("must be between 0 and 1".to_string());
+    }
+
+    Ok(fraction)
 }
 
 pub fn process_cli_options(cli: Cli) -> Cli {
+    try_process_cli_options(cli).unwrap_or_else(|error| error.exit())
+}
+
+// As `process_cli_options`, but returns validation errors rather than exiting.
+pub fn try_process_cli_options(cli: Cli) -> Result<Cli, clap::Error> {
+    let domain = cli.color_wheel_type.domain;
+
+    // Animating the fixed value gives every frame a single wheel with that value.
+    if cli.animate == Some(AnimationParameterType::Fixed)
+        && (cli.fixed.len() > 1 || cli.ring.len() > 1)
+    {
+        return Err(Cli::command().error(
+            clap::error::ErrorKind::ArgumentConflict,
+            "Animating the fixed value can't be used with more than one fixed value or ring",
+        ));
+    }
+
     let mut fixed = cli.fixed.clone();
     if fixed.is_empty() {
-        fixed = match cli.color_wheel_type {
-            ColorWheelType::HslFixedLightness => vec![0.5],
-            _ => vec![1.0],
-        };
+        fixed = vec![domain.default_fixed];
+    }
+
+    for value in fixed.iter().chain(cli.ring.iter().map(|ring| &ring.fixed)) {
+        domain.validate_fixed(*value).map_err(|error| {
+            Cli::command().error(clap::error::ErrorKind::ValueValidation, error)
+        })?;
     }
 
     if let Some(output_value) = cli.output.clone() {
-        if output_value.extension() != Some(OsStr::new(OUTPUT_FILE_EXTENSION)) {
-            let mut cmd = Cli::command();
-            cmd.error(
-                clap::error::ErrorKind::InvalidValue,
-                format!("Output file must end in {OUTPUT_FILE_EXTENSION}"),
-            )
-            .exit();
+        let is_animated_file = cli.animate.is_some() && !cli.frame_sequence;
+        let extension = output_value.extension();
+        if extension != Some(OsStr::new(OUTPUT_FILE_EXTENSION))
+            && !(is_animated_file && extension == Some(OsStr::new(GIF_FILE_EXTENSION)))
+        {
+            let message = if is_animated_file {
+                format!("Output file must end in {OUTPUT_FILE_EXTENSION} or {GIF_FILE_EXTENSION}")
+            } else {
+                format!("Output file must end in {OUTPUT_FILE_EXTENSION}")
+            };
+            return Err(Cli::command().error(clap::error::ErrorKind::InvalidValue, message));
         }
     }
 
-    Cli { fixed, ..cli }
+    // An arc whose ends meet without going all the way round would render nothing.
+    let arc_degrees = cli.end_angle - cli.start_angle;
+    if arc_degrees < 360. && arc_degrees.rem_euclid(360.) == 0. {
+        return Err(Cli::command().error(
+            clap::error::ErrorKind::InvalidValue,
+            "The start and end angles must not give an empty arc",
+        ));
+    }
+
+    validate_bucket_positions(&cli.angular_bucket_boundaries, 360., true)?;
+    validate_bucket_positions(&cli.radial_bucket_boundaries, 1., true)?;
+    validate_bucket_positions(&cli.angular_bucket_samples, 360., false)?;
+    validate_bucket_positions(&cli.radial_bucket_samples, 1., false)?;
+
+    // Perceptual spacing buckets by the proportion of the perceptual distance around the
+    // wheel, which the boundaries and samples given in degrees don't match.
+    if cli.angular_bucket_spacing == BucketSpacingType::Perceptual
+        && !(cli.angular_bucket_boundaries.is_empty() && cli.angular_bucket_samples.is_empty())
+    {
+        return Err(Cli::command().error(
+            clap::error::ErrorKind::ArgumentConflict,
+            "Angular bucket boundaries and samples can't be used with perceptual angular bucket spacing",
+        ));
+    }
+
+    let angular_bucket_counts = std::iter::once(cli.angular_buckets)
+        .chain(cli.ring.iter().filter_map(|ring| ring.angular_buckets));
+    validate_bucket_samples(
+        &cli.angular_bucket_boundaries,
+        &cli.angular_bucket_samples,
+        360.,
+        angular_bucket_counts,
+    )?;
+
+    let radial_bucket_counts = std::iter::once(cli.radial_buckets)
+        .chain(cli.ring.iter().filter_map(|ring| ring.radial_buckets));
+    validate_bucket_samples(
+        &cli.radial_bucket_boundaries,
+        &cli.radial_bucket_samples,
+        1.,
+        radial_bucket_counts,
+    )?;
+
+    Ok(Cli { fixed, ..cli })
+}
+
+fn validate_bucket_positions(
+    positions: &[f64],
+    maximum: f64,
+    is_ascending: bool,
+) -> Result<(), clap::Error> {
+    let is_in_range = positions.iter().all(|p| (0. ..=maximum).contains(p));
+    let is_valid = is_in_range && (!is_ascending || positions.windows(2).all(|w| w[0] < w[1]));
+
+    if !is_valid {
+        let order = if is_ascending {
+            " in ascending order"
+        } else {
+            ""
+        };
+        return Err(Cli::command().error(
+            clap::error::ErrorKind::InvalidValue,
+            format!("Bucket positions must be between 0 and {maximum}{order}"),
+        ));
+    }
+
+    Ok(())
+}
+
+// Checks that each sample position falls inside its own bucket, for every number of buckets
+// the positions are used with.
+fn validate_bucket_samples(
+    boundaries: &[f64],
+    samples: &[f64],
+    maximum: f64,
+    mut bucket_counts: impl Iterator<Item = u32>,
+) -> Result<(), clap::Error> {
+    let to_proportion = |position: &f64| position / maximum;
+    let layout = BucketLayout {
+        boundaries: boundaries.iter().map(to_proportion).collect(),
+        sample_positions: samples.iter().map(to_proportion).collect(),
+    };
+
+    if !bucket_counts.all(|buckets| layout.are_samples_within_buckets(buckets)) {
+        return Err(Cli::command().error(
+            clap::error::ErrorKind::InvalidValue,
+            "Each bucket sample position must fall inside its own bucket",
+        ));
+    }
+
+    Ok(())
 }
 
 #[cfg(test)]
@@ -91,4 +544,144 @@ mod tests {
         use clap::CommandFactory;
         Cli::command().debug_assert()
     }
+
+    fn parse(args: &[&str]) -> Result<Cli, clap::Error> {
+        Cli::try_parse_from(["color-wheel-cli"].iter().chain(args))
+            .and_then(try_process_cli_options)
+    }
+
+    #[test]
+    fn it_should_default_fixed_values_from_the_pixel_generator() {
+        assert_eq!(parse(&["hsl-fixed-lightness"]).unwrap().fixed, vec![0.5]);
+        assert_eq!(parse(&["hsv-fixed-value"]).unwrap().fixed, vec![1.]);
+        assert_eq!(parse(&["oklab-fixed-chroma"]).unwrap().fixed, vec![0.15]);
+    }
+
+    #[test]
+    fn it_should_reject_fixed_values_out_of_range() {
+        assert!(parse(&["hsl-fixed-saturation", "-f", "5"]).is_err());
+        assert!(parse(&["oklab-fixed-chroma", "-f", "0.6"]).is_err());
+        assert!(parse(&["hsv-fixed-value", "--ring", "1.5"]).is_err());
+        assert!(parse(&["oklab-fixed-chroma", "-f", "0.35"]).is_ok());
+    }
+
+    #[test]
+    fn it_should_reject_angular_positions_with_perceptual_spacing() {
+        let perceptual = [
+            "oklab-fixed-lightness",
+            "--angular-bucket-spacing",
+            "perceptual",
+        ];
+        assert!(
+            parse(&[&perceptual[..], &["--angular-bucket-boundaries", "90"]].concat()).is_err()
+        );
+        assert!(parse(
+            &[
+                &perceptual[..],
+                &["-a", "4", "--angular-bucket-samples", "10"]
+            ]
+            .concat()
+        )
+        .is_err());
+        assert!(parse(&[&perceptual[..], &["--radial-bucket-boundaries", "0.5"]].concat()).is_ok());
+    }
+
+    #[test]
+    fn it_should_reject_samples_outside_of_their_buckets() {
+        let bucketed = |args: &[&str]| parse(&[&["hsv-fixed-value"], args].concat());
+        assert!(bucketed(&[
+            "--angular-bucket-boundaries",
+            "180",
+            "--angular-bucket-samples",
+            "270,90"
+        ])
+        .is_err());
+        assert!(bucketed(&[
+            "--angular-bucket-boundaries",
+            "180",
+            "--angular-bucket-samples",
+            "90,270"
+        ])
+        .is_ok());
+        assert!(bucketed(&["-r", "2", "--radial-bucket-samples", "0.2,0.4"]).is_err());
+        assert!(bucketed(&["-r", "2", "--radial-bucket-samples", "0.2,0.6"]).is_ok());
+        assert!(bucketed(&[
+            "-a",
+            "4",
+            "--ring",
+            "1:angular-buckets=2",
+            "--angular-bucket-samples",
+            "10,150"
+        ])
+        .is_err());
+    }
+
+    #[test]
+    fn it_should_reject_animating_more_than_one_fixed_value() {
+        let animated =
+            |args: &[&str]| parse(&[&["hsv-fixed-value", "--animate", "fixed"], args].concat());
+        assert!(animated(&["-e", "-f", "0.2", "-f", "0.8"]).is_err());
+        assert!(animated(&["--ring", "0.2", "--ring", "0.8"]).is_err());
+        assert!(animated(&["-f", "0.2"]).is_ok());
+        assert!(parse(&[
+            "hsv-fixed-value",
+            "--animate",
+            "rotation",
+            "-e",
+            "-f",
+            "0.2",
+            "-f",
+            "0.8"
+        ])
+        .is_ok());
+    }
+
+    #[test]
+    fn it_should_reject_empty_arcs() {
+        assert!(parse(&[
+            "hsv-fixed-value",
+            "--start-angle",
+            "90",
+            "--end-angle",
+            "90"
+        ])
+        .is_err());
+        assert!(parse(&[
+            "hsv-fixed-value",
+            "--start-angle",
+            "90",
+            "--end-angle",
+            "-270"
+        ])
+        .is_err());
+        assert!(parse(&[
+            "hsv-fixed-value",
+            "--start-angle",
+            "90",
+            "--end-angle",
+            "450"
+        ])
+        .is_ok());
+        assert!(parse(&[
+            "hsv-fixed-value",
+            "--start-angle",
+            "270",
+            "--end-angle",
+            "90"
+        ])
+        .is_ok());
+    }
+
+    #[test]
+    fn it_should_require_at_least_one_thread() {
+        assert!(parse(&["hsv-fixed-value", "--threads", "0"]).is_err());
+        assert_eq!(
+            parse(&["hsv-fixed-value", "--threads", "2"])
+                .unwrap()
+                .threads
+                .map(NonZeroUsize::get),
+            Some(2)
+        );
+        assert!(parse(&["hsv-fixed-value"]).unwrap().threads.is_none());
+    }
 }
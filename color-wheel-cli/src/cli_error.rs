@@ -0,0 +1,149 @@
+use std::{error::Error, fmt, path::PathBuf};
+
+use color_wheel_lib::color_wheel_error::ColorWheelError;
+
+#[derive(Debug)]
+pub enum CliError {
+    Render(ColorWheelError),
+    WriteOutput { path: PathBuf, message: String },
+    Terminal { message: String },
+    ThreadPool { message: String },
+    Listen { address: String, message: String },
+}
+
+impl CliError {
+    pub fn write_output(path: PathBuf, error: impl Error) -> CliError {
+        CliError::WriteOutput {
+            path,
+            message: error.to_string(),
+        }
+    }
+
+    pub fn terminal(error: impl Error) -> CliError {
+        CliError::Terminal {
+            message: error.to_string(),
+        }
+    }
+
+    pub fn thread_pool(error: impl Error) -> CliError {
+        CliError::ThreadPool {
+            message: error.to_string(),
+        }
+    }
+
+    pub fn listen(address: &str, error: impl Error) -> CliError {
+        CliError::Listen {
+            address: address.to_string(),
+            message: error.to_string(),
+        }
+    }
+
+    // Clap exits with 2 for invalid arguments, so each error's code follows on from that.
+    pub fn exit_code(&self) -> u8 {
+        match self {
+            CliError::Render(ColorWheelError::NoColorWheels) => 3,
+            CliError::Render(ColorWheelError::ImageTooLarge) => 4,
+            CliError::Render(ColorWheelError::ImageTooSmall) => 5,
+            CliError::Render(ColorWheelError::NotEnoughRows { .. }) => 6,
+            CliError::Render(ColorWheelError::WrongRow { .. }) => 7,
+            CliError::WriteOutput { .. } => 8,
+            CliError::Render(ColorWheelError::FixedValueOutOfRange { .. }) => 9,
+            CliError::Terminal { .. } => 10,
+            CliError::Render(ColorWheelError::OptionOutOfRange { .. }) => 11,
+            CliError::Render(ColorWheelError::EmptyShape) => 12,
+            CliError::Render(ColorWheelError::InvalidBucketLayout) => 13,
+            CliError::ThreadPool { .. } => 14,
+            CliError::Listen { .. } => 15,
+        }
+    }
+}
+
+impl From<ColorWheelError> for CliError {
+    fn from(error: ColorWheelError) -> Self {
+        CliError::Render(error)
+    }
+}
+
+impl fmt::Display for CliError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            CliError::Render(error) => write!(f, "Failed to render color wheels: {error}"),
+            CliError::WriteOutput { path, message } => {
+                write!(f, "Failed to write {}: {message}", path.display())
+            }
+            CliError::Terminal { message } => {
+                write!(f, "Failed to preview in the terminal: {message}")
+            }
+            CliError::ThreadPool { message } => {
+                write!(f, "Failed to create the thread pool: {message}")
+            }
+            CliError::Listen { address, message } => {
+                write!(f, "Failed to listen on {address}: {message}")
+            }
+        }
+    }
+}
+
+impl Error for CliError {}
+
+#[cfg(test)]
+mod tests {
+    use std::collections::HashSet;
+
+    use super::*;
+
+    #[test]
+    fn it_should_use_a_distinct_exit_code_for_each_error() {
+        let errors = [
+            CliError::Render(ColorWheelError::NoColorWheels),
+            CliError::Render(ColorWheelError::ImageTooLarge),
+            CliError::Render(ColorWheelError::ImageTooSmall),
+            CliError::Render(ColorWheelError::NotEnoughRows {
+                expected: 2,
+                actual: 1,
+            }),
+            CliError::Render(ColorWheelError::WrongRow {
+                expected: 2,
+                actual: 1,
+            }),
+            CliError::WriteOutput {
+                path: PathBuf::from("output.png"),
+                message: "Permission denied".to_string(),
+            },
+            CliError::Render(ColorWheelError::FixedValueOutOfRange {
+                name: "chroma",
+                value: 5.,
+                minimum: 0.,
+                maximum: 0.5,
+            }),
+            CliError::Terminal {
+                message: "Not a terminal".to_string(),
+            },
+            CliError::Render(ColorWheelError::OptionOutOfRange {
+                name: "Diameter",
+                value: 1.,
+                minimum: 5.,
+                maximum: 8192.,
+            }),
+            CliError::Render(ColorWheelError::EmptyShape),
+            CliError::Render(ColorWheelError::InvalidBucketLayout),
+            CliError::ThreadPool {
+                message: "The global thread pool has already been initialized".to_string(),
+            },
+            CliError::Listen {
+                address: "127.0.0.1:8080".to_string(),
+                message: "Address already in use".to_string(),
+            },
+        ];
+
+        let exit_codes = errors
+            .iter()
+            .map(CliError::exit_code)
+            .collect::<HashSet<_>>();
+
+        assert_eq!(exit_codes.len(), errors.len());
+        assert!(!exit_codes.contains(&0));
+        assert!(!exit_codes.contains(&1));
+        assert!(!exit_codes.contains(&2));
+    }
+}
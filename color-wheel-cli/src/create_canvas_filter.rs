@@ -0,0 +1,49 @@
+use color_wheel_lib::{
+    canvas_filters::{
+        BackgroundFillCanvasFilter, CanvasFilter, CanvasFilterPipeline,
+        ColorVisionDeficiencyCanvasFilter, DitherCanvasFilter, DropShadowCanvasFilter,
+        GammaCanvasFilter, GrayscaleCanvasFilter, VignetteCanvasFilter,
+    },
+    color_vision_deficiency::ColorVisionDeficiencySimulation,
+};
+
+use crate::{cli::Cli, filter_spec::FilterSpec};
+
+pub fn create_canvas_filter(cli: &Cli) -> CanvasFilterPipeline {
+    CanvasFilterPipeline {
+        filters: cli.filter.iter().map(create_single_canvas_filter).collect(),
+    }
+}
+
+fn create_single_canvas_filter(spec: &FilterSpec) -> Box<dyn CanvasFilter> {
+    match *spec {
+        FilterSpec::Background { color } => Box::new(BackgroundFillCanvasFilter { color }),
+        FilterSpec::Shadow {
+            offset_x,
+            offset_y,
+            blur_radius,
+            color,
+        } => Box::new(DropShadowCanvasFilter {
+            offset_x,
+            offset_y,
+            blur_radius,
+            color,
+        }),
+        FilterSpec::Vignette { strength } => Box::new(VignetteCanvasFilter { strength }),
+        FilterSpec::Grayscale => Box::new(GrayscaleCanvasFilter {}),
+        FilterSpec::Gamma { gamma } => Box::new(GammaCanvasFilter { gamma }),
+        FilterSpec::Dither { levels, method } => Box::new(DitherCanvasFilter {
+            levels,
+            method: method.into(),
+        }),
+        FilterSpec::ColorVisionDeficiency {
+            deficiency,
+            severity,
+        } => Box::new(ColorVisionDeficiencyCanvasFilter {
+            simulation: ColorVisionDeficiencySimulation {
+                deficiency: deficiency.into(),
+                severity,
+            },
+        }),
+    }
+}
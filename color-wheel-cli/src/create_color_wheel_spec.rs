@@ -0,0 +1,84 @@
+use color_wheel_lib::{
+    bucket::{BucketDirection, BucketLayout},
+    color_wheel_ring::ColorWheelRing,
+    color_wheel_shape::ColorWheelShape,
+    color_wheel_spec::ColorWheelSpec,
+    render_thread_pool::RenderThreadPool,
+    ring_options::RingOptions,
+};
+
+use crate::cli::Cli;
+
+pub fn create_color_wheel_spec(cli: &Cli) -> ColorWheelSpec {
+    // The command line gives angular positions in degrees, where the spec uses proportions.
+    let to_proportion = |degrees: &f64| degrees / 360.;
+
+    ColorWheelSpec {
+        registration: cli.color_wheel_type,
+        diameter: cli.diameter,
+        margin: cli.margin,
+        fixed: cli.fixed.clone(),
+        expand: cli.expand,
+        rings: cli
+            .ring
+            .iter()
+            .map(|ring| ColorWheelRing {
+                fixed: ring.fixed,
+                options: RingOptions {
+                    weight: ring.weight,
+                    angle_buckets: ring.angular_buckets,
+                    distance_buckets: ring.radial_buckets,
+                },
+                angle_bucket_direction: ring.angular_sample.map(Into::into),
+                varying_dimension_bucket_direction: ring.radial_sample.map(Into::into),
+            })
+            .collect(),
+        angle_buckets: cli.angular_buckets,
+        distance_buckets: cli.radial_buckets,
+        angle_bucket_layout: BucketLayout {
+            boundaries: cli
+                .angular_bucket_boundaries
+                .iter()
+                .map(to_proportion)
+                .collect(),
+            sample_positions: cli
+                .angular_bucket_samples
+                .iter()
+                .map(to_proportion)
+                .collect(),
+        },
+        distance_bucket_layout: BucketLayout {
+            boundaries: cli.radial_bucket_boundaries.clone(),
+            sample_positions: cli.radial_bucket_samples.clone(),
+        },
+        angle_bucket_spacing: cli.angular_bucket_spacing.into(),
+        angle_bucket_direction: cli
+            .angular_bucket_sample
+            .map_or(BucketDirection::Down, Into::into),
+        varying_dimension_bucket_direction: cli.radial_bucket_sample.map(Into::into),
+        is_varying_dimension_inverted: cli.reverse_radial_colors,
+        is_distance_bucketing_reversed: cli.reverse_radial_bucketing,
+        shape: ColorWheelShape {
+            projection: cli.projection.into(),
+            inner_radius: cli.inner_radius,
+            start_angle_degrees: cli.start_angle,
+            end_angle_degrees: cli.end_angle,
+            ring_gap: cli.ring_gap,
+            rotation_degrees: cli.rotation,
+        },
+        radial_mapping: cli.radial_mapping,
+        supersampling: cli.supersampling,
+        resample_filter: cli.resample_filter.into(),
+        dither: cli.dither.map(Into::into),
+        background: cli.background,
+        simulations: cli
+            .simulate
+            .iter()
+            .map(|simulation| (*simulation).into())
+            .collect(),
+        simulation_severity: cli.simulation_severity,
+        compare_simulations: cli.compare_simulations,
+        // `--threads` sizes the global thread pool.
+        thread_pool: RenderThreadPool::global(),
+    }
+}
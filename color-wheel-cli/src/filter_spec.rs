@@ -0,0 +1,218 @@
+use clap::ValueEnum;
+use color_wheel_lib::pixel::Pixel;
+
+use crate::{
+    cli::{ColorVisionDeficiencyType, DitherMethodType},
+    parse_color::parse_color,
+};
+
+#[derive(Clone, Copy, PartialEq, Debug)]
+pub enum FilterSpec {
+    Background {
+        color: Pixel,
+    },
+    Shadow {
+        offset_x: i32,
+        offset_y: i32,
+        blur_radius: u32,
+        color: Pixel,
+    },
+    Vignette {
+        strength: f64,
+    },
+    Grayscale,
+    Gamma {
+        gamma: f64,
+    },
+    Dither {
+        levels: u32,
+        method: DitherMethodType,
+    },
+    ColorVisionDeficiency {
+        deficiency: ColorVisionDeficiencyType,
+        severity: f64,
+    },
+}
+
+const FILTER_SPEC_HELP: &str = "background:COLOR, shadow[:X,Y,BLUR[,COLOR]], \
+vignette[:STRENGTH], grayscale, gamma:GAMMA, dither[:LEVELS[,METHOD]] or cvd:DEFICIENCY[,SEVERITY]";
+
+// Parses a filter in the form `name` or `name:argument,argument`.
+pub fn parse_filter_spec(value: &str) -> Result<FilterSpec, String> {
+    let (name, arguments) = match value.split_once(':') {
+        Some((name, arguments)) => (name, arguments.split(',').collect::<Vec<_>>()),
+        None => (value, vec![]),
+    };
+
+    let argument_count = arguments.len();
+    let check_argument_count = |minimum: usize, maximum: usize| {
+        if argument_count < minimum || argument_count > maximum {
+            Err(format!(
+                "`{name}` expects between {minimum} and {maximum} arguments but got {argument_count}"
+            ))
+        } else {
+            Ok(())
+        }
+    };
+
+    let spec = match name {
+        "background" => {
+            check_argument_count(1, 1)?;
+            FilterSpec::Background {
+                color: parse_color(arguments[0])?,
+            }
+        }
+        "shadow" => {
+            check_argument_count(0, 4)?;
+            FilterSpec::Shadow {
+                offset_x: parse_argument(arguments.first(), 4)?,
+                offset_y: parse_argument(arguments.get(1), 4)?,
+                blur_radius: parse_argument(arguments.get(2), 8)?,
+                color: match arguments.get(3) {
+                    Some(color) => parse_color(color)?,
+                    None => Pixel::rgba(0, 0, 0, 128),
+                },
+            }
+        }
+        "vignette" => {
+            check_argument_count(0, 1)?;
+            FilterSpec::Vignette {
+                strength: parse_argument(arguments.first(), 0.5)?,
+            }
+        }
+        "grayscale" => {
+            check_argument_count(0, 0)?;
+            FilterSpec::Grayscale
+        }
+        "gamma" => {
+            check_argument_count(1, 1)?;
+            let gamma = parse_argument(arguments.first(), 1.)?;
+            if gamma <= 0. {
+                return Err("gamma must be greater than 0".to_string());
+            }
+            FilterSpec::Gamma { gamma }
+        }
+        "dither" => {
+            check_argument_count(0, 2)?;
+            let levels = parse_argument(arguments.first(), 4)?;
+            if !(2..=256).contains(&levels) {
+                return Err("dither levels must be between 2 and 256".to_string());
+            }
+            FilterSpec::Dither {
+                levels,
+                method: match arguments.get(1) {
+                    Some(method) => DitherMethodType::from_str(method, true)?,
+                    None => DitherMethodType::Bayer,
+                },
+            }
+        }
+        "cvd" => {
+            check_argument_count(1, 2)?;
+            let severity = parse_argument(arguments.get(1), 1.)?;
+            if !(0. ..=1.).contains(&severity) {
+                return Err("severity must be between 0 and 1".to_string());
+            }
+            FilterSpec::ColorVisionDeficiency {
+                deficiency: ColorVisionDeficiencyType::from_str(arguments[0], true)?,
+                severity,
+            }
+        }
+        _ => {
+            return Err(format!(
+                "unknown filter `{name}`, expected {FILTER_SPEC_HELP}"
+            ))
+        }
+    };
+
+    Ok(spec)
+}
+
+fn parse_argument<T: std::str::FromStr>(argument: Option<&&str>, default: T) -> Result<T, String> {
+    match argument {
+        Some(argument) => argument
+            .trim()
+            .parse()
+            .map_err(|_| format!("`{argument}` isn't a valid filter argument")),
+        None => Ok(default),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn it_should_parse_filters_without_arguments() {
+        assert_eq!(parse_filter_spec("grayscale"), Ok(FilterSpec::Grayscale));
+        assert_eq!(
+            parse_filter_spec("vignette"),
+            Ok(FilterSpec::Vignette { strength: 0.5 })
+        );
+        assert_eq!(
+            parse_filter_spec("shadow"),
+            Ok(FilterSpec::Shadow {
+                offset_x: 4,
+                offset_y: 4,
+                blur_radius: 8,
+                color: Pixel::rgba(0, 0, 0, 128)
+            })
+        );
+    }
+
+    #[test]
+    fn it_should_parse_filter_arguments() {
+        assert_eq!(
+            parse_filter_spec("background:#ffffff"),
+            Ok(FilterSpec::Background {
+                color: Pixel::rgb(255, 255, 255)
+            })
+        );
+        assert_eq!(
+            parse_filter_spec("shadow:-2,3,5,#ff000040"),
+            Ok(FilterSpec::Shadow {
+                offset_x: -2,
+                offset_y: 3,
+                blur_radius: 5,
+                color: Pixel::rgba(255, 0, 0, 64)
+            })
+        );
+        assert_eq!(
+            parse_filter_spec("gamma:2.2"),
+            Ok(FilterSpec::Gamma { gamma: 2.2 })
+        );
+        assert_eq!(
+            parse_filter_spec("dither:8"),
+            Ok(FilterSpec::Dither {
+                levels: 8,
+                method: DitherMethodType::Bayer
+            })
+        );
+        assert_eq!(
+            parse_filter_spec("dither:2,floyd-steinberg"),
+            Ok(FilterSpec::Dither {
+                levels: 2,
+                method: DitherMethodType::FloydSteinberg
+            })
+        );
+        assert_eq!(
+            parse_filter_spec("cvd:deuteranopia,0.5"),
+            Ok(FilterSpec::ColorVisionDeficiency {
+                deficiency: ColorVisionDeficiencyType::Deuteranopia,
+                severity: 0.5
+            })
+        );
+    }
+
+    #[test]
+    fn it_should_reject_invalid_filters() {
+        assert!(parse_filter_spec("sepia").is_err());
+        assert!(parse_filter_spec("background").is_err());
+        assert!(parse_filter_spec("grayscale:1").is_err());
+        assert!(parse_filter_spec("gamma:0").is_err());
+        assert!(parse_filter_spec("dither:1").is_err());
+        assert!(parse_filter_spec("dither:4,random").is_err());
+        assert!(parse_filter_spec("cvd:protanopia,2").is_err());
+        assert!(parse_filter_spec("cvd:unknown").is_err());
+        assert!(parse_filter_spec("vignette:strong").is_err());
+    }
+}
@@ -1,27 +1,102 @@
-use std::time::Instant;
+use std::{process::ExitCode, time::Instant};
 
+use animation::render_animation_frames;
 use clap::Parser;
-use cli::{process_cli_options, Cli};
+use cli::{process_cli_options, Cli, Command};
+use cli_error::CliError;
 
-use generate_color_wheel::generate_color_wheel;
+use color_wheel_lib::{
+    canvas_filters::CanvasFilter, render_color_wheel_spec::render_color_wheel_spec,
+};
+use create_canvas_filter::create_canvas_filter;
+use create_color_wheel_spec::create_color_wheel_spec;
+use preview::preview;
+use rayon::ThreadPoolBuilder;
+use serve::serve;
+use write_animation::write_animation;
 use write_output_file::write_output_file;
 
+mod animation;
+mod base64;
 mod cli;
-mod create_pixel_generator;
-mod create_pixel_generator_configuration;
-mod generate_color_wheel;
+mod cli_error;
+mod create_canvas_filter;
+mod create_color_wheel_spec;
+mod filter_spec;
+mod parse_background;
+mod parse_color;
+mod preview;
+mod render_canvas;
+mod ring_spec;
+mod serve;
+mod serve_query;
+mod terminal_half_blocks;
+mod terminal_kitty;
+mod terminal_sixel;
+mod write_animation;
+mod write_gif;
 mod write_output_file;
 
 const OUTPUT_FILE_EXTENSION: &str = "png";
+const GIF_FILE_EXTENSION: &str = "gif";
 
-fn main() {
+fn main() -> ExitCode {
     let cli = process_cli_options(Cli::parse());
 
+    match run(cli) {
+        Ok(()) => ExitCode::SUCCESS,
+        Err(error) => {
+            eprintln!("{error}");
+            ExitCode::from(error.exit_code())
+        }
+    }
+}
+
+fn run(cli: Cli) -> Result<(), CliError> {
+    // Limits every parallel step, including resizing, rather than just rendering.
+    if let Some(threads) = cli.threads {
+        ThreadPoolBuilder::new()
+            .num_threads(threads.get())
+            .build_global()
+            .map_err(CliError::thread_pool)?;
+    }
+
+    if let Some(Command::Preview { .. }) = cli.command {
+        return preview(cli);
+    }
+
+    if let Some(Command::Serve { .. }) = cli.command {
+        return serve(cli);
+    }
+
+    if cli.animate.is_some() {
+        let now = Instant::now();
+        let frames = render_animation_frames(&cli)?;
+        println!(
+            "Render {} frames: {}ms",
+            frames.len(),
+            now.elapsed().as_millis()
+        );
+
+        let now = Instant::now();
+        write_animation(cli, frames)?;
+        println!("Write: {}ms", now.elapsed().as_millis());
+        return Ok(());
+    }
+
     let now = Instant::now();
-    let pixel_writer = generate_color_wheel(&cli);
+    let mut canvas = render_color_wheel_spec(&create_color_wheel_spec(&cli))?;
     println!("Generate: {}ms", now.elapsed().as_millis());
 
+    if !cli.filter.is_empty() {
+        let now = Instant::now();
+        create_canvas_filter(&cli).execute(&mut canvas);
+        println!("Filter: {}ms", now.elapsed().as_millis());
+    }
+
     let now = Instant::now();
-    write_output_file(cli, pixel_writer);
+    write_output_file(cli, canvas)?;
     println!("Write: {}ms", now.elapsed().as_millis());
+
+    Ok(())
 }
@@ -0,0 +1,58 @@
+use color_wheel_lib::background::Background;
+
+use crate::parse_color::parse_color;
+
+const DEFAULT_CHECKERBOARD_SIZE: u32 = 16;
+
+// Parses `transparent`, `checkerboard[:SIZE]` or a hex color.
+pub fn parse_background(value: &str) -> Result<Background, String> {
+    let (name, argument) = match value.split_once(':') {
+        Some((name, argument)) => (name, Some(argument)),
+        None => (value, None),
+    };
+
+    match (name, argument) {
+        ("transparent", None) => Ok(Background::Transparent),
+        ("checkerboard", None) => Ok(Background::checkerboard(DEFAULT_CHECKERBOARD_SIZE)),
+        ("checkerboard", Some(size)) => match size.parse::<u32>() {
+            Ok(size) if size > 0 => Ok(Background::checkerboard(size)),
+            _ => Err(format!("`{size}` isn't a positive checkerboard size")),
+        },
+        _ => parse_color(value).map(Background::Solid),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use color_wheel_lib::pixel::Pixel;
+
+    use super::*;
+
+    #[test]
+    fn it_should_parse_transparent() {
+        assert_eq!(parse_background("transparent"), Ok(Background::Transparent));
+    }
+
+    #[test]
+    fn it_should_parse_checkerboard() {
+        assert_eq!(
+            parse_background("checkerboard"),
+            Ok(Background::checkerboard(DEFAULT_CHECKERBOARD_SIZE))
+        );
+        assert_eq!(
+            parse_background("checkerboard:4"),
+            Ok(Background::checkerboard(4))
+        );
+        assert!(parse_background("checkerboard:0").is_err());
+        assert!(parse_background("checkerboard:x").is_err());
+    }
+
+    #[test]
+    fn it_should_parse_colors() {
+        assert_eq!(
+            parse_background("#ffffff"),
+            Ok(Background::Solid(Pixel::rgb(255, 255, 255)))
+        );
+        assert!(parse_background("white").is_err());
+    }
+}
@@ -0,0 +1,41 @@
+use color_wheel_lib::pixel::{Pixel, OPAQUE};
+
+// Parses a hex color in the form `#rrggbb` or `#rrggbbaa`. The leading `#` is optional.
+pub fn parse_color(value: &str) -> Result<Pixel, String> {
+    let hex = value.strip_prefix('#').unwrap_or(value);
+
+    if (hex.len() != 6 && hex.len() != 8) || !hex.chars().all(|c| c.is_ascii_hexdigit()) {
+        return Err(format!(
+            "`{value}` isn't a color in the form #rrggbb or #rrggbbaa"
+        ));
+    }
+
+    let channel = |index: usize| u8::from_str_radix(&hex[index * 2..index * 2 + 2], 16).unwrap();
+
+    let alpha = if hex.len() == 8 { channel(3) } else { OPAQUE };
+
+    Ok(Pixel::rgba(channel(0), channel(1), channel(2), alpha))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn it_should_parse_rgb() {
+        assert_eq!(parse_color("#ff8000"), Ok(Pixel::rgb(255, 128, 0)));
+        assert_eq!(parse_color("0a0B0c"), Ok(Pixel::rgb(10, 11, 12)));
+    }
+
+    #[test]
+    fn it_should_parse_rgba() {
+        assert_eq!(parse_color("#ff800080"), Ok(Pixel::rgba(255, 128, 0, 128)));
+    }
+
+    #[test]
+    fn it_should_reject_invalid_colors() {
+        assert!(parse_color("#ff80").is_err());
+        assert!(parse_color("#gg8000").is_err());
+        assert!(parse_color("").is_err());
+    }
+}
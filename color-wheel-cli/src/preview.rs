@@ -0,0 +1,351 @@
+use std::{
+    env,
+    io::{self, Write},
+};
+
+use color_wheel_lib::{
+    color_wheel_spec::MAXIMUM_BUCKETS, pixel_generators::PixelGeneratorRegistry,
+};
+use crossterm::{
+    cursor,
+    event::{self, Event, KeyCode, KeyEvent, KeyEventKind, KeyModifiers},
+    terminal,
+};
+
+use crate::{
+    cli::{Cli, Command, PreviewProtocolType},
+    cli_error::CliError,
+    render_canvas::render_canvas,
+    terminal_half_blocks::encode_half_blocks,
+    terminal_kitty::{encode_kitty, CLEAR_KITTY_IMAGES},
+    terminal_sixel::encode_sixel,
+};
+
+// Approximate width of a terminal column in pixels, used to size graphics protocol images.
+const PIXELS_PER_COLUMN: u32 = 8;
+
+// Each press of f or F moves the fixed values by this fraction of their valid range.
+const FIXED_STEP: f64 = 0.05;
+
+// Terminals known to support Sixel graphics, by `TERM` or `TERM_PROGRAM`.
+const SIXEL_TERMINALS: [&str; 5] = ["foot", "mlterm", "yaft", "WezTerm", "contour"];
+
+const KEYS_HELP: &str = "a/A angular  r/R radial  f/F fixed  t/T type  q quit";
+
+// Renders the color wheels in the terminal and re-renders them as keys are pressed.
+pub fn preview(cli: Cli) -> Result<(), CliError> {
+    let Some(Command::Preview { protocol, columns }) = cli.command else {
+        return Ok(());
+    };
+
+    let protocol = detect_protocol(protocol, |name| env::var(name).ok());
+    let cli = preview_cli(cli, protocol, columns);
+
+    let mut output = io::stdout();
+    let raw_terminal = RawTerminal::enable().map_err(CliError::terminal)?;
+    let result = run_preview(&mut output, cli, protocol);
+    drop(raw_terminal);
+
+    result.map_err(CliError::terminal)
+}
+
+fn run_preview(
+    output: &mut impl Write,
+    mut cli: Cli,
+    protocol: PreviewProtocolType,
+) -> io::Result<()> {
+    draw(output, &cli, protocol)?;
+
+    loop {
+        match event::read()? {
+            Event::Key(key) if key.kind != KeyEventKind::Release => {
+                if !apply_preview_key(&mut cli, key) {
+                    break;
+                }
+                draw(output, &cli, protocol)?;
+            }
+            Event::Resize(..) => draw(output, &cli, protocol)?,
+            _ => {}
+        }
+    }
+
+    if protocol == PreviewProtocolType::Kitty {
+        write!(output, "{CLEAR_KITTY_IMAGES}")?;
+    }
+    write!(output, "\x1b[H\x1b[2J")?;
+    output.flush()
+}
+
+fn detect_protocol(
+    protocol: PreviewProtocolType,
+    environment: impl Fn(&str) -> Option<String>,
+) -> PreviewProtocolType {
+    if protocol != PreviewProtocolType::Auto {
+        return protocol;
+    }
+
+    let term = environment("TERM").unwrap_or_default();
+    let term_program = environment("TERM_PROGRAM").unwrap_or_default();
+
+    if environment("KITTY_WINDOW_ID").is_some() || term.contains("kitty") {
+        PreviewProtocolType::Kitty
+    } else if SIXEL_TERMINALS
+        .iter()
+        .any(|name| term.starts_with(name) || term_program == *name)
+    {
+        PreviewProtocolType::Sixel
+    } else {
+        PreviewProtocolType::HalfBlocks
+    }
+}
+
+// Sizes the color wheels to fit the requested number of terminal columns.
+fn preview_cli(cli: Cli, protocol: PreviewProtocolType, columns: u32) -> Cli {
+    let pixels_per_column = match protocol {
+        PreviewProtocolType::HalfBlocks | PreviewProtocolType::Auto => 1,
+        PreviewProtocolType::Sixel | PreviewProtocolType::Kitty => PIXELS_PER_COLUMN,
+    };
+
+    Cli {
+        diameter: columns * pixels_per_column,
+        margin: 0,
+        animate: None,
+        ..cli
+    }
+}
+
+fn draw(output: &mut impl Write, cli: &Cli, protocol: PreviewProtocolType) -> io::Result<()> {
+    // Some combinations of keys leave no room for the rings, so show why instead of exiting.
+    let image = match (render_canvas(cli), protocol) {
+        (Err(error), _) => error.to_string(),
+        (Ok(canvas), PreviewProtocolType::HalfBlocks | PreviewProtocolType::Auto) => {
+            encode_half_blocks(&canvas)
+        }
+        (Ok(canvas), PreviewProtocolType::Sixel) => encode_sixel(&canvas),
+        (Ok(canvas), PreviewProtocolType::Kitty) => {
+            format!("{CLEAR_KITTY_IMAGES}{}", encode_kitty(&canvas))
+        }
+    };
+
+    write!(
+        output,
+        "\x1b[H\x1b[2J{image}\r\n{}\r\n{KEYS_HELP}",
+        preview_status(cli)
+    )?;
+    output.flush()
+}
+
+fn preview_status(cli: &Cli) -> String {
+    let fixed = cli
+        .fixed
+        .iter()
+        .map(|fixed| format!("{fixed:.2}"))
+        .collect::<Vec<_>>()
+        .join(",");
+
+    format!(
+        "{}  angular buckets: {}  radial buckets: {}  fixed: {fixed}",
+        cli.color_wheel_type.name, cli.angular_buckets, cli.radial_buckets
+    )
+}
+
+// Updates the options for a key press. Returns false when the preview should close. Other
+// keys, such as arrow and function keys, are ignored.
+fn apply_preview_key(cli: &mut Cli, key: KeyEvent) -> bool {
+    match key.code {
+        // Quit on q, escape or Ctrl+C.
+        KeyCode::Char('c') if key.modifiers.contains(KeyModifiers::CONTROL) => return false,
+        KeyCode::Char('q') | KeyCode::Esc => return false,
+        KeyCode::Char('a') => cli.angular_buckets = cli.angular_buckets.saturating_sub(1),
+        KeyCode::Char('A') => cli.angular_buckets = (cli.angular_buckets + 1).min(MAXIMUM_BUCKETS),
+        KeyCode::Char('r') => cli.radial_buckets = cli.radial_buckets.saturating_sub(1),
+        KeyCode::Char('R') => cli.radial_buckets = (cli.radial_buckets + 1).min(MAXIMUM_BUCKETS),
+        KeyCode::Char('f') => adjust_fixed(cli, -FIXED_STEP),
+        KeyCode::Char('F') => adjust_fixed(cli, FIXED_STEP),
+        KeyCode::Char('t') => cycle_color_wheel_type(cli, 1),
+        KeyCode::Char('T') => cycle_color_wheel_type(cli, -1),
+        _ => {}
+    }
+
+    true
+}
+
+fn adjust_fixed(cli: &mut Cli, step: f64) {
+    let range = cli.color_wheel_type.domain.fixed;
+    let adjust = |fixed: f64| {
+        (fixed + step * (range.maximum - range.minimum)).clamp(range.minimum, range.maximum)
+    };
+
+    for fixed in cli.fixed.iter_mut() {
+        *fixed = adjust(*fixed);
+    }
+    for ring in cli.ring.iter_mut() {
+        ring.fixed = adjust(ring.fixed);
+    }
+}
+
+fn cycle_color_wheel_type(cli: &mut Cli, step: isize) {
+    let registry = PixelGeneratorRegistry::default();
+    let types = registry.registrations();
+    let index = types
+        .iter()
+        .position(|color_wheel_type| color_wheel_type.name == cli.color_wheel_type.name)
+        .unwrap_or(0);
+    cli.color_wheel_type = types[(index as isize + step).rem_euclid(types.len() as isize) as usize];
+
+    // Keep the fixed values valid for the new color wheel type.
+    let range = cli.color_wheel_type.domain.fixed;
+    for fixed in cli.fixed.iter_mut() {
+        *fixed = fixed.clamp(range.minimum, range.maximum);
+    }
+    for ring in cli.ring.iter_mut() {
+        ring.fixed = ring.fixed.clamp(range.minimum, range.maximum);
+    }
+}
+
+// Reads key presses without waiting for enter or echoing them, until dropped.
+struct RawTerminal;
+
+impl RawTerminal {
+    fn enable() -> io::Result<RawTerminal> {
+        terminal::enable_raw_mode()?;
+        crossterm::execute!(io::stdout(), cursor::Hide)?;
+
+        Ok(RawTerminal)
+    }
+}
+
+impl Drop for RawTerminal {
+    fn drop(&mut self) {
+        // Restoring the terminal is best effort, as there's nothing more to do if it fails.
+        let _ = crossterm::execute!(io::stdout(), cursor::Show);
+        let _ = terminal::disable_raw_mode();
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use clap::Parser;
+
+    use super::*;
+
+    fn parse(args: &[&str]) -> Cli {
+        Cli::parse_from(["color-wheel-cli"].iter().chain(args))
+    }
+
+    fn key(character: char) -> KeyEvent {
+        KeyEvent::new(KeyCode::Char(character), KeyModifiers::NONE)
+    }
+
+    #[test]
+    fn it_should_parse_preview_after_render_options() {
+        let cli = parse(&[
+            "hsl-fixed-lightness",
+            "-a",
+            "12",
+            "preview",
+            "--columns",
+            "30",
+        ]);
+
+        assert_eq!(cli.angular_buckets, 12);
+        assert_eq!(
+            cli.command,
+            Some(Command::Preview {
+                protocol: PreviewProtocolType::Auto,
+                columns: 30
+            })
+        );
+    }
+
+    #[test]
+    fn it_should_detect_protocol_from_environment() {
+        let environment = |variables: &'static [(&'static str, &'static str)]| {
+            move |name: &str| {
+                variables
+                    .iter()
+                    .find(|(key, _)| *key == name)
+                    .map(|(_, value)| value.to_string())
+            }
+        };
+
+        let detect = |variables| detect_protocol(PreviewProtocolType::Auto, environment(variables));
+        assert_eq!(
+            detect(&[("TERM", "xterm-kitty")]),
+            PreviewProtocolType::Kitty
+        );
+        assert_eq!(detect(&[("TERM", "foot")]), PreviewProtocolType::Sixel);
+        assert_eq!(
+            detect(&[("TERM", "xterm-256color"), ("TERM_PROGRAM", "WezTerm")]),
+            PreviewProtocolType::Sixel
+        );
+        assert_eq!(
+            detect(&[("TERM", "xterm-256color")]),
+            PreviewProtocolType::HalfBlocks
+        );
+        assert_eq!(
+            detect_protocol(
+                PreviewProtocolType::Sixel,
+                environment(&[("TERM", "xterm-kitty")])
+            ),
+            PreviewProtocolType::Sixel
+        );
+    }
+
+    #[test]
+    fn it_should_adjust_options_for_keys() {
+        let mut cli = parse(&["hsl-fixed-lightness", "-f", "0.98", "preview"]);
+
+        assert!(apply_preview_key(&mut cli, key('A')));
+        assert!(apply_preview_key(&mut cli, key('R')));
+        assert!(apply_preview_key(&mut cli, key('r')));
+        assert!(apply_preview_key(&mut cli, key('F')));
+        assert!(apply_preview_key(&mut cli, key('t')));
+
+        assert_eq!(cli.angular_buckets, 1);
+        assert_eq!(cli.radial_buckets, 0);
+        assert_eq!(cli.fixed, vec![1.]);
+        assert_eq!(cli.color_wheel_type.name, "hsv-fixed-saturation");
+    }
+
+    #[test]
+    fn it_should_wrap_when_cycling_types() {
+        let mut cli = parse(&["hsl-fixed-saturation", "preview"]);
+        apply_preview_key(&mut cli, key('T'));
+        assert_eq!(cli.color_wheel_type.name, "oklab-fixed-chroma");
+    }
+
+    #[test]
+    fn it_should_keep_fixed_values_in_range_when_cycling_types() {
+        let mut cli = parse(&["oklab-fixed-lightness", "-f", "0.8", "preview"]);
+        apply_preview_key(&mut cli, key('t'));
+        assert_eq!(cli.color_wheel_type.name, "oklab-fixed-chroma");
+        assert_eq!(cli.fixed, vec![0.5]);
+    }
+
+    #[test]
+    fn it_should_quit_on_q_escape_or_ctrl_c() {
+        let mut cli = parse(&["hsl-fixed-lightness", "preview"]);
+        assert!(!apply_preview_key(&mut cli, key('q')));
+        assert!(!apply_preview_key(
+            &mut cli,
+            KeyEvent::new(KeyCode::Esc, KeyModifiers::NONE)
+        ));
+        assert!(!apply_preview_key(
+            &mut cli,
+            KeyEvent::new(KeyCode::Char('c'), KeyModifiers::CONTROL)
+        ));
+    }
+
+    #[test]
+    fn it_should_ignore_arrow_and_function_keys() {
+        let mut cli = parse(&["hsl-fixed-lightness", "preview"]);
+        for code in [KeyCode::Up, KeyCode::Left, KeyCode::F(1), KeyCode::Home] {
+            assert!(apply_preview_key(
+                &mut cli,
+                KeyEvent::new(code, KeyModifiers::NONE)
+            ));
+        }
+        assert!(apply_preview_key(&mut cli, key('c')));
+    }
+}
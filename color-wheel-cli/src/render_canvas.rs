@@ -0,0 +1,20 @@
+use color_wheel_lib::{
+    canvas::Canvas, canvas_filters::CanvasFilter, color_wheel_error::ColorWheelError,
+    render_color_wheel_spec::render_color_wheel_spec,
+};
+
+use crate::{
+    cli::Cli, create_canvas_filter::create_canvas_filter,
+    create_color_wheel_spec::create_color_wheel_spec,
+};
+
+// Renders the color wheels through the same pipeline as a still image, without timings.
+pub fn render_canvas(cli: &Cli) -> Result<Canvas, ColorWheelError> {
+    let mut canvas = render_color_wheel_spec(&create_color_wheel_spec(cli))?;
+
+    if !cli.filter.is_empty() {
+        create_canvas_filter(cli).execute(&mut canvas);
+    }
+
+    Ok(canvas)
+}
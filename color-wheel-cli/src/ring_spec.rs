@@ -0,0 +1,115 @@
+use clap::ValueEnum;
+use color_wheel_lib::color_wheel_spec::MAXIMUM_BUCKETS;
+
+use crate::cli::BucketSampleType;
+
+#[derive(Clone, Copy, PartialEq, Debug)]
+pub struct RingSpec {
+    pub fixed: f64,
+    pub weight: f64,
+    pub angular_buckets: Option<u32>,
+    pub radial_buckets: Option<u32>,
+    pub angular_sample: Option<BucketSampleType>,
+    pub radial_sample: Option<BucketSampleType>,
+}
+
+// Parses a ring in the form `FIXED[:OPTION=VALUE,...]`, for example `0.5:weight=2,radial-buckets=3`.
+pub fn parse_ring_spec(value: &str) -> Result<RingSpec, String> {
+    let (fixed, options) = match value.split_once(':') {
+        Some((fixed, options)) => (fixed, options.split(',').collect::<Vec<_>>()),
+        None => (value, vec![]),
+    };
+
+    let mut spec = RingSpec {
+        fixed: fixed
+            .parse()
+            .map_err(|_| format!("`{fixed}` isn't a number"))?,
+        weight: 1.,
+        angular_buckets: None,
+        radial_buckets: None,
+        angular_sample: None,
+        radial_sample: None,
+    };
+
+    for option in options {
+        let (name, value) = option
+            .split_once('=')
+            .ok_or_else(|| format!("`{option}` isn't in the form OPTION=VALUE"))?;
+
+        match name {
+            "weight" => {
+                spec.weight = value
+                    .parse()
+                    .ok()
+                    .filter(|weight: &f64| *weight > 0.)
+                    .ok_or_else(|| format!("`{value}` isn't a positive number"))?;
+            }
+            "angular-buckets" => spec.angular_buckets = Some(parse_buckets(value)?),
+            "radial-buckets" => spec.radial_buckets = Some(parse_buckets(value)?),
+            "angular-sample" => spec.angular_sample = Some(BucketSampleType::from_str(value, true)?),
+            "radial-sample" => spec.radial_sample = Some(BucketSampleType::from_str(value, true)?),
+            _ => {
+                return Err(format!(
+                    "`{name}` isn't one of weight, angular-buckets, radial-buckets, angular-sample or radial-sample"
+                ))
+            }
+        }
+    }
+
+    Ok(spec)
+}
+
+fn parse_buckets(value: &str) -> Result<u32, String> {
+    value
+        .parse()
+        .ok()
+        .filter(|buckets| *buckets <= MAXIMUM_BUCKETS)
+        .ok_or_else(|| format!("`{value}` isn't a number of buckets from 0 to {MAXIMUM_BUCKETS}"))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn it_should_parse_fixed_value() {
+        assert_eq!(
+            parse_ring_spec("0.5"),
+            Ok(RingSpec {
+                fixed: 0.5,
+                weight: 1.,
+                angular_buckets: None,
+                radial_buckets: None,
+                angular_sample: None,
+                radial_sample: None,
+            })
+        );
+    }
+
+    #[test]
+    fn it_should_parse_options() {
+        assert_eq!(
+            parse_ring_spec(
+                "1:weight=3,angular-buckets=12,radial-buckets=8,angular-sample=center,radial-sample=floor"
+            ),
+            Ok(RingSpec {
+                fixed: 1.,
+                weight: 3.,
+                angular_buckets: Some(12),
+                radial_buckets: Some(8),
+                angular_sample: Some(BucketSampleType::Center),
+                radial_sample: Some(BucketSampleType::Floor),
+            })
+        );
+    }
+
+    #[test]
+    fn it_should_reject_invalid_rings() {
+        assert!(parse_ring_spec("").is_err());
+        assert!(parse_ring_spec("0.5:weight").is_err());
+        assert!(parse_ring_spec("0.5:weight=0").is_err());
+        assert!(parse_ring_spec("0.5:radial-buckets=-1").is_err());
+        assert!(parse_ring_spec("0.5:angular-sample=middle").is_err());
+        assert!(parse_ring_spec("0.5:size=2").is_err());
+    }
+}
@@ -0,0 +1,485 @@
+use std::{
+    collections::{hash_map::DefaultHasher, HashMap},
+    hash::{Hash, Hasher},
+    io::{Read, Write},
+    net::{TcpListener, TcpStream},
+    sync::{
+        atomic::{AtomicU32, Ordering},
+        Arc, Mutex,
+    },
+    thread,
+    time::Duration,
+};
+
+use clap::Parser;
+use color_wheel_lib::canvas::Canvas;
+
+use crate::{
+    base64::base64,
+    cli::{try_process_cli_options, Cli, Command},
+    cli_error::CliError,
+    create_color_wheel_spec::create_color_wheel_spec,
+    render_canvas::render_canvas,
+    serve_query::query_to_args,
+};
+
+const REQUEST_TIMEOUT: Duration = Duration::from_secs(10);
+const MAXIMUM_REQUEST_SIZE: usize = 16 * 1024;
+
+// Each simulation renders another copy of the color wheels.
+const MAXIMUM_SIMULATIONS: usize = 4;
+
+// Each fixed value or ring renders another wheel.
+const MAXIMUM_COLOR_WHEELS: usize = 16;
+
+// The supersampled canvas, including every simulated copy, may be at most this many times
+// the area of an image of the largest diameter.
+const MAXIMUM_CANVAS_AREA_FACTOR: u64 = 4;
+
+#[derive(Clone, Debug)]
+struct ServerOptions {
+    default_color_wheel_type: String,
+    max_diameter: u32,
+    cache_size: usize,
+    max_connections: u32,
+}
+
+#[derive(Clone, PartialEq, Debug)]
+struct Response {
+    status: &'static str,
+    content_type: &'static str,
+    body: Vec<u8>,
+}
+
+type ResponseCache = Mutex<HashMap<u64, Response>>;
+
+// One of the connections which may be handled at once, given back when dropped.
+struct ConnectionSlot(Arc<AtomicU32>);
+
+impl ConnectionSlot {
+    fn try_acquire(active_connections: &Arc<AtomicU32>, max_connections: u32) -> Option<Self> {
+        active_connections
+            .fetch_update(Ordering::SeqCst, Ordering::SeqCst, |active| {
+                (active < max_connections).then_some(active + 1)
+            })
+            .ok()
+            .map(|_| ConnectionSlot(active_connections.clone()))
+    }
+}
+
+impl Drop for ConnectionSlot {
+    fn drop(&mut self) {
+        self.0.fetch_sub(1, Ordering::SeqCst);
+    }
+}
+
+pub fn serve(cli: Cli) -> Result<(), CliError> {
+    let Some(Command::Serve {
+        address,
+        max_diameter,
+        cache_size,
+        max_connections,
+    }) = cli.command
+    else {
+        return Ok(());
+    };
+
+    let options = ServerOptions {
+        default_color_wheel_type: cli.color_wheel_type.name.to_string(),
+        max_diameter,
+        cache_size,
+        max_connections,
+    };
+
+    let listener =
+        TcpListener::bind(&address).map_err(|error| CliError::listen(&address, error))?;
+    println!("Serving color wheels at http://{address}/wheel.png");
+    run_server(listener, options);
+
+    Ok(())
+}
+
+// Handles each connection on its own thread. Rendering is itself parallel, so this mostly
+// stops slow clients from blocking others. Connections beyond the limit are turned away
+// rather than queued, as each may render an image of the largest size.
+fn run_server(listener: TcpListener, options: ServerOptions) {
+    let options = Arc::new(options);
+    let cache = Arc::new(ResponseCache::default());
+    let active_connections = Arc::new(AtomicU32::new(0));
+
+    for stream in listener.incoming().flatten() {
+        let slot = ConnectionSlot::try_acquire(&active_connections, options.max_connections);
+        let options = options.clone();
+        let cache = cache.clone();
+        thread::spawn(move || match slot {
+            Some(_slot) => handle_connection(stream, &options, &cache),
+            None => reject_connection(stream),
+        });
+    }
+}
+
+fn handle_connection(mut stream: TcpStream, options: &ServerOptions, cache: &ResponseCache) {
+    stream.set_read_timeout(Some(REQUEST_TIMEOUT)).ok();
+
+    let response = match read_request_target(&mut stream) {
+        Ok(target) => respond(&target, options, cache),
+        Err(response) => response,
+    };
+
+    write_response(&mut stream, &response);
+}
+
+// Reads the request before answering, as closing a connection with unread data resets it
+// rather than delivering the response.
+fn reject_connection(mut stream: TcpStream) {
+    stream.set_read_timeout(Some(REQUEST_TIMEOUT)).ok();
+    read_request_target(&mut stream).ok();
+
+    write_response(
+        &mut stream,
+        &error_response(
+            "503 Service Unavailable",
+            "Too many requests, try again later",
+        ),
+    );
+}
+
+fn write_response(stream: &mut TcpStream, response: &Response) {
+    let header = format!(
+        "HTTP/1.1 {}\r\nContent-Type: {}\r\nContent-Length: {}\r\nConnection: close\r\n\r\n",
+        response.status,
+        response.content_type,
+        response.body.len()
+    );
+    stream
+        .write_all(header.as_bytes())
+        .and_then(|_| stream.write_all(&response.body))
+        .ok();
+}
+
+// Reads the request headers and returns the request's path and query.
+fn read_request_target(stream: &mut TcpStream) -> Result<String, Response> {
+    let mut request = vec![];
+    let mut buffer = [0; 1024];
+    while !request.windows(4).any(|window| window == b"\r\n\r\n") {
+        let length = stream
+            .read(&mut buffer)
+            .map_err(|_| error_response("400 Bad Request", "Failed to read request"))?;
+        if length == 0 {
+            break;
+        }
+
+        request.extend_from_slice(&buffer[..length]);
+        if request.len() > MAXIMUM_REQUEST_SIZE {
+            return Err(error_response(
+                "431 Request Header Fields Too Large",
+                "Request is too large",
+            ));
+        }
+    }
+
+    let request = String::from_utf8_lossy(&request);
+    let mut request_line = request.lines().next().unwrap_or_default().split(' ');
+    match (request_line.next(), request_line.next()) {
+        (Some("GET"), Some(target)) => Ok(target.to_string()),
+        (Some(_), Some(_)) => Err(error_response(
+            "405 Method Not Allowed",
+            "Only GET is supported",
+        )),
+        _ => Err(error_response("400 Bad Request", "Malformed request")),
+    }
+}
+
+fn respond(target: &str, options: &ServerOptions, cache: &ResponseCache) -> Response {
+    let (path, query) = target.split_once('?').unwrap_or((target, ""));
+    let is_svg = match path {
+        "/wheel.png" => false,
+        "/wheel.svg" => true,
+        _ => return error_response("404 Not Found", "Use /wheel.png or /wheel.svg"),
+    };
+
+    let args = match query_to_args(query, &options.default_color_wheel_type) {
+        Ok(args) => args,
+        Err(message) => return error_response("400 Bad Request", &message),
+    };
+
+    let mut hasher = DefaultHasher::new();
+    (is_svg, &args).hash(&mut hasher);
+    let key = hasher.finish();
+
+    if let Some(response) = cache.lock().unwrap().get(&key) {
+        return response.clone();
+    }
+
+    let cli = match Cli::try_parse_from(&args).and_then(try_process_cli_options) {
+        Ok(cli) => cli,
+        Err(error) => return error_response("400 Bad Request", &error.to_string()),
+    };
+
+    if let Err(message) = validate_image_size(&cli, options.max_diameter) {
+        return error_response("400 Bad Request", &message);
+    }
+
+    let canvas = match render_canvas(&cli) {
+        Ok(canvas) => canvas,
+        Err(error) => return error_response("400 Bad Request", &error.to_string()),
+    };
+
+    let png = match encode_png(&canvas) {
+        Ok(png) => png,
+        Err(error) => {
+            return error_response(
+                "500 Internal Server Error",
+                &format!("Failed to encode the color wheel: {error}"),
+            )
+        }
+    };
+    let response = if is_svg {
+        Response {
+            status: "200 OK",
+            content_type: "image/svg+xml",
+            body: wrap_png_in_svg(&png, canvas.width(), canvas.height()).into_bytes(),
+        }
+    } else {
+        Response {
+            status: "200 OK",
+            content_type: "image/png",
+            body: png,
+        }
+    };
+
+    let mut cache = cache.lock().unwrap();
+    if cache.len() >= options.cache_size {
+        cache.clear();
+    }
+    if options.cache_size > 0 {
+        cache.insert(key, response.clone());
+    }
+
+    response
+}
+
+fn validate_image_size(cli: &Cli, max_diameter: u32) -> Result<(), String> {
+    if cli.diameter > max_diameter || cli.margin > max_diameter {
+        return Err(format!(
+            "Diameter and margin must be at most {max_diameter} pixels"
+        ));
+    }
+
+    if cli.simulate.len() > MAXIMUM_SIMULATIONS {
+        return Err(format!(
+            "At most {MAXIMUM_SIMULATIONS} simulations may be requested"
+        ));
+    }
+
+    if cli.fixed.len() > MAXIMUM_COLOR_WHEELS || cli.ring.len() > MAXIMUM_COLOR_WHEELS {
+        return Err(format!(
+            "At most {MAXIMUM_COLOR_WHEELS} fixed values or rings may be requested"
+        ));
+    }
+
+    let side = cli.supersampling as u64 * (cli.diameter as u64 + 2 * cli.margin as u64);
+    let area = side * side * create_color_wheel_spec(cli).set_count() as u64;
+    let maximum_area = MAXIMUM_CANVAS_AREA_FACTOR * (max_diameter as u64).pow(2);
+    if area > maximum_area {
+        return Err(format!(
+            "The supersampled image may have at most {maximum_area} pixels, so reduce the diameter, margin, supersampling or simulations"
+        ));
+    }
+
+    Ok(())
+}
+
+fn error_response(status: &'static str, message: &str) -> Response {
+    Response {
+        status,
+        content_type: "text/plain; charset=utf-8",
+        body: format!("{message}\n").into_bytes(),
+    }
+}
+
+fn encode_png(canvas: &Canvas) -> Result<Vec<u8>, png::EncodingError> {
+    let mut png = vec![];
+    let mut encoder = png::Encoder::new(&mut png, canvas.width(), canvas.height());
+    encoder.set_color(png::ColorType::Rgba);
+    encoder.set_depth(png::BitDepth::Eight);
+
+    let mut writer = encoder.write_header()?;
+    writer.write_image_data(canvas.data())?;
+    writer.finish()?;
+
+    Ok(png)
+}
+
+// The color wheels are raster images, so for pages which can only embed SVG, this wraps the
+// PNG in an SVG. It isn't vector, so it's a third larger than the PNG and doesn't scale.
+fn wrap_png_in_svg(png: &[u8], width: u32, height: u32) -> String {
+    format!(
+        "<svg xmlns=\"http://www.w3.org/2000/svg\" width=\"{width}\" height=\"{height}\" viewBox=\"0 0 {width} {height}\">\
+         <image width=\"{width}\" height=\"{height}\" href=\"data:image/png;base64,{}\"/></svg>\n",
+        base64(png)
+    )
+}
+
+#[cfg(test)]
+mod tests {
+    use std::net::SocketAddr;
+
+    use super::*;
+
+    const PNG_SIGNATURE: &[u8] = b"\x89PNG\r\n\x1a\n";
+
+    fn options() -> ServerOptions {
+        ServerOptions {
+            default_color_wheel_type: "hsl-fixed-lightness".to_string(),
+            max_diameter: 100,
+            cache_size: 10,
+            max_connections: 4,
+        }
+    }
+
+    fn request(address: SocketAddr, target: &str) -> Vec<u8> {
+        let mut stream = TcpStream::connect(address).unwrap();
+        stream
+            .write_all(format!("GET {target} HTTP/1.1\r\nHost: localhost\r\n\r\n").as_bytes())
+            .unwrap();
+        let mut response = vec![];
+        stream.read_to_end(&mut response).unwrap();
+        response
+    }
+
+    #[test]
+    fn it_should_serve_png_over_http() {
+        let listener = TcpListener::bind("127.0.0.1:0").unwrap();
+        let address = listener.local_addr().unwrap();
+        thread::spawn(move || run_server(listener, options()));
+
+        let response = request(address, "/wheel.png?d=21&m=0&s=1&a=6");
+
+        let header_end = response.windows(4).position(|w| w == b"\r\n\r\n").unwrap() + 4;
+        let header = String::from_utf8_lossy(&response[..header_end]);
+        assert!(header.starts_with("HTTP/1.1 200 OK\r\n"));
+        assert!(header.contains("Content-Type: image/png\r\n"));
+        assert!(response[header_end..].starts_with(PNG_SIGNATURE));
+    }
+
+    #[test]
+    fn it_should_reject_connections_beyond_the_limit() {
+        let listener = TcpListener::bind("127.0.0.1:0").unwrap();
+        let address = listener.local_addr().unwrap();
+        thread::spawn(move || {
+            run_server(
+                listener,
+                ServerOptions {
+                    max_connections: 1,
+                    ..options()
+                },
+            )
+        });
+
+        // Holds the only connection until it's dropped, by not sending a request.
+        let slow_client = TcpStream::connect(address).unwrap();
+        let response = request(address, "/wheel.png?d=21&m=0&s=1");
+        assert!(response.starts_with(b"HTTP/1.1 503 Service Unavailable\r\n"));
+
+        drop(slow_client);
+        let response = (0..100)
+            .map(|_| {
+                thread::sleep(Duration::from_millis(10));
+                request(address, "/wheel.png?d=21&m=0&s=1")
+            })
+            .find(|response| !response.starts_with(b"HTTP/1.1 503"))
+            .unwrap();
+        assert!(response.starts_with(b"HTTP/1.1 200 OK\r\n"));
+    }
+
+    #[test]
+    fn it_should_return_encoding_errors() {
+        // A canvas without any pixels can't be encoded.
+        assert!(encode_png(&Canvas::new(0, 0)).is_err());
+        assert!(encode_png(&Canvas::new(2, 2))
+            .unwrap()
+            .starts_with(PNG_SIGNATURE));
+    }
+
+    #[test]
+    fn it_should_return_an_error_when_the_address_is_in_use() {
+        let listener = TcpListener::bind("127.0.0.1:0").unwrap();
+        let address = listener.local_addr().unwrap().to_string();
+        let cli = Cli::try_parse_from([
+            "color-wheel-cli",
+            "hsv-fixed-value",
+            "serve",
+            "--address",
+            &address,
+        ])
+        .unwrap();
+
+        assert!(matches!(serve(cli), Err(CliError::Listen { .. })));
+    }
+
+    #[test]
+    fn it_should_cache_responses() {
+        let cache = ResponseCache::default();
+
+        let first = respond("/wheel.svg?d=11&m=0&s=1", &options(), &cache);
+        let second = respond("/wheel.svg?d=11&m=0&s=1", &options(), &cache);
+
+        assert_eq!(first.status, "200 OK");
+        assert_eq!(first.content_type, "image/svg+xml");
+        assert!(String::from_utf8(first.body.clone())
+            .unwrap()
+            .contains("width=\"11\""));
+        assert_eq!(first, second);
+        assert_eq!(cache.lock().unwrap().len(), 1);
+    }
+
+    #[test]
+    fn it_should_reject_invalid_requests() {
+        let cache = ResponseCache::default();
+
+        assert_eq!(
+            respond("/wheel.png?a=5000", &options(), &cache).status,
+            "400 Bad Request"
+        );
+        assert_eq!(
+            respond("/wheel.png?type=rgb", &options(), &cache).status,
+            "400 Bad Request"
+        );
+        assert_eq!(
+            respond("/wheel.png?d=101", &options(), &cache).status,
+            "400 Bad Request"
+        );
+        assert_eq!(
+            respond(
+                "/wheel.png?d=20&m=0&ring-gap=30&f=0.5&f=1&e",
+                &options(),
+                &cache
+            )
+            .status,
+            "400 Bad Request"
+        );
+        assert_eq!(
+            respond("/wheel.png?d=100&m=100&s=8", &options(), &cache).status,
+            "400 Bad Request"
+        );
+        assert_eq!(
+            respond(
+                &format!("/wheel.png?d=20&m=0&s=1&e{}", "&f=0.5".repeat(17)),
+                &options(),
+                &cache
+            )
+            .status,
+            "400 Bad Request"
+        );
+        assert_eq!(
+            respond("/wheel.png?threads=64", &options(), &cache).status,
+            "400 Bad Request"
+        );
+        assert_eq!(
+            respond("/other.png", &options(), &cache).status,
+            "404 Not Found"
+        );
+        assert!(cache.lock().unwrap().is_empty());
+    }
+}
@@ -0,0 +1,177 @@
+// The only options which may be given as query parameters. Others aren't useful for a single
+// image, refer to local files or the server's threads, or may take too long to render.
+const ALLOWED_PARAMETERS: [&str; 39] = [
+    "a",
+    "r",
+    "f",
+    "d",
+    "m",
+    "e",
+    "c",
+    "b",
+    "s",
+    "angular-buckets",
+    "radial-buckets",
+    "angular-bucket-spacing",
+    "angular-bucket-sample",
+    "radial-bucket-sample",
+    "angular-bucket-boundaries",
+    "radial-bucket-boundaries",
+    "angular-bucket-samples",
+    "radial-bucket-samples",
+    "fixed",
+    "diameter",
+    "margin",
+    "expand",
+    "projection",
+    "inner-radius",
+    "start-angle",
+    "end-angle",
+    "rotation",
+    "ring-gap",
+    "radial-mapping",
+    "ring",
+    "reverse-radial-colors",
+    "reverse-radial-bucketing",
+    "supersampling",
+    "resample-filter",
+    "background",
+    "dither",
+    "simulate",
+    "simulation-severity",
+    "compare-simulations",
+];
+
+// Converts URL query parameters into command line arguments, so that they are validated
+// exactly as on the command line. Single letter parameters become short options, such as
+// `a=12` to `-a=12`, and the `type` parameter gives the color wheel type.
+pub fn query_to_args(query: &str, default_color_wheel_type: &str) -> Result<Vec<String>, String> {
+    let mut color_wheel_type = default_color_wheel_type.to_string();
+    let mut args = vec![];
+
+    for parameter in query.split('&').filter(|parameter| !parameter.is_empty()) {
+        let (key, value) = parameter.split_once('=').unwrap_or((parameter, ""));
+        let key = percent_decode(key)?;
+        let value = percent_decode(value)?;
+
+        if key == "type" {
+            color_wheel_type = value;
+            continue;
+        }
+
+        if !ALLOWED_PARAMETERS.contains(&key.as_str()) {
+            return Err(format!("Parameter `{key}` isn't allowed"));
+        }
+
+        let option = if key.chars().count() == 1 {
+            format!("-{key}")
+        } else {
+            format!("--{key}")
+        };
+
+        // Flags are given without a value or as `true`.
+        match value.as_str() {
+            "" | "true" => args.push(option),
+            "false" => {}
+            _ => args.push(format!("{option}={value}")),
+        }
+    }
+
+    Ok([String::from("color-wheel-cli"), color_wheel_type]
+        .into_iter()
+        .chain(args)
+        .collect())
+}
+
+fn percent_decode(value: &str) -> Result<String, String> {
+    let mut bytes = vec![];
+    let mut input = value.bytes();
+    while let Some(byte) = input.next() {
+        match byte {
+            b'+' => bytes.push(b' '),
+            b'%' => {
+                let hex = [input.next(), input.next()];
+                let decoded = match hex {
+                    [Some(high), Some(low)] => std::str::from_utf8(&[high, low])
+                        .ok()
+                        .and_then(|hex| u8::from_str_radix(hex, 16).ok()),
+                    _ => None,
+                };
+                bytes.push(decoded.ok_or(format!("`{value}` isn't correctly encoded"))?);
+            }
+            _ => bytes.push(byte),
+        }
+    }
+
+    String::from_utf8(bytes).map_err(|_| format!("`{value}` isn't valid UTF-8"))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn it_should_convert_parameters_to_options() {
+        assert_eq!(
+            query_to_args(
+                "type=oklab-fixed-lightness&fixed=0.7&a=12&expand&reverse-radial-colors=false",
+                "hsl-fixed-saturation"
+            ),
+            Ok(vec![
+                "color-wheel-cli".to_string(),
+                "oklab-fixed-lightness".to_string(),
+                "--fixed=0.7".to_string(),
+                "-a=12".to_string(),
+                "--expand".to_string(),
+            ])
+        );
+    }
+
+    #[test]
+    fn it_should_use_default_type() {
+        assert_eq!(
+            query_to_args("", "hsl-fixed-saturation"),
+            Ok(vec![
+                "color-wheel-cli".to_string(),
+                "hsl-fixed-saturation".to_string()
+            ])
+        );
+    }
+
+    #[test]
+    fn it_should_decode_values() {
+        assert_eq!(
+            query_to_args("background=%23ffffff&start-angle=-90", "hsv-fixed-value")
+                .unwrap()
+                .split_off(2),
+            vec!["--background=#ffffff", "--start-angle=-90"]
+        );
+    }
+
+    #[test]
+    fn it_should_reject_invalid_parameters() {
+        assert!(query_to_args("output=/tmp/x.png", "hsv-fixed-value").is_err());
+        assert!(query_to_args("--help", "hsv-fixed-value").is_err());
+        assert!(query_to_args("fixed=%zz", "hsv-fixed-value").is_err());
+    }
+
+    #[test]
+    fn it_should_only_allow_render_options() {
+        for parameter in [
+            "o=x.png",
+            "threads=64",
+            "animate=rotation",
+            "frame-sequence",
+            "filter=shadow",
+            "preview",
+        ] {
+            assert!(
+                query_to_args(parameter, "hsv-fixed-value").is_err(),
+                "{parameter}"
+            );
+        }
+        assert!(
+            query_to_args("compare-simulations&simulate=protanopia", "hsv-fixed-value").is_ok()
+        );
+    }
+}
@@ -0,0 +1,61 @@
+use std::fmt::Write;
+
+use color_wheel_lib::canvas::Canvas;
+
+// Pixels with less alpha than this are left as the terminal's own background.
+const ALPHA_THRESHOLD: u8 = 128;
+
+// Draws the canvas with 24-bit colored half block characters, two pixels per character.
+pub fn encode_half_blocks(canvas: &Canvas) -> String {
+    let mut output = String::new();
+    let width = canvas.width() as usize;
+
+    let rows = canvas.data().chunks_exact(width * 4).collect::<Vec<_>>();
+    for row_pair in rows.chunks(2) {
+        for x in 0..width {
+            let top = pixel_color(row_pair[0], x);
+            let bottom = row_pair.get(1).and_then(|row| pixel_color(row, x));
+
+            match (top, bottom) {
+                (Some([r, g, b]), Some([br, bg, bb])) => {
+                    write!(output, "\x1b[38;2;{r};{g};{b}m\x1b[48;2;{br};{bg};{bb}m▀").unwrap()
+                }
+                (Some([r, g, b]), None) => {
+                    write!(output, "\x1b[0m\x1b[38;2;{r};{g};{b}m▀").unwrap()
+                }
+                (None, Some([r, g, b])) => {
+                    write!(output, "\x1b[0m\x1b[38;2;{r};{g};{b}m▄").unwrap()
+                }
+                (None, None) => output.push_str("\x1b[0m "),
+            }
+        }
+        output.push_str("\x1b[0m\r\n");
+    }
+
+    output
+}
+
+fn pixel_color(row: &[u8], x: usize) -> Option<[u8; 3]> {
+    let pixel = &row[x * 4..x * 4 + 4];
+    (pixel[3] >= ALPHA_THRESHOLD).then(|| [pixel[0], pixel[1], pixel[2]])
+}
+
+#[cfg(test)]
+mod tests {
+    use color_wheel_lib::pixel::Pixel;
+
+    use super::*;
+
+    #[test]
+    fn it_should_draw_two_rows_per_line() {
+        let mut canvas = Canvas::new(2, 2);
+        canvas.set_pixel(0, 0, Pixel::rgb(1, 2, 3));
+        canvas.set_pixel(0, 1, Pixel::rgb(4, 5, 6));
+        canvas.set_pixel(1, 1, Pixel::rgb(7, 8, 9));
+
+        assert_eq!(
+            encode_half_blocks(&canvas),
+            "\x1b[38;2;1;2;3m\x1b[48;2;4;5;6m▀\x1b[0m\x1b[38;2;7;8;9m▄\x1b[0m\r\n"
+        );
+    }
+}
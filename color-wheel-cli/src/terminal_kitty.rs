@@ -0,0 +1,51 @@
+use std::fmt::Write;
+
+use color_wheel_lib::canvas::Canvas;
+
+use crate::base64::base64;
+
+// The Kitty graphics protocol limits the size of each escape sequence's payload.
+const CHUNK_SIZE: usize = 4096;
+
+// Draws the canvas with the Kitty graphics protocol as raw RGBA data.
+pub fn encode_kitty(canvas: &Canvas) -> String {
+    let encoded = base64(canvas.data());
+    let chunks = encoded.as_bytes().chunks(CHUNK_SIZE).collect::<Vec<_>>();
+
+    let mut output = String::new();
+    for (index, chunk) in chunks.iter().enumerate() {
+        let more = if index + 1 < chunks.len() { 1 } else { 0 };
+        let chunk = std::str::from_utf8(chunk).unwrap();
+        if index == 0 {
+            write!(
+                output,
+                "\x1b_Ga=T,f=32,s={},v={},m={more};{chunk}\x1b\\",
+                canvas.width(),
+                canvas.height()
+            )
+            .unwrap();
+        } else {
+            write!(output, "\x1b_Gm={more};{chunk}\x1b\\").unwrap();
+        }
+    }
+
+    output
+}
+
+// Removes all images previously drawn with the Kitty graphics protocol.
+pub const CLEAR_KITTY_IMAGES: &str = "\x1b_Ga=d\x1b\\";
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn it_should_split_large_images_into_chunks() {
+        let canvas = Canvas::new(32, 32);
+        let kitty = encode_kitty(&canvas);
+
+        assert!(kitty.starts_with("\x1b_Ga=T,f=32,s=32,v=32,m=1;AAAA"));
+        assert_eq!(kitty.matches("\x1b_G").count(), 2);
+        assert!(kitty.contains("\x1b_Gm=0;"));
+    }
+}
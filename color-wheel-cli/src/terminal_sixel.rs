@@ -0,0 +1,128 @@
+use std::{collections::BTreeSet, fmt::Write};
+
+use color_wheel_lib::canvas::Canvas;
+
+// Pixels with less alpha than this are left transparent.
+const ALPHA_THRESHOLD: u8 = 128;
+
+// Number of levels of each channel in the palette's color cube.
+const CUBE_LEVELS: u32 = 6;
+
+// Each Sixel character covers a column of this many pixels.
+const SIXEL_HEIGHT: usize = 6;
+
+// Draws the canvas with the Sixel graphics protocol, using a color cube palette.
+pub fn encode_sixel(canvas: &Canvas) -> String {
+    let width = canvas.width() as usize;
+    let height = canvas.height() as usize;
+
+    // Background pixels are left transparent rather than drawn in color 0.
+    let mut output = String::from("\x1bP0;1;0q");
+    write!(output, "\"1;1;{width};{height}").unwrap();
+
+    for index in 0..CUBE_LEVELS.pow(3) {
+        let red = index / (CUBE_LEVELS * CUBE_LEVELS);
+        let green = index / CUBE_LEVELS % CUBE_LEVELS;
+        let blue = index % CUBE_LEVELS;
+        let percent = |level: u32| level * 100 / (CUBE_LEVELS - 1);
+        write!(
+            output,
+            "#{index};2;{};{};{}",
+            percent(red),
+            percent(green),
+            percent(blue)
+        )
+        .unwrap();
+    }
+
+    let indices = canvas
+        .data()
+        .chunks_exact(4)
+        .map(palette_index)
+        .collect::<Vec<_>>();
+
+    for band_top in (0..height).step_by(SIXEL_HEIGHT) {
+        let band_bottom = (band_top + SIXEL_HEIGHT).min(height);
+        let band = &indices[band_top * width..band_bottom * width];
+        let colors = band.iter().flatten().collect::<BTreeSet<_>>();
+
+        for (color_number, &color) in colors.iter().enumerate() {
+            if color_number > 0 {
+                output.push('$');
+            }
+            write!(output, "#{color}").unwrap();
+
+            let mut run = RunLengthEncoder::default();
+            for x in 0..width {
+                let bits = (0..band_bottom - band_top)
+                    .filter(|dy| band[dy * width + x] == Some(*color))
+                    .fold(0, |bits, dy| bits | 1 << dy);
+                run.push(&mut output, (63 + bits) as u8 as char);
+            }
+            run.finish(&mut output);
+        }
+
+        output.push('-');
+    }
+
+    output.push_str("\x1b\\");
+    output
+}
+
+fn palette_index(pixel: &[u8]) -> Option<u32> {
+    if pixel[3] < ALPHA_THRESHOLD {
+        return None;
+    }
+
+    let level = |channel: u8| (channel as u32 * (CUBE_LEVELS - 1) + 127) / 255;
+    Some((level(pixel[0]) * CUBE_LEVELS + level(pixel[1])) * CUBE_LEVELS + level(pixel[2]))
+}
+
+// Repeated Sixel characters can be written once with a count.
+#[derive(Default)]
+struct RunLengthEncoder {
+    character: Option<char>,
+    count: usize,
+}
+
+impl RunLengthEncoder {
+    fn push(&mut self, output: &mut String, character: char) {
+        if self.character != Some(character) {
+            self.finish(output);
+            self.character = Some(character);
+        }
+        self.count += 1;
+    }
+
+    fn finish(&mut self, output: &mut String) {
+        if let Some(character) = self.character {
+            if self.count > 3 {
+                write!(output, "!{}{character}", self.count).unwrap();
+            } else {
+                output.extend(std::iter::repeat_n(character, self.count));
+            }
+        }
+        self.character = None;
+        self.count = 0;
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use color_wheel_lib::pixel::Pixel;
+
+    use super::*;
+
+    #[test]
+    fn it_should_draw_each_color_in_bands() {
+        let mut canvas = Canvas::new(5, 2);
+        for x in 0..5 {
+            canvas.set_pixel(x, 1, Pixel::rgb(255, 0, 0));
+        }
+        canvas.set_pixel(0, 0, Pixel::rgb(0, 0, 255));
+
+        let sixel = encode_sixel(&canvas);
+        assert!(sixel.starts_with("\x1bP0;1;0q\"1;1;5;2#0;2;0;0;0"));
+        assert!(sixel.ends_with("#5@!4?$#180!5A-\x1b\\"));
+    }
+}
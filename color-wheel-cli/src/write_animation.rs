@@ -0,0 +1,95 @@
+use std::{
+    ffi::OsStr,
+    fs::File,
+    io::{BufWriter, Write},
+    path::{Path, PathBuf},
+};
+
+use color_wheel_lib::canvas::Canvas;
+
+use crate::{
+    cli::Cli, cli_error::CliError, write_gif::write_gif, GIF_FILE_EXTENSION, OUTPUT_FILE_EXTENSION,
+};
+
+pub fn write_animation(cli: Cli, frames: Vec<Canvas>) -> Result<(), CliError> {
+    let output_file_path = cli
+        .output
+        .unwrap_or(PathBuf::from(format!("output.{OUTPUT_FILE_EXTENSION}")));
+
+    if cli.frame_sequence {
+        return write_frame_sequence(&output_file_path, &frames);
+    }
+
+    let write_error = |error| CliError::write_output(output_file_path.clone(), error);
+
+    let file = File::create(&output_file_path).map_err(write_error)?;
+    let mut writer = BufWriter::new(file);
+
+    if output_file_path.extension() == Some(OsStr::new(GIF_FILE_EXTENSION)) {
+        write_gif(&mut writer, &frames, cli.frame_delay)
+            .and_then(|_| writer.flush())
+            .map_err(write_error)
+    } else {
+        write_apng(&mut writer, &frames, cli.frame_delay)
+            .map_err(|error| CliError::write_output(output_file_path.clone(), error))
+    }
+}
+
+fn write_apng(
+    writer: &mut impl Write,
+    frames: &[Canvas],
+    frame_delay_ms: u16,
+) -> Result<(), png::EncodingError> {
+    let mut encoder = png::Encoder::new(writer, frames[0].width(), frames[0].height());
+    encoder.set_color(png::ColorType::Rgba);
+    encoder.set_depth(png::BitDepth::Eight);
+    encoder.set_animated(frames.len() as u32, 0)?;
+    encoder.set_frame_delay(frame_delay_ms, 1000)?;
+
+    let mut png_writer = encoder.write_header()?;
+    for frame in frames {
+        png_writer.write_image_data(frame.data())?;
+    }
+    png_writer.finish()
+}
+
+// Writes each frame alongside the output path, for example `output-0001.png`.
+fn write_frame_sequence(output_file_path: &Path, frames: &[Canvas]) -> Result<(), CliError> {
+    for (index, frame) in frames.iter().enumerate() {
+        let frame_file_path = frame_file_path(output_file_path, index);
+        image::save_buffer(
+            &frame_file_path,
+            frame.data(),
+            frame.width(),
+            frame.height(),
+            image::ColorType::Rgba8,
+        )
+        .map_err(|error| CliError::write_output(frame_file_path, error))?;
+    }
+
+    Ok(())
+}
+
+fn frame_file_path(output_file_path: &Path, index: usize) -> PathBuf {
+    let file_stem = output_file_path
+        .file_stem()
+        .unwrap_or(OsStr::new("output"))
+        .to_string_lossy();
+    output_file_path.with_file_name(format!(
+        "{file_stem}-{:04}.{OUTPUT_FILE_EXTENSION}",
+        index + 1
+    ))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn it_should_number_frame_files() {
+        assert_eq!(
+            frame_file_path(Path::new("wheels/hsl.png"), 11),
+            PathBuf::from("wheels/hsl-0012.png")
+        );
+    }
+}
@@ -0,0 +1,151 @@
+use std::{borrow::Cow, io};
+
+use color_quant::NeuQuant;
+use color_wheel_lib::canvas::Canvas;
+use gif::{DisposalMethod, Encoder, EncodingError, Frame, Repeat};
+
+// The last palette entry is reserved for transparent pixels.
+const TRANSPARENT_INDEX: u8 = 255;
+const OPAQUE_COLOR_COUNT: usize = 255;
+
+// Pixels with less alpha than this are written as transparent, as GIF has no partial transparency.
+const ALPHA_THRESHOLD: u8 = 128;
+
+// Limits how many pixels are used to build the palette, so long animations quantize quickly.
+const MAXIMUM_PALETTE_SAMPLES: usize = 1 << 20;
+
+// Higher is faster but lower quality, from 1 to 30.
+const QUANTIZER_SAMPLE_FACTOR: i32 = 10;
+
+// Writes the frames as a looping animated GIF. All frames share a single palette so that
+// colors don't flicker between frames.
+pub fn write_gif(writer: impl io::Write, frames: &[Canvas], frame_delay_ms: u16) -> io::Result<()> {
+    let width = gif_dimension(frames[0].width())?;
+    let height = gif_dimension(frames[0].height())?;
+
+    let quantizer = create_shared_palette(frames);
+    let mut palette = quantizer.color_map_rgb();
+    palette.resize(256 * 3, 0);
+
+    let mut encoder = Encoder::new(writer, width, height, &palette).map_err(to_io_error)?;
+    encoder.set_repeat(Repeat::Infinite).map_err(to_io_error)?;
+
+    let delay_centiseconds = (frame_delay_ms as f64 / 10.).round() as u16;
+    for frame in frames {
+        let indices = frame
+            .data()
+            .chunks_exact(4)
+            .map(|pixel| palette_index(&quantizer, pixel))
+            .collect::<Vec<_>>();
+
+        // Each frame is cleared to the background before the next, so transparent pixels
+        // don't show the previous frame.
+        let frame = Frame {
+            delay: delay_centiseconds,
+            dispose: DisposalMethod::Background,
+            transparent: Some(TRANSPARENT_INDEX),
+            width,
+            height,
+            buffer: Cow::Owned(indices),
+            ..Frame::default()
+        };
+        encoder.write_frame(&frame).map_err(to_io_error)?;
+    }
+
+    Ok(())
+}
+
+fn gif_dimension(size: u32) -> io::Result<u16> {
+    u16::try_from(size)
+        .map_err(|_| io::Error::new(io::ErrorKind::InvalidInput, "Image is too large for a GIF."))
+}
+
+fn to_io_error(error: EncodingError) -> io::Error {
+    match error {
+        EncodingError::Io(error) => error,
+        EncodingError::Format(error) => io::Error::new(io::ErrorKind::InvalidInput, error),
+    }
+}
+
+fn create_shared_palette(frames: &[Canvas]) -> NeuQuant {
+    let pixel_count = frames
+        .iter()
+        .map(|frame| frame.data().len() / 4)
+        .sum::<usize>();
+    let stride = pixel_count.div_ceil(MAXIMUM_PALETTE_SAMPLES).max(1);
+
+    let mut samples = frames
+        .iter()
+        .flat_map(|frame| frame.data().chunks_exact(4).step_by(stride))
+        .filter(|pixel| pixel[3] >= ALPHA_THRESHOLD)
+        .flat_map(|pixel| [pixel[0], pixel[1], pixel[2], 255])
+        .collect::<Vec<_>>();
+
+    if samples.is_empty() {
+        samples.extend_from_slice(&[0, 0, 0, 255]);
+    }
+
+    NeuQuant::new(QUANTIZER_SAMPLE_FACTOR, OPAQUE_COLOR_COUNT, &samples)
+}
+
+fn palette_index(quantizer: &NeuQuant, pixel: &[u8]) -> u8 {
+    if pixel[3] < ALPHA_THRESHOLD {
+        return TRANSPARENT_INDEX;
+    }
+
+    quantizer.index_of(&[pixel[0], pixel[1], pixel[2], 255]) as u8
+}
+
+#[cfg(test)]
+mod tests {
+    use color_wheel_lib::pixel::Pixel;
+    use gif::DecodeOptions;
+
+    use super::*;
+
+    #[test]
+    fn it_should_write_transparent_pixels_to_the_reserved_index() {
+        let quantizer = NeuQuant::new(QUANTIZER_SAMPLE_FACTOR, OPAQUE_COLOR_COUNT, &[1, 2, 3, 255]);
+        assert_eq!(palette_index(&quantizer, &[1, 2, 3, 0]), TRANSPARENT_INDEX);
+        assert_ne!(
+            palette_index(&quantizer, &[1, 2, 3, 255]),
+            TRANSPARENT_INDEX
+        );
+    }
+
+    #[test]
+    fn it_should_write_frames_which_decode_to_the_same_colors() {
+        // Enough pixels of each color for the quantizer to learn them, above a transparent row.
+        let frames = [Pixel::rgb(255, 0, 0), Pixel::rgb(0, 0, 255)].map(|color| {
+            let mut canvas = Canvas::new(32, 32);
+            canvas.map_pixels(0, 1, 32, 31, |_| color);
+            canvas
+        });
+
+        let mut output = vec![];
+        write_gif(&mut output, &frames, 100).unwrap();
+
+        let mut options = DecodeOptions::new();
+        options.set_color_output(gif::ColorOutput::RGBA);
+        let mut decoder = options.read_info(output.as_slice()).unwrap();
+        assert_eq!((decoder.width(), decoder.height()), (32, 32));
+
+        for expected in &frames {
+            let frame = decoder.read_next_frame().unwrap().unwrap();
+            assert_eq!(frame.delay, 10);
+
+            for (actual, expected) in frame
+                .buffer
+                .chunks_exact(4)
+                .zip(expected.data().chunks_exact(4))
+            {
+                if expected[3] == 0 {
+                    assert_eq!(actual[3], 0);
+                } else {
+                    assert_eq!(actual, expected);
+                }
+            }
+        }
+        assert!(decoder.read_next_frame().unwrap().is_none());
+    }
+}
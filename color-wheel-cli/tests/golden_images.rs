@@ -0,0 +1,232 @@
+use std::{
+    env, fs,
+    path::{Path, PathBuf},
+    process::Command,
+};
+
+use color_wheel_lib::{perceptual_distance::perceptual_distance, pixel::Pixel};
+use image::{Rgba, RgbaImage};
+
+// Set to any value to write the current output as the new reference images.
+const BLESS_VARIABLE: &str = "BLESS_GOLDEN_IMAGES";
+
+const REFERENCE_DIRECTORY: &str = concat!(env!("CARGO_MANIFEST_DIR"), "/tests/golden");
+
+// Small enough to keep the references small, without supersampling so that most outputs
+// don't depend on the resizer. Tests may pass their own value for any of these options.
+const COMMON_ARGS: [&str; 6] = ["-d", "64", "-m", "4", "-s", "1"];
+
+// Trigonometry may round differently between platforms, which nudges colors slightly.
+const MAXIMUM_CHANNEL_DIFFERENCE: u8 = 2;
+const MAXIMUM_DELTA_E: f64 = 0.01;
+
+// Pixels right on an edge or bucket boundary may land on the other side of it instead.
+const MAXIMUM_MISMATCHED_FRACTION: f64 = 0.002;
+
+// Each test renders the color wheel type and options with the command line, and compares
+// the image with `tests/golden/<test name>.png`.
+macro_rules! golden_image_tests {
+    ($($name:ident: [$($arg:expr),* $(,)?],)*) => {
+        $(
+            #[test]
+            fn $name() {
+                assert_golden_image(stringify!($name), &[$($arg),*]);
+            }
+        )*
+    };
+}
+
+// The README samples, followed by other options which change the image.
+golden_image_tests! {
+    hsv_fixed_value_three: ["hsv-fixed-value", "-f", "1", "-f", "0.6", "-f", "0.2", "-a", "18", "-r", "12"],
+    hsl_fixed_lightness_three: ["hsl-fixed-lightness", "-f", "0.8", "-f", "0.5", "-f", "0.2", "-a", "18", "-r", "12"],
+    hsv_fixed_saturation_three: ["hsv-fixed-saturation", "-f", "1", "-f", "0.6", "-f", "0.2", "-a", "18", "-r", "12"],
+    hsl_fixed_saturation_three: ["hsl-fixed-saturation", "-f", "1", "-f", "0.6", "-f", "0.2", "-a", "18", "-r", "12"],
+    oklab_fixed_chroma_three: ["oklab-fixed-chroma", "-f", "0.085", "-f", "0.2", "-f", "0.35", "-a", "18", "-r", "12"],
+    oklab_fixed_lightness_three: ["oklab-fixed-lightness", "-f", "0.1", "-f", "0.5", "-f", "1.0", "-a", "18", "-r", "12"],
+    hsl_fixed_saturation_smooth: ["hsl-fixed-saturation"],
+    hsl_fixed_saturation_smooth_reversed_colors: ["hsl-fixed-saturation", "-c"],
+    hsl_fixed_saturation_angular: ["hsl-fixed-saturation", "-a", "36"],
+    hsl_fixed_saturation_radial: ["hsl-fixed-saturation", "-r", "12"],
+    hsv_fixed_saturation_expand: ["hsv-fixed-saturation", "-f", "0.25", "-f", "0.5", "-f", "1", "-a", "18", "-r", "12", "-e"],
+    hsl_fixed_saturation_expand: ["hsl-fixed-saturation", "-f", "0.1", "-f", "0.5", "-f", "1", "-a", "18", "-r", "12", "-e", "-b"],
+    hsv_fixed_saturation_coarse: ["hsv-fixed-saturation", "-a", "12", "-r", "5"],
+    oklab_fixed_lightness_perceptual_buckets: ["oklab-fixed-lightness", "-f", "0.7", "-a", "12", "-r", "4", "--angular-bucket-spacing", "perceptual", "--radial-mapping", "perceptual"],
+    hsl_fixed_lightness_rings: ["hsl-fixed-lightness", "--ring", "0.3:weight=2,radial-buckets=3", "--ring", "0.7:angular-buckets=24", "--ring-gap", "2"],
+    hsv_fixed_value_arc: ["hsv-fixed-value", "-a", "12", "--inner-radius", "0.4", "--start-angle", "-90", "--end-angle", "90", "--rotation", "45"],
+    hsl_fixed_saturation_unrolled_square: ["hsl-fixed-saturation", "-a", "12", "-r", "6", "--projection", "unrolled-square"],
+    hsv_fixed_value_unrolled_strip: ["hsv-fixed-value", "-a", "24", "--projection", "unrolled-strip"],
+    oklab_fixed_lightness_unrolled_triangle_in_ring: ["oklab-fixed-lightness", "-f", "0.7", "-r", "8", "--projection", "unrolled-triangle-in-ring"],
+    hsv_fixed_saturation_checkerboard: ["hsv-fixed-saturation", "-r", "6", "--background", "checkerboard:8"],
+    hsl_fixed_lightness_simulations: ["hsl-fixed-lightness", "-a", "18", "--simulate", "deuteranopia", "--simulate", "tritanopia"],
+    oklab_fixed_chroma_filters: ["oklab-fixed-chroma", "--filter", "shadow", "--filter", "background:#ffffff"],
+    oklab_fixed_lightness_dither: ["oklab-fixed-lightness", "-f", "0.5", "--dither", "bayer"],
+    hsv_fixed_value_supersampled_box: ["hsv-fixed-value", "-a", "12", "-r", "4", "-s", "2", "--resample-filter", "box"],
+    hsv_fixed_value_supersampled_hamming: ["hsv-fixed-value", "-a", "12", "-r", "4", "-s", "2", "--resample-filter", "hamming"],
+    hsv_fixed_value_supersampled_lanczos3: ["hsv-fixed-value", "-a", "12", "-r", "4", "-s", "2", "--resample-filter", "lanczos3"],
+    hsv_fixed_value_supersampled_mitchell: ["hsv-fixed-value", "-a", "12", "-r", "4", "-s", "2", "--resample-filter", "mitchell"],
+    oklab_fixed_lightness_supersampled_dither: ["oklab-fixed-lightness", "-f", "0.5", "-s", "2", "--dither", "floyd-steinberg"],
+}
+
+fn assert_golden_image(name: &str, args: &[&str]) {
+    let output_directory = Path::new(env!("CARGO_TARGET_TMPDIR")).join("golden_images");
+    fs::create_dir_all(&output_directory).unwrap();
+
+    let actual_path = output_directory.join(format!("{name}.png"));
+    render(args, &actual_path);
+
+    let reference_path = Path::new(REFERENCE_DIRECTORY).join(format!("{name}.png"));
+    if env::var_os(BLESS_VARIABLE).is_some() {
+        fs::create_dir_all(REFERENCE_DIRECTORY).unwrap();
+        fs::copy(&actual_path, &reference_path).unwrap();
+        return;
+    }
+
+    let reference = image::open(&reference_path)
+        .unwrap_or_else(|error| {
+            panic!(
+                "Failed to open {}: {error}. Run with {BLESS_VARIABLE}=1 to create it.",
+                reference_path.display()
+            )
+        })
+        .to_rgba8();
+    let actual = image::open(&actual_path).unwrap().to_rgba8();
+
+    assert_eq!(
+        actual.dimensions(),
+        reference.dimensions(),
+        "{name} is a different size to its reference image."
+    );
+
+    let comparison = compare(&reference, &actual);
+    let allowed_mismatches =
+        (MAXIMUM_MISMATCHED_FRACTION * reference.pixels().len() as f64) as usize;
+    if comparison.mismatched > allowed_mismatches {
+        let diff_path = output_directory.join(format!("{name}-diff.png"));
+        comparison.diff.save(&diff_path).unwrap();
+
+        panic!(
+            "{name} differs from {} in {} pixels, by up to {} per channel and ΔE {:.4}. \
+             See {} for the output and {} for the differences, or run with {BLESS_VARIABLE}=1 \
+             if the change is intended.",
+            reference_path.display(),
+            comparison.mismatched,
+            comparison.maximum_channel_difference,
+            comparison.maximum_delta_e,
+            actual_path.display(),
+            diff_path.display()
+        );
+    }
+}
+
+fn render(args: &[&str], output_path: &PathBuf) {
+    let common_args = COMMON_ARGS
+        .chunks(2)
+        .filter(|option| !args.contains(&option[0]))
+        .flatten();
+
+    let output = Command::new(env!("CARGO_BIN_EXE_color-wheel-cli"))
+        .args(args)
+        .args(common_args)
+        .arg("-o")
+        .arg(output_path)
+        .output()
+        .unwrap();
+
+    assert!(
+        output.status.success(),
+        "Failed to render {args:?}: {}",
+        String::from_utf8_lossy(&output.stderr)
+    );
+}
+
+struct Comparison {
+    mismatched: usize,
+    maximum_channel_difference: u8,
+    maximum_delta_e: f64,
+    // The reference faded, with mismatched pixels in red.
+    diff: RgbaImage,
+}
+
+fn compare(reference: &RgbaImage, actual: &RgbaImage) -> Comparison {
+    let mut comparison = Comparison {
+        mismatched: 0,
+        maximum_channel_difference: 0,
+        maximum_delta_e: 0.,
+        diff: RgbaImage::new(reference.width(), reference.height()),
+    };
+
+    for ((expected, actual), diff) in reference
+        .pixels()
+        .zip(actual.pixels())
+        .zip(comparison.diff.pixels_mut())
+    {
+        let channel_difference = expected
+            .0
+            .iter()
+            .zip(actual.0)
+            .map(|(expected, actual)| expected.abs_diff(actual))
+            .max()
+            .unwrap_or(0);
+        let delta_e = perceptual_distance(Pixel { data: expected.0 }, Pixel { data: actual.0 });
+
+        comparison.maximum_channel_difference = comparison
+            .maximum_channel_difference
+            .max(channel_difference);
+        comparison.maximum_delta_e = comparison.maximum_delta_e.max(delta_e);
+
+        *diff = if channel_difference > MAXIMUM_CHANNEL_DIFFERENCE || delta_e > MAXIMUM_DELTA_E {
+            comparison.mismatched += 1;
+            Rgba([255, 0, 0, 255])
+        } else {
+            let [red, green, blue, alpha] = expected.0;
+            Rgba([red / 4, green / 4, blue / 4, alpha])
+        };
+    }
+
+    comparison
+}
+
+#[test]
+fn comparison_should_allow_small_differences_and_mark_large_ones() {
+    let reference = RgbaImage::from_pixel(2, 1, Rgba([100, 150, 200, 255]));
+    let mut actual = reference.clone();
+    actual.put_pixel(0, 0, Rgba([101, 150, 199, 255]));
+    actual.put_pixel(1, 0, Rgba([200, 150, 200, 255]));
+
+    let comparison = compare(&reference, &actual);
+
+    assert_eq!(comparison.mismatched, 1);
+    assert_eq!(comparison.maximum_channel_difference, 100);
+    assert_eq!(*comparison.diff.get_pixel(0, 0), Rgba([25, 37, 50, 255]));
+    assert_eq!(*comparison.diff.get_pixel(1, 0), Rgba([255, 0, 0, 255]));
+}
+
+#[test]
+fn thread_count_should_not_change_the_image() {
+    let output_directory = Path::new(env!("CARGO_TARGET_TMPDIR")).join("golden_images");
+    fs::create_dir_all(&output_directory).unwrap();
+
+    // Supersampled, so that resizing is checked as well as rendering.
+    let render_with_threads = |threads: &str| {
+        let path = output_directory.join(format!("threads-{threads}.png"));
+        let output = Command::new(env!("CARGO_BIN_EXE_color-wheel-cli"))
+            .args(["oklab-fixed-lightness", "-f", "0.7", "-r", "5"])
+            .args(["-d", "96", "-s", "3", "--dither", "floyd-steinberg"])
+            .args(["--threads", threads, "-o"])
+            .arg(&path)
+            .output()
+            .unwrap();
+        assert!(output.status.success());
+
+        fs::read(path).unwrap()
+    };
+
+    let expected = render_with_threads("1");
+    for threads in ["2", "5"] {
+        assert!(
+            render_with_threads(threads) == expected,
+            "Rendering with {threads} threads changed the image."
+        );
+    }
+}
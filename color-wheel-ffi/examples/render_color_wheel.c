@@ -0,0 +1,49 @@
+// Renders a color wheel and prints the color at the top of it.
+//
+// cargo build --release -p color-wheel-ffi
+// cc color-wheel-ffi/examples/render_color_wheel.c -I color-wheel-ffi/include
+//     target/release/libcolor_wheel.a -lpthread -ldl -lm -o render_color_wheel
+
+#include <stdio.h>
+
+#include "color_wheel.h"
+
+int main(void) {
+  ColorWheelSpec *spec = color_wheel_spec_new(COLOR_WHEEL_TYPE_OKLAB_FIXED_LIGHTNESS);
+  const double fixed[] = {0.7};
+
+  if (spec == NULL) {
+    fprintf(stderr, "Unknown color wheel type.\n");
+    return 1;
+  }
+
+  if (color_wheel_spec_set_diameter(spec, 200) != COLOR_WHEEL_STATUS_OK ||
+      color_wheel_spec_set_angular_buckets(spec, 12) != COLOR_WHEEL_STATUS_OK ||
+      color_wheel_spec_set_fixed(spec, fixed, 1) != COLOR_WHEEL_STATUS_OK) {
+    fprintf(stderr, "Invalid spec.\n");
+    color_wheel_spec_free(spec);
+    return 1;
+  }
+
+  uint32_t width, height;
+  color_wheel_spec_image_size(spec, &width, &height);
+
+  size_t length = (size_t)width * height * 4;
+  uint8_t *pixels = malloc(length);
+  ColorWheelStatus status = color_wheel_render(spec, pixels, length);
+  if (status != COLOR_WHEEL_STATUS_OK) {
+    fprintf(stderr, "Failed to render: %d\n", status);
+    free(pixels);
+    color_wheel_spec_free(spec);
+    return (int)status;
+  }
+
+  uint8_t rgba[4];
+  color_wheel_color_at(spec, 0, 0.0, 1.0, rgba);
+  printf("Rendered %ux%u pixels. Top color: #%02x%02x%02x\n", width, height, rgba[0], rgba[1],
+         rgba[2]);
+
+  free(pixels);
+  color_wheel_spec_free(spec);
+  return 0;
+}
@@ -0,0 +1,262 @@
+#ifndef COLOR_WHEEL_H
+#define COLOR_WHEEL_H
+
+/* Generated by cbindgen from color-wheel-ffi. Do not edit by hand. */
+
+#include <stdarg.h>
+#include <stdbool.h>
+#include <stddef.h>
+#include <stdint.h>
+#include <stdlib.h>
+
+typedef enum ColorWheelType {
+  COLOR_WHEEL_TYPE_HSL_FIXED_SATURATION = 0,
+  COLOR_WHEEL_TYPE_HSL_FIXED_LIGHTNESS = 1,
+  COLOR_WHEEL_TYPE_HSV_FIXED_SATURATION = 2,
+  COLOR_WHEEL_TYPE_HSV_FIXED_VALUE = 3,
+  COLOR_WHEEL_TYPE_OKLAB_FIXED_LIGHTNESS = 4,
+  COLOR_WHEEL_TYPE_OKLAB_FIXED_CHROMA = 5,
+} ColorWheelType;
+
+typedef enum ColorWheelProjectionType {
+  COLOR_WHEEL_PROJECTION_TYPE_POLAR = 0,
+  COLOR_WHEEL_PROJECTION_TYPE_UNROLLED_SQUARE = 1,
+  COLOR_WHEEL_PROJECTION_TYPE_UNROLLED_STRIP = 2,
+  COLOR_WHEEL_PROJECTION_TYPE_UNROLLED_TRIANGLE_IN_RING = 3,
+} ColorWheelProjectionType;
+
+typedef enum ColorWheelResampleFilterType {
+  COLOR_WHEEL_RESAMPLE_FILTER_TYPE_BOX = 0,
+  COLOR_WHEEL_RESAMPLE_FILTER_TYPE_HAMMING = 1,
+  COLOR_WHEEL_RESAMPLE_FILTER_TYPE_LANCZOS3 = 2,
+  COLOR_WHEEL_RESAMPLE_FILTER_TYPE_MITCHELL = 3,
+} ColorWheelResampleFilterType;
+
+typedef enum ColorWheelStatus {
+  COLOR_WHEEL_STATUS_OK = 0,
+  COLOR_WHEEL_STATUS_NULL_POINTER = 1,
+  COLOR_WHEEL_STATUS_INVALID_ARGUMENT = 2,
+  COLOR_WHEEL_STATUS_BUFFER_TOO_SMALL = 3,
+  COLOR_WHEEL_STATUS_RENDER_FAILED = 4,
+  COLOR_WHEEL_STATUS_NO_COLOR_WHEELS = 5,
+  COLOR_WHEEL_STATUS_IMAGE_TOO_LARGE = 6,
+  COLOR_WHEEL_STATUS_IMAGE_TOO_SMALL = 7,
+  COLOR_WHEEL_STATUS_FIXED_VALUE_OUT_OF_RANGE = 8,
+  COLOR_WHEEL_STATUS_OPTION_OUT_OF_RANGE = 9,
+  COLOR_WHEEL_STATUS_EMPTY_SHAPE = 10,
+  COLOR_WHEEL_STATUS_INVALID_BUCKET_LAYOUT = 11,
+} ColorWheelStatus;
+
+typedef struct ColorWheelSpec ColorWheelSpec;
+
+#ifdef __cplusplus
+extern "C" {
+#endif // __cplusplus
+
+/**
+ * Creates a spec with the same defaults as the command line, or returns null when
+ * `color_wheel_type` isn't a `ColorWheelType`.
+ */
+ColorWheelSpec *color_wheel_spec_new(uint32_t color_wheel_type);
+
+/**
+ * # Safety
+ *
+ * `spec` must be null or a pointer returned by `color_wheel_spec_new` which hasn't been freed.
+ */
+void color_wheel_spec_free(ColorWheelSpec *spec);
+
+/**
+ * # Safety
+ *
+ * `spec` must be null or a valid spec.
+ */
+ColorWheelStatus color_wheel_spec_set_diameter(ColorWheelSpec *spec, uint32_t diameter);
+
+/**
+ * # Safety
+ *
+ * `spec` must be null or a valid spec.
+ */
+ColorWheelStatus color_wheel_spec_set_margin(ColorWheelSpec *spec, uint32_t margin);
+
+/**
+ * # Safety
+ *
+ * `spec` must be null or a valid spec.
+ */
+ColorWheelStatus color_wheel_spec_set_angular_buckets(ColorWheelSpec *spec, uint32_t buckets);
+
+/**
+ * # Safety
+ *
+ * `spec` must be null or a valid spec.
+ */
+ColorWheelStatus color_wheel_spec_set_radial_buckets(ColorWheelSpec *spec, uint32_t buckets);
+
+/**
+ * # Safety
+ *
+ * `spec` must be null or a valid spec, and `fixed` must be null or point to `length` doubles.
+ */
+ColorWheelStatus color_wheel_spec_set_fixed(ColorWheelSpec *spec,
+                                            const double *fixed,
+                                            size_t length);
+
+/**
+ * # Safety
+ *
+ * `spec` must be null or a valid spec.
+ */
+ColorWheelStatus color_wheel_spec_set_expand(ColorWheelSpec *spec, bool expand);
+
+/**
+ * # Safety
+ *
+ * `spec` must be null or a valid spec.
+ */
+ColorWheelStatus color_wheel_spec_set_reverse_radial_colors(ColorWheelSpec *spec,
+                                                            bool reverse_radial_colors);
+
+/**
+ * Renders each output pixel from `supersampling` squared pixels, from 1 to 8.
+ *
+ * # Safety
+ *
+ * `spec` must be null or a valid spec.
+ */
+ColorWheelStatus color_wheel_spec_set_supersampling(ColorWheelSpec *spec, uint32_t supersampling);
+
+/**
+ * Sets the resample filter to a `ColorWheelResampleFilterType`.
+ *
+ * # Safety
+ *
+ * `spec` must be null or a valid spec.
+ */
+ColorWheelStatus color_wheel_spec_set_resample_filter(ColorWheelSpec *spec,
+                                                      uint32_t resample_filter);
+
+/**
+ * Sets the projection to a `ColorWheelProjectionType`.
+ *
+ * # Safety
+ *
+ * `spec` must be null or a valid spec.
+ */
+ColorWheelStatus color_wheel_spec_set_projection(ColorWheelSpec *spec, uint32_t projection);
+
+/**
+ * Sets the radius of the hole in the middle of the wheel, from 0 to 1.
+ *
+ * # Safety
+ *
+ * `spec` must be null or a valid spec.
+ */
+ColorWheelStatus color_wheel_spec_set_inner_radius(ColorWheelSpec *spec, double inner_radius);
+
+/**
+ * Sets the visible arc, in degrees clockwise from the top. The arc may wrap past 360 degrees.
+ *
+ * # Safety
+ *
+ * `spec` must be null or a valid spec.
+ */
+ColorWheelStatus color_wheel_spec_set_arc(ColorWheelSpec *spec,
+                                          double start_angle_degrees,
+                                          double end_angle_degrees);
+
+/**
+ * Rotates the colors clockwise around the wheel without moving the visible arc.
+ *
+ * # Safety
+ *
+ * `spec` must be null or a valid spec.
+ */
+ColorWheelStatus color_wheel_spec_set_rotation(ColorWheelSpec *spec, double rotation_degrees);
+
+/**
+ * # Safety
+ *
+ * `spec` must be null or a valid spec.
+ */
+ColorWheelStatus color_wheel_spec_set_ring_gap(ColorWheelSpec *spec, uint32_t ring_gap);
+
+/**
+ * Sets the radial mapping from a string, as on the command line: `linear`, `sqrt`,
+ * `gamma:N` or `perceptual`.
+ *
+ * # Safety
+ *
+ * `spec` must be null or a valid spec, and `radial_mapping` must be null or a null
+ * terminated string.
+ */
+ColorWheelStatus color_wheel_spec_set_radial_mapping(ColorWheelSpec *spec,
+                                                     const char *radial_mapping);
+
+/**
+ * Adds a ring, which draws the spec as a single wheel of rings rather than its fixed values.
+ * Negative bucket counts use the spec's own.
+ *
+ * # Safety
+ *
+ * `spec` must be null or a valid spec.
+ */
+ColorWheelStatus color_wheel_spec_add_ring(ColorWheelSpec *spec,
+                                           double fixed,
+                                           double weight,
+                                           int32_t angular_buckets,
+                                           int32_t radial_buckets);
+
+/**
+ * Removes every ring, so that the spec draws its fixed values again.
+ *
+ * # Safety
+ *
+ * `spec` must be null or a valid spec.
+ */
+ColorWheelStatus color_wheel_spec_clear_rings(ColorWheelSpec *spec);
+
+/**
+ * # Safety
+ *
+ * `spec` must be null or a valid spec, and `width` and `height` must be null or writable.
+ */
+ColorWheelStatus color_wheel_spec_image_size(const ColorWheelSpec *spec,
+                                             uint32_t *width,
+                                             uint32_t *height);
+
+/**
+ * Renders the spec's color wheels as RGBA pixels, row by row, into `buffer`. The buffer must
+ * hold at least `width * height * 4` bytes, as given by `color_wheel_spec_image_size`.
+ *
+ * # Safety
+ *
+ * `spec` must be null or a valid spec, and `buffer` must be null or point to `buffer_length`
+ * writable bytes.
+ */
+ColorWheelStatus color_wheel_render(const ColorWheelSpec *spec,
+                                    uint8_t *buffer,
+                                    size_t buffer_length);
+
+/**
+ * Writes the RGBA color at a position on one of the spec's color wheels into `rgba`, without
+ * rendering an image. The angle is in degrees clockwise from the top, and the radius is
+ * from 0 at the center to 1 at the edge of the wheel. Positions in the gap between rings
+ * are transparent.
+ *
+ * # Safety
+ *
+ * `spec` must be null or a valid spec, and `rgba` must be null or point to 4 writable bytes.
+ */
+ColorWheelStatus color_wheel_color_at(const ColorWheelSpec *spec,
+                                      size_t wheel_index,
+                                      double angle_degrees,
+                                      double radius,
+                                      uint8_t *rgba);
+
+#ifdef __cplusplus
+}  // extern "C"
+#endif  // __cplusplus
+
+#endif  /* COLOR_WHEEL_H */
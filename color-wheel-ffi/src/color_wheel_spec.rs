@@ -0,0 +1,601 @@
+use std::ffi::{c_char, CStr};
+
+use color_wheel_lib::{
+    color_wheel_projection::ColorWheelProjection,
+    color_wheel_ring::ColorWheelRing,
+    color_wheel_spec,
+    pixel_generators::{
+        HslFixedLightnessPixelGenerator, HslFixedSaturationPixelGenerator,
+        HsvFixedSaturationPixelGenerator, HsvFixedValuePixelGenerator,
+        OklabFixedChromaPixelGenerator, OklabFixedLightnessPixelGenerator,
+        PixelGeneratorRegistration,
+    },
+    radial_mapping::RadialMapping,
+    resample::ResampleFilter,
+    ring_options::RingOptions,
+};
+
+use crate::color_wheel_status::ColorWheelStatus;
+
+#[repr(C)]
+#[derive(Copy, Clone, PartialEq, Eq, Debug)]
+pub enum ColorWheelType {
+    HslFixedSaturation = 0,
+    HslFixedLightness = 1,
+    HsvFixedSaturation = 2,
+    HsvFixedValue = 3,
+    OklabFixedLightness = 4,
+    OklabFixedChroma = 5,
+}
+
+impl ColorWheelType {
+    // The registered pixel generator for the type, which gives its valid fixed values.
+    pub fn registration(self) -> PixelGeneratorRegistration {
+        match self {
+            ColorWheelType::HslFixedSaturation => HslFixedSaturationPixelGenerator::REGISTRATION,
+            ColorWheelType::HslFixedLightness => HslFixedLightnessPixelGenerator::REGISTRATION,
+            ColorWheelType::HsvFixedSaturation => HsvFixedSaturationPixelGenerator::REGISTRATION,
+            ColorWheelType::HsvFixedValue => HsvFixedValuePixelGenerator::REGISTRATION,
+            ColorWheelType::OklabFixedLightness => OklabFixedLightnessPixelGenerator::REGISTRATION,
+            ColorWheelType::OklabFixedChroma => OklabFixedChromaPixelGenerator::REGISTRATION,
+        }
+    }
+}
+
+// C can pass any integer where an enum is expected, so the functions take the values as
+// integers and convert them, rejecting the ones which aren't variants.
+impl TryFrom<u32> for ColorWheelType {
+    type Error = ();
+
+    fn try_from(value: u32) -> Result<Self, Self::Error> {
+        match value {
+            0 => Ok(ColorWheelType::HslFixedSaturation),
+            1 => Ok(ColorWheelType::HslFixedLightness),
+            2 => Ok(ColorWheelType::HsvFixedSaturation),
+            3 => Ok(ColorWheelType::HsvFixedValue),
+            4 => Ok(ColorWheelType::OklabFixedLightness),
+            5 => Ok(ColorWheelType::OklabFixedChroma),
+            _ => Err(()),
+        }
+    }
+}
+
+#[repr(C)]
+#[derive(Copy, Clone, PartialEq, Eq, Debug)]
+pub enum ColorWheelProjectionType {
+    Polar = 0,
+    UnrolledSquare = 1,
+    UnrolledStrip = 2,
+    UnrolledTriangleInRing = 3,
+}
+
+impl From<ColorWheelProjectionType> for ColorWheelProjection {
+    fn from(value: ColorWheelProjectionType) -> Self {
+        match value {
+            ColorWheelProjectionType::Polar => ColorWheelProjection::Polar,
+            ColorWheelProjectionType::UnrolledSquare => ColorWheelProjection::UnrolledSquare,
+            ColorWheelProjectionType::UnrolledStrip => ColorWheelProjection::UnrolledStrip,
+            ColorWheelProjectionType::UnrolledTriangleInRing => {
+                ColorWheelProjection::UnrolledTriangleInRing
+            }
+        }
+    }
+}
+
+impl TryFrom<u32> for ColorWheelProjectionType {
+    type Error = ();
+
+    fn try_from(value: u32) -> Result<Self, Self::Error> {
+        match value {
+            0 => Ok(ColorWheelProjectionType::Polar),
+            1 => Ok(ColorWheelProjectionType::UnrolledSquare),
+            2 => Ok(ColorWheelProjectionType::UnrolledStrip),
+            3 => Ok(ColorWheelProjectionType::UnrolledTriangleInRing),
+            _ => Err(()),
+        }
+    }
+}
+
+#[repr(C)]
+#[derive(Copy, Clone, PartialEq, Eq, Debug)]
+pub enum ColorWheelResampleFilterType {
+    Box = 0,
+    Hamming = 1,
+    Lanczos3 = 2,
+    Mitchell = 3,
+}
+
+impl From<ColorWheelResampleFilterType> for ResampleFilter {
+    fn from(value: ColorWheelResampleFilterType) -> Self {
+        match value {
+            ColorWheelResampleFilterType::Box => ResampleFilter::Box,
+            ColorWheelResampleFilterType::Hamming => ResampleFilter::Hamming,
+            ColorWheelResampleFilterType::Lanczos3 => ResampleFilter::Lanczos3,
+            ColorWheelResampleFilterType::Mitchell => ResampleFilter::Mitchell,
+        }
+    }
+}
+
+impl TryFrom<u32> for ColorWheelResampleFilterType {
+    type Error = ();
+
+    fn try_from(value: u32) -> Result<Self, Self::Error> {
+        match value {
+            0 => Ok(ColorWheelResampleFilterType::Box),
+            1 => Ok(ColorWheelResampleFilterType::Hamming),
+            2 => Ok(ColorWheelResampleFilterType::Lanczos3),
+            3 => Ok(ColorWheelResampleFilterType::Mitchell),
+            _ => Err(()),
+        }
+    }
+}
+
+// Describes the color wheels to render. Opaque to C, which creates it with
+// `color_wheel_spec_new` and must release it with `color_wheel_spec_free`.
+#[derive(Clone, Debug)]
+pub struct ColorWheelSpec(pub(crate) color_wheel_spec::ColorWheelSpec);
+
+/// Creates a spec with the same defaults as the command line, or returns null when
+/// `color_wheel_type` isn't a `ColorWheelType`.
+#[no_mangle]
+pub extern "C" fn color_wheel_spec_new(color_wheel_type: u32) -> *mut ColorWheelSpec {
+    let Ok(color_wheel_type) = ColorWheelType::try_from(color_wheel_type) else {
+        return std::ptr::null_mut();
+    };
+
+    Box::into_raw(Box::new(ColorWheelSpec(
+        color_wheel_spec::ColorWheelSpec::new(color_wheel_type.registration()),
+    )))
+}
+
+/// # Safety
+///
+/// `spec` must be null or a pointer returned by `color_wheel_spec_new` which hasn't been freed.
+#[no_mangle]
+pub unsafe extern "C" fn color_wheel_spec_free(spec: *mut ColorWheelSpec) {
+    if !spec.is_null() {
+        drop(Box::from_raw(spec));
+    }
+}
+
+/// # Safety
+///
+/// `spec` must be null or a valid spec.
+#[no_mangle]
+pub unsafe extern "C" fn color_wheel_spec_set_diameter(
+    spec: *mut ColorWheelSpec,
+    diameter: u32,
+) -> ColorWheelStatus {
+    update_spec(spec, |spec| spec.diameter = diameter)
+}
+
+/// # Safety
+///
+/// `spec` must be null or a valid spec.
+#[no_mangle]
+pub unsafe extern "C" fn color_wheel_spec_set_margin(
+    spec: *mut ColorWheelSpec,
+    margin: u32,
+) -> ColorWheelStatus {
+    update_spec(spec, |spec| spec.margin = margin)
+}
+
+/// # Safety
+///
+/// `spec` must be null or a valid spec.
+#[no_mangle]
+pub unsafe extern "C" fn color_wheel_spec_set_angular_buckets(
+    spec: *mut ColorWheelSpec,
+    buckets: u32,
+) -> ColorWheelStatus {
+    update_spec(spec, |spec| spec.angle_buckets = buckets)
+}
+
+/// # Safety
+///
+/// `spec` must be null or a valid spec.
+#[no_mangle]
+pub unsafe extern "C" fn color_wheel_spec_set_radial_buckets(
+    spec: *mut ColorWheelSpec,
+    buckets: u32,
+) -> ColorWheelStatus {
+    update_spec(spec, |spec| spec.distance_buckets = buckets)
+}
+
+/// # Safety
+///
+/// `spec` must be null or a valid spec, and `fixed` must be null or point to `length` doubles.
+#[no_mangle]
+pub unsafe extern "C" fn color_wheel_spec_set_fixed(
+    spec: *mut ColorWheelSpec,
+    fixed: *const f64,
+    length: usize,
+) -> ColorWheelStatus {
+    if fixed.is_null() {
+        return ColorWheelStatus::NullPointer;
+    }
+
+    let fixed = std::slice::from_raw_parts(fixed, length);
+    update_spec(spec, |spec| spec.fixed = fixed.to_vec())
+}
+
+/// # Safety
+///
+/// `spec` must be null or a valid spec.
+#[no_mangle]
+pub unsafe extern "C" fn color_wheel_spec_set_expand(
+    spec: *mut ColorWheelSpec,
+    expand: bool,
+) -> ColorWheelStatus {
+    update_spec(spec, |spec| spec.expand = expand)
+}
+
+/// # Safety
+///
+/// `spec` must be null or a valid spec.
+#[no_mangle]
+pub unsafe extern "C" fn color_wheel_spec_set_reverse_radial_colors(
+    spec: *mut ColorWheelSpec,
+    reverse_radial_colors: bool,
+) -> ColorWheelStatus {
+    update_spec(spec, |spec| {
+        spec.is_varying_dimension_inverted = reverse_radial_colors
+    })
+}
+
+/// Renders each output pixel from `supersampling` squared pixels, from 1 to 8.
+///
+/// # Safety
+///
+/// `spec` must be null or a valid spec.
+#[no_mangle]
+pub unsafe extern "C" fn color_wheel_spec_set_supersampling(
+    spec: *mut ColorWheelSpec,
+    supersampling: u32,
+) -> ColorWheelStatus {
+    update_spec(spec, |spec| spec.supersampling = supersampling)
+}
+
+/// Sets the resample filter to a `ColorWheelResampleFilterType`.
+///
+/// # Safety
+///
+/// `spec` must be null or a valid spec.
+#[no_mangle]
+pub unsafe extern "C" fn color_wheel_spec_set_resample_filter(
+    spec: *mut ColorWheelSpec,
+    resample_filter: u32,
+) -> ColorWheelStatus {
+    let Ok(resample_filter) = ColorWheelResampleFilterType::try_from(resample_filter) else {
+        return ColorWheelStatus::InvalidArgument;
+    };
+
+    update_spec(spec, |spec| spec.resample_filter = resample_filter.into())
+}
+
+/// Sets the projection to a `ColorWheelProjectionType`.
+///
+/// # Safety
+///
+/// `spec` must be null or a valid spec.
+#[no_mangle]
+pub unsafe extern "C" fn color_wheel_spec_set_projection(
+    spec: *mut ColorWheelSpec,
+    projection: u32,
+) -> ColorWheelStatus {
+    let Ok(projection) = ColorWheelProjectionType::try_from(projection) else {
+        return ColorWheelStatus::InvalidArgument;
+    };
+
+    update_spec(spec, |spec| spec.shape.projection = projection.into())
+}
+
+/// Sets the radius of the hole in the middle of the wheel, from 0 to 1.
+///
+/// # Safety
+///
+/// `spec` must be null or a valid spec.
+#[no_mangle]
+pub unsafe extern "C" fn color_wheel_spec_set_inner_radius(
+    spec: *mut ColorWheelSpec,
+    inner_radius: f64,
+) -> ColorWheelStatus {
+    update_spec(spec, |spec| spec.shape.inner_radius = inner_radius)
+}
+
+/// Sets the visible arc, in degrees clockwise from the top. The arc may wrap past 360 degrees.
+///
+/// # Safety
+///
+/// `spec` must be null or a valid spec.
+#[no_mangle]
+pub unsafe extern "C" fn color_wheel_spec_set_arc(
+    spec: *mut ColorWheelSpec,
+    start_angle_degrees: f64,
+    end_angle_degrees: f64,
+) -> ColorWheelStatus {
+    update_spec(spec, |spec| {
+        spec.shape.start_angle_degrees = start_angle_degrees;
+        spec.shape.end_angle_degrees = end_angle_degrees;
+    })
+}
+
+/// Rotates the colors clockwise around the wheel without moving the visible arc.
+///
+/// # Safety
+///
+/// `spec` must be null or a valid spec.
+#[no_mangle]
+pub unsafe extern "C" fn color_wheel_spec_set_rotation(
+    spec: *mut ColorWheelSpec,
+    rotation_degrees: f64,
+) -> ColorWheelStatus {
+    update_spec(spec, |spec| spec.shape.rotation_degrees = rotation_degrees)
+}
+
+/// # Safety
+///
+/// `spec` must be null or a valid spec.
+#[no_mangle]
+pub unsafe extern "C" fn color_wheel_spec_set_ring_gap(
+    spec: *mut ColorWheelSpec,
+    ring_gap: u32,
+) -> ColorWheelStatus {
+    update_spec(spec, |spec| spec.shape.ring_gap = ring_gap)
+}
+
+/// Sets the radial mapping from a string, as on the command line: `linear`, `sqrt`,
+/// `gamma:N` or `perceptual`.
+///
+/// # Safety
+///
+/// `spec` must be null or a valid spec, and `radial_mapping` must be null or a null
+/// terminated string.
+#[no_mangle]
+pub unsafe extern "C" fn color_wheel_spec_set_radial_mapping(
+    spec: *mut ColorWheelSpec,
+    radial_mapping: *const c_char,
+) -> ColorWheelStatus {
+    if radial_mapping.is_null() {
+        return ColorWheelStatus::NullPointer;
+    }
+
+    let Some(radial_mapping) = CStr::from_ptr(radial_mapping)
+        .to_str()
+        .ok()
+        .and_then(|radial_mapping| radial_mapping.parse::<RadialMapping>().ok())
+    else {
+        return ColorWheelStatus::InvalidArgument;
+    };
+
+    update_spec(spec, |spec| spec.radial_mapping = radial_mapping)
+}
+
+/// Adds a ring, which draws the spec as a single wheel of rings rather than its fixed values.
+/// Negative bucket counts use the spec's own.
+///
+/// # Safety
+///
+/// `spec` must be null or a valid spec.
+#[no_mangle]
+pub unsafe extern "C" fn color_wheel_spec_add_ring(
+    spec: *mut ColorWheelSpec,
+    fixed: f64,
+    weight: f64,
+    angular_buckets: i32,
+    radial_buckets: i32,
+) -> ColorWheelStatus {
+    update_spec(spec, |spec| {
+        spec.rings.push(ColorWheelRing {
+            options: RingOptions {
+                weight,
+                angle_buckets: u32::try_from(angular_buckets).ok(),
+                distance_buckets: u32::try_from(radial_buckets).ok(),
+            },
+            ..ColorWheelRing::new(fixed)
+        })
+    })
+}
+
+/// Removes every ring, so that the spec draws its fixed values again.
+///
+/// # Safety
+///
+/// `spec` must be null or a valid spec.
+#[no_mangle]
+pub unsafe extern "C" fn color_wheel_spec_clear_rings(
+    spec: *mut ColorWheelSpec,
+) -> ColorWheelStatus {
+    update_spec(spec, |spec| spec.rings.clear())
+}
+
+/// # Safety
+///
+/// `spec` must be null or a valid spec, and `width` and `height` must be null or writable.
+#[no_mangle]
+pub unsafe extern "C" fn color_wheel_spec_image_size(
+    spec: *const ColorWheelSpec,
+    width: *mut u32,
+    height: *mut u32,
+) -> ColorWheelStatus {
+    let Some(ColorWheelSpec(spec)) = spec.as_ref() else {
+        return ColorWheelStatus::NullPointer;
+    };
+    if width.is_null() || height.is_null() {
+        return ColorWheelStatus::NullPointer;
+    }
+
+    *width = spec.image_width();
+    *height = spec.image_height();
+    ColorWheelStatus::Ok
+}
+
+// Applies the update only if it leaves the spec valid, so that it can always be rendered.
+unsafe fn update_spec(
+    spec: *mut ColorWheelSpec,
+    update: impl FnOnce(&mut color_wheel_spec::ColorWheelSpec),
+) -> ColorWheelStatus {
+    let Some(ColorWheelSpec(spec)) = spec.as_mut() else {
+        return ColorWheelStatus::NullPointer;
+    };
+
+    let mut updated_spec = spec.clone();
+    update(&mut updated_spec);
+    if let Err(error) = updated_spec.validate() {
+        return error.into();
+    }
+
+    *spec = updated_spec;
+    ColorWheelStatus::Ok
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn it_should_validate_settings() {
+        let spec = color_wheel_spec_new(ColorWheelType::HsvFixedValue as u32);
+
+        unsafe {
+            assert_eq!(
+                color_wheel_spec_set_diameter(spec, 2),
+                ColorWheelStatus::OptionOutOfRange
+            );
+            assert_eq!(
+                color_wheel_spec_set_angular_buckets(spec, 1001),
+                ColorWheelStatus::OptionOutOfRange
+            );
+            assert_eq!(
+                color_wheel_spec_set_fixed(spec, [f64::NAN].as_ptr(), 1),
+                ColorWheelStatus::FixedValueOutOfRange
+            );
+            assert_eq!(
+                color_wheel_spec_set_fixed(spec, [1.5].as_ptr(), 1),
+                ColorWheelStatus::FixedValueOutOfRange
+            );
+            assert_eq!(
+                color_wheel_spec_set_fixed(spec, [].as_ptr(), 0),
+                ColorWheelStatus::NoColorWheels
+            );
+            assert_eq!(
+                color_wheel_spec_set_diameter(std::ptr::null_mut(), 100),
+                ColorWheelStatus::NullPointer
+            );
+
+            assert_eq!(
+                color_wheel_spec_set_diameter(spec, 100),
+                ColorWheelStatus::Ok
+            );
+            assert_eq!(color_wheel_spec_set_margin(spec, 5), ColorWheelStatus::Ok);
+            assert_eq!(
+                color_wheel_spec_set_fixed(spec, [1., 0.5].as_ptr(), 2),
+                ColorWheelStatus::Ok
+            );
+
+            let (mut width, mut height) = (0, 0);
+            assert_eq!(
+                color_wheel_spec_image_size(spec, &mut width, &mut height),
+                ColorWheelStatus::Ok
+            );
+            assert_eq!((width, height), (220, 110));
+
+            color_wheel_spec_free(spec);
+        }
+    }
+
+    #[test]
+    fn it_should_validate_shape_and_rings() {
+        let spec = color_wheel_spec_new(ColorWheelType::OklabFixedLightness as u32);
+
+        unsafe {
+            assert_eq!(
+                color_wheel_spec_set_supersampling(spec, 9),
+                ColorWheelStatus::OptionOutOfRange
+            );
+            assert_eq!(
+                color_wheel_spec_set_inner_radius(spec, 1.5),
+                ColorWheelStatus::OptionOutOfRange
+            );
+            assert_eq!(
+                color_wheel_spec_set_arc(spec, 90., 90.),
+                ColorWheelStatus::EmptyShape
+            );
+            assert_eq!(
+                color_wheel_spec_set_radial_mapping(spec, c"cubic".as_ptr()),
+                ColorWheelStatus::InvalidArgument
+            );
+            assert_eq!(
+                color_wheel_spec_add_ring(spec, 0.5, 0., -1, -1),
+                ColorWheelStatus::EmptyShape
+            );
+
+            assert_eq!(
+                color_wheel_spec_set_arc(spec, 270., 90.),
+                ColorWheelStatus::Ok
+            );
+            assert_eq!(
+                color_wheel_spec_set_radial_mapping(spec, c"gamma:2".as_ptr()),
+                ColorWheelStatus::Ok
+            );
+            assert_eq!(
+                color_wheel_spec_add_ring(spec, 0.5, 2., 12, -1),
+                ColorWheelStatus::Ok
+            );
+            assert_eq!(
+                (&(*spec).0.rings)[0].options,
+                RingOptions {
+                    weight: 2.,
+                    angle_buckets: Some(12),
+                    distance_buckets: None,
+                }
+            );
+            assert_eq!(color_wheel_spec_clear_rings(spec), ColorWheelStatus::Ok);
+            assert!((*spec).0.rings.is_empty());
+
+            color_wheel_spec_free(spec);
+        }
+    }
+    #[test]
+    fn it_should_reject_unknown_enum_values() {
+        assert!(color_wheel_spec_new(6).is_null());
+        assert!(color_wheel_spec_new(u32::MAX).is_null());
+
+        let spec = color_wheel_spec_new(ColorWheelType::HslFixedLightness as u32);
+        assert!(!spec.is_null());
+
+        unsafe {
+            assert_eq!(
+                color_wheel_spec_set_projection(spec, 4),
+                ColorWheelStatus::InvalidArgument
+            );
+            assert_eq!(
+                color_wheel_spec_set_resample_filter(spec, 42),
+                ColorWheelStatus::InvalidArgument
+            );
+            assert_eq!((*spec).0.shape.projection, ColorWheelProjection::Polar);
+
+            assert_eq!(
+                color_wheel_spec_set_projection(
+                    spec,
+                    ColorWheelProjectionType::UnrolledStrip as u32
+                ),
+                ColorWheelStatus::Ok
+            );
+            assert_eq!(
+                color_wheel_spec_set_resample_filter(
+                    spec,
+                    ColorWheelResampleFilterType::Mitchell as u32
+                ),
+                ColorWheelStatus::Ok
+            );
+            assert_eq!(
+                (*spec).0.shape.projection,
+                ColorWheelProjection::UnrolledStrip
+            );
+            assert_eq!((*spec).0.resample_filter, ResampleFilter::Mitchell);
+
+            color_wheel_spec_free(spec);
+        }
+    }
+}
@@ -0,0 +1,39 @@
+use color_wheel_lib::color_wheel_error::ColorWheelError;
+
+// Returned by every function which can fail. Invalid specs give the code of the reason they
+// can't be rendered, like the command line's exit codes.
+#[repr(C)]
+#[derive(Copy, Clone, PartialEq, Eq, Debug)]
+pub enum ColorWheelStatus {
+    Ok = 0,
+    NullPointer = 1,
+    InvalidArgument = 2,
+    BufferTooSmall = 3,
+    RenderFailed = 4,
+    NoColorWheels = 5,
+    ImageTooLarge = 6,
+    ImageTooSmall = 7,
+    FixedValueOutOfRange = 8,
+    OptionOutOfRange = 9,
+    EmptyShape = 10,
+    InvalidBucketLayout = 11,
+}
+
+impl From<ColorWheelError> for ColorWheelStatus {
+    fn from(error: ColorWheelError) -> Self {
+        match error {
+            ColorWheelError::NoColorWheels => ColorWheelStatus::NoColorWheels,
+            ColorWheelError::ImageTooLarge => ColorWheelStatus::ImageTooLarge,
+            ColorWheelError::ImageTooSmall => ColorWheelStatus::ImageTooSmall,
+            ColorWheelError::FixedValueOutOfRange { .. } => ColorWheelStatus::FixedValueOutOfRange,
+            ColorWheelError::OptionOutOfRange { .. } => ColorWheelStatus::OptionOutOfRange,
+            ColorWheelError::EmptyShape => ColorWheelStatus::EmptyShape,
+            ColorWheelError::InvalidBucketLayout => ColorWheelStatus::InvalidBucketLayout,
+
+            // Internal rendering errors which a spec can't cause.
+            ColorWheelError::NotEnoughRows { .. } | ColorWheelError::WrongRow { .. } => {
+                ColorWheelStatus::RenderFailed
+            }
+        }
+    }
+}
@@ -0,0 +1,8 @@
+// A stable C API for rendering color wheels. See `include/color_wheel.h`.
+mod color_wheel_spec;
+mod color_wheel_status;
+mod render;
+
+pub use color_wheel_spec::*;
+pub use color_wheel_status::ColorWheelStatus;
+pub use render::*;
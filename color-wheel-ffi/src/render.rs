@@ -0,0 +1,192 @@
+use std::panic::{self, AssertUnwindSafe};
+
+use color_wheel_lib::{
+    color_wheel_definition::ColorWheelDefinition, color_wheel_error::ColorWheelError,
+    color_wheel_query::ColorWheelQuery, pixel::Pixel, pixel_generators::PixelGenerator,
+    render_color_wheel_spec::render_color_wheel_spec,
+};
+
+use crate::{color_wheel_spec::ColorWheelSpec, color_wheel_status::ColorWheelStatus};
+
+/// Renders the spec's color wheels as RGBA pixels, row by row, into `buffer`. The buffer must
+/// hold at least `width * height * 4` bytes, as given by `color_wheel_spec_image_size`.
+///
+/// # Safety
+///
+/// `spec` must be null or a valid spec, and `buffer` must be null or point to `buffer_length`
+/// writable bytes.
+#[no_mangle]
+pub unsafe extern "C" fn color_wheel_render(
+    spec: *const ColorWheelSpec,
+    buffer: *mut u8,
+    buffer_length: usize,
+) -> ColorWheelStatus {
+    let Some(ColorWheelSpec(spec)) = spec.as_ref() else {
+        return ColorWheelStatus::NullPointer;
+    };
+    if buffer.is_null() {
+        return ColorWheelStatus::NullPointer;
+    }
+
+    let required_length = spec.image_width() as usize * spec.image_height() as usize * 4;
+    if buffer_length < required_length {
+        return ColorWheelStatus::BufferTooSmall;
+    }
+
+    // Panics mustn't unwind into the caller. The spec is only read, so a panic can't leave it
+    // half updated, although its thread pool isn't marked as unwind safe.
+    let canvas = match panic::catch_unwind(AssertUnwindSafe(|| render_color_wheel_spec(spec))) {
+        Ok(Ok(canvas)) => canvas,
+        Ok(Err(error)) => return error.into(),
+        Err(_) => return ColorWheelStatus::RenderFailed,
+    };
+
+    std::slice::from_raw_parts_mut(buffer, required_length).copy_from_slice(canvas.data());
+    ColorWheelStatus::Ok
+}
+
+/// Writes the RGBA color at a position on one of the spec's color wheels into `rgba`, without
+/// rendering an image. The angle is in degrees clockwise from the top, and the radius is
+/// from 0 at the center to 1 at the edge of the wheel. Positions in the gap between rings
+/// are transparent.
+///
+/// # Safety
+///
+/// `spec` must be null or a valid spec, and `rgba` must be null or point to 4 writable bytes.
+#[no_mangle]
+pub unsafe extern "C" fn color_wheel_color_at(
+    spec: *const ColorWheelSpec,
+    wheel_index: usize,
+    angle_degrees: f64,
+    radius: f64,
+    rgba: *mut u8,
+) -> ColorWheelStatus {
+    let Some(ColorWheelSpec(spec)) = spec.as_ref() else {
+        return ColorWheelStatus::NullPointer;
+    };
+    if rgba.is_null() {
+        return ColorWheelStatus::NullPointer;
+    }
+
+    if wheel_index >= spec.wheel_count() as usize
+        || !(0. ..=1.).contains(&radius)
+        || !angle_degrees.is_finite()
+    {
+        return ColorWheelStatus::InvalidArgument;
+    }
+
+    let angle_degrees = angle_degrees.rem_euclid(360.);
+    let pixel = match panic::catch_unwind(AssertUnwindSafe(|| {
+        color_at(
+            &spec.color_wheel_definitions()[wheel_index],
+            angle_degrees,
+            radius,
+        )
+    })) {
+        Ok(Ok(pixel)) => pixel,
+        Ok(Err(error)) => return error.into(),
+        Err(_) => return ColorWheelStatus::RenderFailed,
+    };
+
+    std::slice::from_raw_parts_mut(rgba, 4).copy_from_slice(&pixel.data);
+    ColorWheelStatus::Ok
+}
+
+// Looks up the color the same way as rendering a pixel. Positions off the wheel are transparent.
+fn color_at<TPixelGenerator: PixelGenerator>(
+    definition: &ColorWheelDefinition<TPixelGenerator>,
+    angle_degrees: f64,
+    radius: f64,
+) -> Result<Pixel, ColorWheelError> {
+    Ok(ColorWheelQuery::new(definition)?
+        .color_at_polar_position(angle_degrees, radius)
+        .map_or(Pixel::rgba(0, 0, 0, 0), |point| point.pixel))
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::color_wheel_spec::*;
+
+    use super::*;
+
+    #[test]
+    fn it_should_render_into_buffer() {
+        let spec = color_wheel_spec_new(ColorWheelType::HslFixedLightness as u32);
+
+        unsafe {
+            color_wheel_spec_set_diameter(spec, 31);
+            color_wheel_spec_set_margin(spec, 0);
+
+            let mut buffer = vec![0; 31 * 31 * 4];
+            assert_eq!(
+                color_wheel_render(spec, buffer.as_mut_ptr(), buffer.len() - 1),
+                ColorWheelStatus::BufferTooSmall
+            );
+            assert_eq!(
+                color_wheel_render(spec, buffer.as_mut_ptr(), buffer.len()),
+                ColorWheelStatus::Ok
+            );
+
+            // Transparent in the corner and opaque in the middle.
+            assert_eq!(buffer[3], 0);
+            assert_eq!(buffer[(15 * 31 + 15) * 4 + 3], 255);
+
+            color_wheel_spec_free(spec);
+        }
+    }
+
+    #[test]
+    fn it_should_return_the_reason_a_spec_cant_be_rendered() {
+        let spec = color_wheel_spec_new(ColorWheelType::HsvFixedValue as u32);
+
+        unsafe {
+            color_wheel_spec_set_diameter(spec, 21);
+            color_wheel_spec_set_fixed(spec, [0.5, 1.].as_ptr(), 2);
+            color_wheel_spec_set_expand(spec, true);
+            color_wheel_spec_set_ring_gap(spec, 30);
+
+            let mut buffer = vec![0; 41 * 41 * 4];
+            assert_eq!(
+                color_wheel_render(spec, buffer.as_mut_ptr(), buffer.len()),
+                ColorWheelStatus::ImageTooSmall
+            );
+
+            color_wheel_spec_free(spec);
+        }
+    }
+
+    #[test]
+    fn it_should_get_color_at_position() {
+        let spec = color_wheel_spec_new(ColorWheelType::HsvFixedValue as u32);
+
+        unsafe {
+            color_wheel_spec_set_fixed(spec, [0.5, 1.].as_ptr(), 2);
+            color_wheel_spec_set_expand(spec, true);
+
+            let mut rgba = [0; 4];
+            assert_eq!(
+                color_wheel_color_at(spec, 0, 0., 1., rgba.as_mut_ptr()),
+                ColorWheelStatus::Ok
+            );
+            assert_eq!(rgba, [255, 0, 0, 255]);
+
+            // Cyan, half way out of the inner ring at half saturation, with a value of 0.5.
+            assert_eq!(
+                color_wheel_color_at(spec, 0, 180., 0.25, rgba.as_mut_ptr()),
+                ColorWheelStatus::Ok
+            );
+            assert_eq!(rgba, [64, 128, 128, 255]);
+
+            assert_eq!(
+                color_wheel_color_at(spec, 1, 0., 0.5, rgba.as_mut_ptr()),
+                ColorWheelStatus::InvalidArgument
+            );
+            assert_eq!(
+                color_wheel_color_at(spec, 0, 0., 1.5, rgba.as_mut_ptr()),
+                ColorWheelStatus::InvalidArgument
+            );
+
+            color_wheel_spec_free(spec);
+        }
+    }
+}
@@ -0,0 +1,84 @@
+use color_wheel_lib::{
+    color_wheel_projection::ColorWheelProjection,
+    color_wheel_spec::ColorWheelSpec,
+    pixel::Pixel,
+    pixel_generators::{hsl_to_pixel, HslFixedLightnessPixelGenerator, PixelGenerator},
+    render_color_wheel_spec::render_color_wheel_spec,
+};
+use criterion::{black_box, criterion_group, criterion_main, Criterion};
+
+const RADIUS: f64 = 1000.;
+
+fn relative_xs() -> Vec<f64> {
+    (0..2000).map(|x| x as f64 + 0.5 - RADIUS).collect()
+}
+
+// Projecting a row a pixel at a time, against four pixels at a time.
+fn project(criterion: &mut Criterion) {
+    let relative_xs = relative_xs();
+    let projection = ColorWheelProjection::Polar;
+    let mut group = criterion.benchmark_group("project polar row");
+
+    group.bench_function("each pixel", |bencher| {
+        bencher.iter(|| {
+            relative_xs
+                .iter()
+                .map(|relative_x| projection.project(black_box(*relative_x), 300.5, RADIUS))
+                .collect::<Vec<_>>()
+        })
+    });
+    group.bench_function("lanes", |bencher| {
+        bencher.iter(|| projection.project_row(black_box(&relative_xs), 300.5, RADIUS))
+    });
+
+    group.finish();
+}
+
+// Converting a row of colors a pixel at a time, against four pixels at a time.
+fn convert(criterion: &mut Criterion) {
+    let angles_degrees = (0..2000).map(|x| x as f64 * 0.18).collect::<Vec<_>>();
+    let saturations = (0..2000).map(|x| x as f64 / 2000.).collect::<Vec<_>>();
+    let spec = ColorWheelSpec::new(HslFixedLightnessPixelGenerator::REGISTRATION);
+    let pixel_generator = HslFixedLightnessPixelGenerator {
+        lightness: 0.5,
+        configuration: spec.configuration(),
+    };
+    let mut pixels = vec![Pixel::transparent(); 2000];
+    let mut group = criterion.benchmark_group("convert hsl row");
+
+    group.bench_function("each pixel", |bencher| {
+        bencher.iter(|| {
+            for ((angle_degrees, saturation), pixel) in
+                angles_degrees.iter().zip(&saturations).zip(&mut pixels)
+            {
+                *pixel = hsl_to_pixel(black_box(*angle_degrees), *saturation, 0.5);
+            }
+        })
+    });
+    group.bench_function("lanes", |bencher| {
+        bencher.iter(|| {
+            pixel_generator.get_pixels(black_box(&angles_degrees), &saturations, &mut pixels)
+        })
+    });
+
+    group.finish();
+}
+
+fn render(criterion: &mut Criterion) {
+    let spec = ColorWheelSpec {
+        diameter: 500,
+        supersampling: 4,
+        ..ColorWheelSpec::new(HslFixedLightnessPixelGenerator::REGISTRATION)
+    };
+
+    let mut group = criterion.benchmark_group("render");
+    group.sample_size(10);
+    group.bench_function(
+        "hsl fixed lightness, 500 pixels, 4x supersampled",
+        |bencher| bencher.iter(|| render_color_wheel_spec(black_box(&spec)).unwrap()),
+    );
+    group.finish();
+}
+
+criterion_group!(benches, project, convert, render);
+criterion_main!(benches);
@@ -0,0 +1,97 @@
+use crate::pixel::Pixel;
+
+#[derive(Debug, PartialEq, Eq, Clone, Copy, Default)]
+pub enum Background {
+    #[default]
+    Transparent,
+    Solid(Pixel),
+
+    // Alternating squares of two colors, as commonly used to preview transparency.
+    Checkerboard {
+        size: u32,
+        first: Pixel,
+        second: Pixel,
+    },
+}
+
+impl Background {
+    pub fn checkerboard(size: u32) -> Self {
+        Background::Checkerboard {
+            size,
+            first: Pixel::rgb(255, 255, 255),
+            second: Pixel::rgb(204, 204, 204),
+        }
+    }
+
+    pub fn get_pixel(&self, x: u32, y: u32) -> Pixel {
+        match *self {
+            Background::Transparent => Pixel::transparent(),
+            Background::Solid(pixel) => pixel,
+            Background::Checkerboard {
+                size,
+                first,
+                second,
+            } => {
+                let size = size.max(1);
+                if (x / size + y / size).is_multiple_of(2) {
+                    first
+                } else {
+                    second
+                }
+            }
+        }
+    }
+
+    // Whether every pixel of the background is either fully opaque or fully transparent.
+    pub fn is_opaque_or_transparent(&self) -> bool {
+        let check = |pixel: Pixel| pixel.alpha() == 0 || pixel.alpha() == 255;
+        match *self {
+            Background::Transparent => true,
+            Background::Solid(pixel) => check(pixel),
+            Background::Checkerboard { first, second, .. } => check(first) && check(second),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn transparent_should_return_transparent_pixels() {
+        assert_eq!(
+            Background::Transparent.get_pixel(3, 4),
+            Pixel::transparent()
+        );
+    }
+
+    #[test]
+    fn solid_should_return_color() {
+        let background = Background::Solid(Pixel::rgb(1, 2, 3));
+        assert_eq!(background.get_pixel(0, 0), Pixel::rgb(1, 2, 3));
+        assert_eq!(background.get_pixel(100, 7), Pixel::rgb(1, 2, 3));
+    }
+
+    #[test]
+    fn checkerboard_should_alternate_squares() {
+        let background = Background::Checkerboard {
+            size: 2,
+            first: Pixel::rgb(1, 1, 1),
+            second: Pixel::rgb(2, 2, 2),
+        };
+
+        assert_eq!(background.get_pixel(0, 0), Pixel::rgb(1, 1, 1));
+        assert_eq!(background.get_pixel(1, 1), Pixel::rgb(1, 1, 1));
+        assert_eq!(background.get_pixel(2, 0), Pixel::rgb(2, 2, 2));
+        assert_eq!(background.get_pixel(0, 3), Pixel::rgb(2, 2, 2));
+        assert_eq!(background.get_pixel(2, 2), Pixel::rgb(1, 1, 1));
+    }
+
+    #[test]
+    fn it_should_detect_partial_transparency() {
+        assert!(Background::Transparent.is_opaque_or_transparent());
+        assert!(Background::Solid(Pixel::rgb(1, 2, 3)).is_opaque_or_transparent());
+        assert!(Background::checkerboard(8).is_opaque_or_transparent());
+        assert!(!Background::Solid(Pixel::rgba(1, 2, 3, 4)).is_opaque_or_transparent());
+    }
+}
@@ -1,14 +1,154 @@
+// Which point within each bucket gives the bucket's color.
 #[derive(Debug, PartialEq, Eq, Hash, Clone, Copy)]
 pub enum BucketDirection {
     Down,
     Up,
+    Center,
+}
+
+// How bucket boundaries are spaced along an axis.
+#[derive(Debug, PartialEq, Eq, Hash, Clone, Copy)]
+pub enum BucketSpacing {
+    // Boundaries are an equal distance apart, for example an equal number of degrees.
+    Uniform,
+
+    // Boundaries are an equal perceptual (OKLab) distance apart, averaged across the fixed slice.
+    Perceptual,
+}
+
+// Optional non-uniform buckets and sample positions along an axis. Both are proportions of
+// the axis, from 0 to 1.
+#[derive(Debug, Clone, PartialEq, Default)]
+pub struct BucketLayout {
+    // Boundaries between buckets in ascending order. When empty, buckets are evenly spaced.
+    pub boundaries: Vec<f64>,
+
+    // The position to sample for each bucket, instead of using the bucket direction.
+    // Buckets without a sample position use the bucket direction.
+    pub sample_positions: Vec<f64>,
+}
+
+impl BucketLayout {
+    pub fn bucket_count(&self, buckets: u32) -> u32 {
+        if self.boundaries.is_empty() {
+            buckets
+        } else {
+            self.boundaries.len() as u32 + 1
+        }
+    }
+
+    // Whether each sample position falls inside the bucket it gives the color of.
+    pub fn are_samples_within_buckets(&self, buckets: u32) -> bool {
+        let bucket_count = self.bucket_count(buckets);
+        if bucket_count < 1 {
+            return true;
+        }
+
+        let edges = bucket_edges(bucket_count, self);
+        self.sample_positions
+            .iter()
+            .zip(edges.windows(2))
+            .all(|(sample_position, edges)| (edges[0]..=edges[1]).contains(sample_position))
+    }
 }
 
 pub fn bucket(value: f64, maximum: f64, buckets: u32, direction: BucketDirection) -> f64 {
+    bucket_with_layout(value, maximum, buckets, direction, &BucketLayout::default())
+}
+
+pub fn bucket_with_layout(
+    value: f64,
+    maximum: f64,
+    buckets: u32,
+    direction: BucketDirection,
+    layout: &BucketLayout,
+) -> f64 {
+    let bucket_count = layout.bucket_count(buckets);
+    if bucket_count < 1 {
+        return value;
+    }
+
+    if layout.boundaries.is_empty() && layout.sample_positions.is_empty() {
+        match direction {
+            BucketDirection::Down => return bucket_down(value, maximum, buckets),
+            BucketDirection::Up => return bucket_up(value, maximum, buckets),
+            BucketDirection::Center => {}
+        }
+    }
+
+    let edges = bucket_edges(bucket_count, layout);
+    let index = edge_bucket_index(&edges, value / maximum, direction);
+
+    let sample_position = match layout.sample_positions.get(index) {
+        Some(sample_position) => *sample_position,
+        None => match direction {
+            BucketDirection::Down => edges[index],
+            BucketDirection::Up => edges[index + 1],
+            BucketDirection::Center => (edges[index] + edges[index + 1]) / 2.,
+        },
+    };
+
+    sample_position * maximum
+}
+
+// The index of the bucket which a value falls in, counting from the start of the axis, or
+// `None` when the axis isn't bucketed. Matches the bucket `bucket_with_layout` samples.
+pub fn bucket_index(
+    value: f64,
+    maximum: f64,
+    buckets: u32,
+    direction: BucketDirection,
+    layout: &BucketLayout,
+) -> Option<u32> {
+    let bucket_count = layout.bucket_count(buckets);
+    if bucket_count < 1 {
+        return None;
+    }
+
+    let last_index = bucket_count - 1;
+    if layout.boundaries.is_empty() && layout.sample_positions.is_empty() {
+        let factor = maximum / buckets as f64;
+        match direction {
+            BucketDirection::Down => {
+                return Some(((value / factor).floor().max(0.) as u32).min(last_index))
+            }
+            BucketDirection::Up => {
+                let buckets_from_end = ((maximum - value) / factor).floor().max(0.) as u32;
+                return Some(last_index.saturating_sub(buckets_from_end));
+            }
+            BucketDirection::Center => {}
+        }
+    }
+
+    let edges = bucket_edges(bucket_count, layout);
+    Some(edge_bucket_index(&edges, value / maximum, direction) as u32)
+}
+
+// The proportions along the axis at which each bucket starts, followed by the end of the axis.
+fn bucket_edges(bucket_count: u32, layout: &BucketLayout) -> Vec<f64> {
+    if layout.boundaries.is_empty() {
+        (0..=bucket_count)
+            .map(|index| index as f64 / bucket_count as f64)
+            .collect()
+    } else {
+        std::iter::once(0.)
+            .chain(layout.boundaries.iter().copied())
+            .chain(std::iter::once(1.))
+            .collect()
+    }
+}
+
+fn edge_bucket_index(edges: &[f64], proportion: f64, direction: BucketDirection) -> usize {
+    // As with uniform buckets, values on a boundary belong to the bucket below when
+    // bucketing up, and to the bucket above otherwise.
+    let last_index = edges.len() - 2;
     match direction {
-        BucketDirection::Down => bucket_down(value, maximum, buckets),
-        BucketDirection::Up => bucket_up(value, maximum, buckets),
+        BucketDirection::Up => edges[1..].partition_point(|edge| *edge < proportion),
+        _ => edges
+            .partition_point(|edge| *edge <= proportion)
+            .saturating_sub(1),
     }
+    .min(last_index)
 }
 
 fn bucket_down(value: f64, maximum: f64, buckets: u32) -> f64 {
@@ -31,6 +171,20 @@ mod tests {
 
     use super::*;
 
+    #[test]
+    fn it_should_check_samples_are_within_their_buckets() {
+        let layout = |boundaries: &[f64], sample_positions: &[f64]| BucketLayout {
+            boundaries: boundaries.to_vec(),
+            sample_positions: sample_positions.to_vec(),
+        };
+
+        assert!(layout(&[0.5], &[0.25, 0.5]).are_samples_within_buckets(0));
+        assert!(!layout(&[0.5], &[0.75, 0.25]).are_samples_within_buckets(0));
+        assert!(layout(&[], &[0.1, 0.3, 0.6]).are_samples_within_buckets(4));
+        assert!(!layout(&[], &[0.1, 0.6]).are_samples_within_buckets(4));
+        assert!(layout(&[], &[0.9]).are_samples_within_buckets(0));
+    }
+
     #[test]
     fn test_bucket_up() {
         assert_approx_eq!(f64, bucket(0.45, 1., 10, BucketDirection::Up), 0.5);
@@ -52,4 +206,93 @@ mod tests {
 
         assert_approx_eq!(f64, bucket(187., 360., 36, BucketDirection::Down), 180.);
     }
+
+    #[test]
+    fn test_bucket_center() {
+        assert_approx_eq!(f64, bucket(0.45, 1., 10, BucketDirection::Center), 0.45);
+        assert_approx_eq!(f64, bucket(0.5, 1., 10, BucketDirection::Center), 0.55);
+        assert_approx_eq!(f64, bucket(0.59, 1., 10, BucketDirection::Center), 0.55);
+        assert_approx_eq!(f64, bucket(1., 1., 10, BucketDirection::Center), 0.95);
+
+        assert_approx_eq!(f64, bucket(187., 360., 36, BucketDirection::Center), 185.);
+    }
+
+    #[test]
+    fn test_bucket_with_boundaries() {
+        let layout = BucketLayout {
+            boundaries: vec![0.2, 0.7],
+            sample_positions: vec![],
+        };
+
+        let bucket = |value, direction| bucket_with_layout(value, 10., 0, direction, &layout);
+
+        assert_approx_eq!(f64, bucket(1., BucketDirection::Down), 0.);
+        assert_approx_eq!(f64, bucket(2., BucketDirection::Down), 2.);
+        assert_approx_eq!(f64, bucket(6., BucketDirection::Down), 2.);
+        assert_approx_eq!(f64, bucket(10., BucketDirection::Down), 7.);
+
+        assert_approx_eq!(f64, bucket(0., BucketDirection::Up), 2.);
+        assert_approx_eq!(f64, bucket(2., BucketDirection::Up), 2.);
+        assert_approx_eq!(f64, bucket(6., BucketDirection::Up), 7.);
+        assert_approx_eq!(f64, bucket(8., BucketDirection::Up), 10.);
+
+        assert_approx_eq!(f64, bucket(3., BucketDirection::Center), 4.5);
+    }
+
+    #[test]
+    fn test_bucket_with_sample_positions() {
+        let layout = BucketLayout {
+            boundaries: vec![],
+            sample_positions: vec![0.1, 0.9],
+        };
+
+        let bucket = |value, direction| bucket_with_layout(value, 1., 3, direction, &layout);
+
+        assert_approx_eq!(f64, bucket(0.2, BucketDirection::Down), 0.1);
+        assert_approx_eq!(f64, bucket(0.5, BucketDirection::Down), 0.9);
+
+        // The third bucket has no sample position, so uses the direction.
+        assert_approx_eq!(f64, bucket(0.8, BucketDirection::Down), 2. / 3.);
+        assert_approx_eq!(f64, bucket(0.8, BucketDirection::Up), 1.);
+    }
+
+    #[test]
+    fn test_bucket_count() {
+        let layout = BucketLayout {
+            boundaries: vec![0.2, 0.7],
+            sample_positions: vec![],
+        };
+
+        assert_eq!(BucketLayout::default().bucket_count(5), 5);
+        assert_eq!(layout.bucket_count(5), 3);
+    }
+
+    #[test]
+    fn test_bucket_index() {
+        let uniform = BucketLayout::default();
+        let index = |value, direction| bucket_index(value, 360., 36, direction, &uniform);
+
+        assert_eq!(index(187., BucketDirection::Down), Some(18));
+        assert_eq!(index(187., BucketDirection::Up), Some(18));
+        assert_eq!(index(190., BucketDirection::Down), Some(19));
+        assert_eq!(index(190., BucketDirection::Up), Some(18));
+        assert_eq!(index(0., BucketDirection::Up), Some(0));
+        assert_eq!(index(360., BucketDirection::Down), Some(35));
+        assert_eq!(index(187., BucketDirection::Center), Some(18));
+        assert_eq!(
+            bucket_index(0.5, 1., 0, BucketDirection::Down, &uniform),
+            None
+        );
+
+        let layout = BucketLayout {
+            boundaries: vec![0.2, 0.7],
+            sample_positions: vec![],
+        };
+        let index = |value, direction| bucket_index(value, 1., 5, direction, &layout);
+
+        assert_eq!(index(0.1, BucketDirection::Down), Some(0));
+        assert_eq!(index(0.2, BucketDirection::Down), Some(1));
+        assert_eq!(index(0.2, BucketDirection::Up), Some(0));
+        assert_eq!(index(0.9, BucketDirection::Center), Some(2));
+    }
 }
@@ -0,0 +1,58 @@
+use super::*;
+
+impl Canvas {
+    pub fn map_pixels<TMap>(&mut self, x: u32, y: u32, width: u32, height: u32, map: TMap)
+    where
+        TMap: Fn(Pixel) -> Pixel,
+    {
+        if x + width > self.width || y + height > self.height {
+            panic!("Requested region is out of bounds: {x},{y} {width}x{height}");
+        }
+
+        for region_y in y..y + height {
+            for region_x in x..x + width {
+                let pixel = self.get_pixel(region_x, region_y);
+                self.set_pixel(region_x, region_y, map(pixel));
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn it_should_only_map_pixels_in_region() {
+        let mut target = canvas(3, 3);
+
+        target.map_pixels(1, 1, 2, 1, |_| Pixel::rgb(1, 2, 3));
+
+        let mapped: Vec<bool> = target
+            .iter_pixels()
+            .map(|p| p == Pixel::rgb(1, 2, 3))
+            .collect();
+
+        assert_eq!(
+            mapped,
+            vec![false, false, false, false, true, true, false, false, false]
+        );
+    }
+
+    #[test]
+    fn it_should_pass_existing_pixel_to_map() {
+        let mut target = canvas(1, 1);
+        target.set_pixel(0, 0, Pixel::rgb(10, 20, 30));
+
+        target.map_pixels(0, 0, 1, 1, |p| Pixel::rgb(p.blue(), p.green(), p.red()));
+
+        assert_eq!(target.get_pixel(0, 0), Pixel::rgb(30, 20, 10));
+    }
+
+    #[test]
+    #[should_panic]
+    fn when_region_out_of_bounds_it_should_panic() {
+        let mut target = canvas(2, 3);
+        target.map_pixels(1, 0, 2, 1, |p| p);
+    }
+}
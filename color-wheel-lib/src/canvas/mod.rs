@@ -1,10 +1,11 @@
-use crate::pixel::BYTES_PER_PIXEL;
+use crate::{background::Background, pixel::BYTES_PER_PIXEL};
 
 use super::pixel::Pixel;
 use super::pixel::TRANSPARENT;
 
 mod get_pixel;
 mod get_ppm;
+mod map_pixels;
 mod set_pixel;
 
 #[derive(Clone, Debug)]
@@ -23,6 +24,33 @@ impl Canvas {
         }
     }
 
+    pub fn with_background(width: u32, height: u32, background: Background) -> Canvas {
+        let mut canvas = Canvas::new(width, height);
+        if background == Background::Transparent {
+            return canvas;
+        }
+
+        for y in 0..height {
+            for x in 0..width {
+                canvas.set_pixel(x, y, background.get_pixel(x, y));
+            }
+        }
+
+        canvas
+    }
+
+    pub fn from_data(width: u32, height: u32, data: Vec<u8>) -> Canvas {
+        if data.len() != width as usize * height as usize * BYTES_PER_PIXEL {
+            panic!("Canvas data was not the expected size for a {width}x{height} canvas.");
+        }
+
+        Canvas {
+            width,
+            height,
+            data,
+        }
+    }
+
     pub fn width(&self) -> u32 {
         self.width
     }
@@ -73,6 +101,32 @@ mod tests {
         }
     }
 
+    #[test]
+    fn it_should_initialize_to_background() {
+        let background = Background::checkerboard(1);
+        let result = Canvas::with_background(2, 2, background);
+
+        assert_eq!(result.get_pixel(0, 0), background.get_pixel(0, 0));
+        assert_eq!(result.get_pixel(1, 0), background.get_pixel(1, 0));
+        assert_eq!(result.get_pixel(0, 1), background.get_pixel(0, 1));
+        assert_eq!(result.get_pixel(1, 1), background.get_pixel(1, 1));
+        assert_ne!(result.get_pixel(0, 0), result.get_pixel(1, 0));
+    }
+
+    #[test]
+    fn it_should_create_from_data() {
+        let result = Canvas::from_data(2, 1, vec![1, 2, 3, 4, 5, 6, 7, 8]);
+
+        assert_eq!(result.get_pixel(0, 0), Pixel::rgba(1, 2, 3, 4));
+        assert_eq!(result.get_pixel(1, 0), Pixel::rgba(5, 6, 7, 8));
+    }
+
+    #[test]
+    #[should_panic]
+    fn when_data_is_wrong_size_it_should_panic() {
+        Canvas::from_data(2, 2, vec![1, 2, 3, 4, 5, 6, 7, 8]);
+    }
+
     #[test]
     fn it_should_iterate_pixels() {
         let mut canvas = canvas(2, 2);
@@ -0,0 +1,49 @@
+use crate::{canvas::Canvas, pixel::Pixel};
+
+use super::canvas_filter::CanvasFilter;
+
+// Places the canvas over a solid color.
+pub struct BackgroundFillCanvasFilter {
+    pub color: Pixel,
+}
+
+impl CanvasFilter for BackgroundFillCanvasFilter {
+    fn execute(&self, canvas: &mut Canvas) {
+        let (width, height) = (canvas.width(), canvas.height());
+        canvas.map_pixels(0, 0, width, height, |pixel| pixel.over(self.color));
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::canvas::canvas;
+
+    use super::*;
+
+    #[test]
+    fn it_should_fill_transparent_pixels() {
+        let mut canvas = canvas(2, 1);
+        canvas.set_pixel(1, 0, Pixel::rgb(1, 2, 3));
+
+        let target = BackgroundFillCanvasFilter {
+            color: Pixel::rgb(255, 255, 255),
+        };
+        target.execute(&mut canvas);
+
+        assert_eq!(canvas.get_pixel(0, 0), Pixel::rgb(255, 255, 255));
+        assert_eq!(canvas.get_pixel(1, 0), Pixel::rgb(1, 2, 3));
+    }
+
+    #[test]
+    fn it_should_blend_partially_transparent_pixels() {
+        let mut canvas = canvas(1, 1);
+        canvas.set_pixel(0, 0, Pixel::rgba(255, 0, 0, 128));
+
+        let target = BackgroundFillCanvasFilter {
+            color: Pixel::rgb(0, 0, 255),
+        };
+        target.execute(&mut canvas);
+
+        assert_eq!(canvas.get_pixel(0, 0), Pixel::rgb(128, 0, 127));
+    }
+}
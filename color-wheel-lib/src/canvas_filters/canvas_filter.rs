@@ -0,0 +1,6 @@
+use crate::canvas::Canvas;
+
+#[cfg_attr(test, mockall::automock)]
+pub trait CanvasFilter {
+    fn execute(&self, canvas: &mut Canvas);
+}
@@ -0,0 +1,67 @@
+use crate::canvas::Canvas;
+
+use super::canvas_filter::CanvasFilter;
+
+// Applies each filter in turn, so the output of one filter is the input to the next.
+#[derive(Default)]
+pub struct CanvasFilterPipeline {
+    pub filters: Vec<Box<dyn CanvasFilter>>,
+}
+
+impl CanvasFilter for CanvasFilterPipeline {
+    fn execute(&self, canvas: &mut Canvas) {
+        for filter in self.filters.iter() {
+            filter.execute(canvas);
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use mockall::Sequence;
+
+    use crate::{canvas::canvas, canvas_filters::MockCanvasFilter, pixel::Pixel};
+
+    use super::*;
+
+    #[test]
+    fn it_should_apply_filters_in_order() {
+        let mut sequence = Sequence::new();
+
+        let mut first = MockCanvasFilter::new();
+        first
+            .expect_execute()
+            .once()
+            .in_sequence(&mut sequence)
+            .returning(|canvas| canvas.set_pixel(0, 0, Pixel::rgb(1, 2, 3)));
+
+        let mut second = MockCanvasFilter::new();
+        second
+            .expect_execute()
+            .once()
+            .in_sequence(&mut sequence)
+            .returning(|canvas| {
+                assert_eq!(canvas.get_pixel(0, 0), Pixel::rgb(1, 2, 3));
+                canvas.set_pixel(0, 0, Pixel::rgb(4, 5, 6));
+            });
+
+        let pipeline = CanvasFilterPipeline {
+            filters: vec![Box::new(first), Box::new(second)],
+        };
+
+        let mut canvas = canvas(1, 1);
+        pipeline.execute(&mut canvas);
+
+        assert_eq!(canvas.get_pixel(0, 0), Pixel::rgb(4, 5, 6));
+    }
+
+    #[test]
+    fn when_no_filters_it_should_not_change_canvas() {
+        let pipeline = CanvasFilterPipeline::default();
+
+        let mut canvas = canvas(1, 1);
+        pipeline.execute(&mut canvas);
+
+        assert_eq!(canvas.get_pixel(0, 0), Pixel::transparent());
+    }
+}
@@ -0,0 +1,45 @@
+use crate::{canvas::Canvas, color_vision_deficiency::ColorVisionDeficiencySimulation};
+
+use super::canvas_filter::CanvasFilter;
+
+pub struct ColorVisionDeficiencyCanvasFilter {
+    pub simulation: ColorVisionDeficiencySimulation,
+}
+
+impl CanvasFilter for ColorVisionDeficiencyCanvasFilter {
+    fn execute(&self, canvas: &mut Canvas) {
+        let (width, height) = (canvas.width(), canvas.height());
+        canvas.map_pixels(0, 0, width, height, |pixel| self.simulation.simulate(pixel));
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{canvas::canvas, color_vision_deficiency::ColorVisionDeficiency, pixel::Pixel};
+
+    use super::*;
+
+    #[test]
+    fn it_should_simulate_every_pixel() {
+        let mut canvas = canvas(2, 1);
+        canvas.set_pixel(0, 0, Pixel::rgb(255, 0, 0));
+        canvas.set_pixel(1, 0, Pixel::rgb(0, 0, 255));
+
+        let simulation = ColorVisionDeficiencySimulation {
+            deficiency: ColorVisionDeficiency::Tritanopia,
+            severity: 1.,
+        };
+
+        let target = ColorVisionDeficiencyCanvasFilter { simulation };
+        target.execute(&mut canvas);
+
+        assert_eq!(
+            canvas.get_pixel(0, 0),
+            simulation.simulate(Pixel::rgb(255, 0, 0))
+        );
+        assert_eq!(
+            canvas.get_pixel(1, 0),
+            simulation.simulate(Pixel::rgb(0, 0, 255))
+        );
+    }
+}
@@ -0,0 +1,91 @@
+use crate::{
+    canvas::Canvas,
+    dither::{dither, DitherMethod},
+};
+
+use super::canvas_filter::CanvasFilter;
+
+// Reduces each color channel to the given number of levels, using dithering to hide the
+// banding this would otherwise introduce. This is useful for previewing how a color wheel
+// would look with a limited palette.
+pub struct DitherCanvasFilter {
+    pub levels: u32,
+    pub method: DitherMethod,
+}
+
+impl CanvasFilter for DitherCanvasFilter {
+    fn execute(&self, canvas: &mut Canvas) {
+        if self.levels < 2 {
+            return;
+        }
+
+        let data = canvas
+            .data()
+            .iter()
+            .map(|value| *value as f64 / 255.)
+            .collect::<Vec<_>>();
+
+        *canvas = dither(
+            canvas.width(),
+            canvas.height(),
+            &data,
+            self.levels,
+            self.method,
+        );
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{canvas::canvas, pixel::Pixel};
+
+    use super::*;
+
+    #[test]
+    fn it_should_reduce_to_levels() {
+        let mut canvas = canvas(8, 8);
+        canvas.map_pixels(0, 0, 8, 8, |_| Pixel::rgb(0, 100, 255));
+
+        let target = DitherCanvasFilter {
+            levels: 2,
+            method: DitherMethod::Bayer,
+        };
+        target.execute(&mut canvas);
+
+        assert!(canvas
+            .iter_pixels()
+            .all(|p| p.red() == 0 && p.blue() == 255 && (p.green() == 0 || p.green() == 255)));
+
+        // The proportion of on pixels should approximate the original value.
+        let on_count = canvas.iter_pixels().filter(|p| p.green() == 255).count();
+        assert_eq!(on_count, 25);
+    }
+
+    #[test]
+    fn it_should_not_change_canvas_at_full_precision() {
+        let mut canvas = canvas(5, 5);
+        canvas.map_pixels(0, 0, 5, 5, |_| Pixel::rgba(30, 60, 90, 200));
+        let original = canvas.clone();
+
+        let target = DitherCanvasFilter {
+            levels: 256,
+            method: DitherMethod::FloydSteinberg,
+        };
+        target.execute(&mut canvas);
+
+        assert_eq!(canvas.data(), original.data());
+    }
+
+    #[test]
+    fn it_should_ignore_transparent_pixels() {
+        let mut canvas = canvas(2, 2);
+
+        let target = DitherCanvasFilter {
+            levels: 2,
+            method: DitherMethod::BlueNoise,
+        };
+        target.execute(&mut canvas);
+
+        assert!(canvas.iter_pixels().all(|p| p == Pixel::transparent()));
+    }
+}
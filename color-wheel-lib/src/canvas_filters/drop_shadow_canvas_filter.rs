@@ -0,0 +1,161 @@
+use crate::{canvas::Canvas, pixel::Pixel};
+
+use super::canvas_filter::CanvasFilter;
+
+// Repeated box blurs are a cheap approximation of a gaussian blur.
+const BLUR_PASSES: usize = 3;
+
+// Draws a blurred copy of the canvas's alpha channel, in the given color, behind the canvas.
+pub struct DropShadowCanvasFilter {
+    pub offset_x: i32,
+    pub offset_y: i32,
+    pub blur_radius: u32,
+    pub color: Pixel,
+}
+
+impl CanvasFilter for DropShadowCanvasFilter {
+    fn execute(&self, canvas: &mut Canvas) {
+        let width = canvas.width() as usize;
+        let height = canvas.height() as usize;
+
+        let mut shadow = vec![0.; width * height];
+        for y in 0..height {
+            for x in 0..width {
+                let source_x = x as i64 - self.offset_x as i64;
+                let source_y = y as i64 - self.offset_y as i64;
+                if source_x < 0
+                    || source_y < 0
+                    || source_x >= width as i64
+                    || source_y >= height as i64
+                {
+                    continue;
+                }
+
+                let alpha = canvas.get_pixel(source_x as u32, source_y as u32).alpha();
+                shadow[x + y * width] = alpha as f64 / 255.;
+            }
+        }
+
+        for _ in 0..BLUR_PASSES {
+            box_blur(&mut shadow, width, height, self.blur_radius as usize);
+        }
+
+        for y in 0..height {
+            for x in 0..width {
+                let shadow_alpha = self.color.alpha() as f64 * shadow[x + y * width];
+                let shadow_pixel = Pixel::rgba(
+                    self.color.red(),
+                    self.color.green(),
+                    self.color.blue(),
+                    shadow_alpha.round() as u8,
+                );
+
+                let pixel = canvas.get_pixel(x as u32, y as u32);
+                canvas.set_pixel(x as u32, y as u32, pixel.over(shadow_pixel));
+            }
+        }
+    }
+}
+
+fn box_blur(values: &mut [f64], width: usize, height: usize, radius: usize) {
+    if radius == 0 {
+        return;
+    }
+
+    let mut line = Vec::new();
+    for y in 0..height {
+        line.clear();
+        line.extend((0..width).map(|x| values[x + y * width]));
+        for (x, value) in blur_line(&line, radius).into_iter().enumerate() {
+            values[x + y * width] = value;
+        }
+    }
+
+    for x in 0..width {
+        line.clear();
+        line.extend((0..height).map(|y| values[x + y * width]));
+        for (y, value) in blur_line(&line, radius).into_iter().enumerate() {
+            values[x + y * width] = value;
+        }
+    }
+}
+
+// Averages each value with its neighbours within the radius, treating values beyond the
+// ends of the line as zero.
+fn blur_line(line: &[f64], radius: usize) -> Vec<f64> {
+    let window_size = (radius * 2 + 1) as f64;
+    let mut result = Vec::with_capacity(line.len());
+
+    let mut sum: f64 = line.iter().take(radius).sum();
+    for index in 0..line.len() {
+        if index + radius < line.len() {
+            sum += line[index + radius];
+        }
+
+        result.push(sum / window_size);
+
+        if index >= radius {
+            sum -= line[index - radius];
+        }
+    }
+
+    result
+}
+
+#[cfg(test)]
+mod tests {
+    use float_cmp::assert_approx_eq;
+
+    use crate::canvas::canvas;
+
+    use super::*;
+
+    #[test]
+    fn it_should_draw_offset_shadow_behind_canvas() {
+        let mut canvas = canvas(4, 4);
+        canvas.set_pixel(1, 1, Pixel::rgb(255, 0, 0));
+
+        let target = DropShadowCanvasFilter {
+            offset_x: 1,
+            offset_y: 2,
+            blur_radius: 0,
+            color: Pixel::rgba(0, 0, 0, 128),
+        };
+        target.execute(&mut canvas);
+
+        assert_eq!(canvas.get_pixel(1, 1), Pixel::rgb(255, 0, 0));
+        assert_eq!(canvas.get_pixel(2, 3), Pixel::rgba(0, 0, 0, 128));
+        assert_eq!(canvas.get_pixel(2, 2), Pixel::transparent());
+    }
+
+    #[test]
+    fn it_should_blur_shadow() {
+        let mut canvas = canvas(9, 1);
+        canvas.set_pixel(4, 0, Pixel::rgb(255, 255, 255));
+
+        let target = DropShadowCanvasFilter {
+            offset_x: 0,
+            offset_y: 0,
+            blur_radius: 1,
+            color: Pixel::rgb(0, 0, 0),
+        };
+        target.execute(&mut canvas);
+
+        assert_eq!(canvas.get_pixel(4, 0), Pixel::rgb(255, 255, 255));
+        assert!(canvas.get_pixel(3, 0).alpha() > 0);
+        assert!(canvas.get_pixel(5, 0).alpha() > 0);
+        assert_eq!(canvas.get_pixel(0, 0), Pixel::transparent());
+    }
+
+    #[test]
+    fn blur_line_should_preserve_total_away_from_edges() {
+        let line = [0., 0., 0., 3., 0., 0., 0.];
+        let result = blur_line(&line, 1);
+
+        assert_eq!(result.len(), line.len());
+        assert_approx_eq!(f64, result[2], 1.);
+        assert_approx_eq!(f64, result[3], 1.);
+        assert_approx_eq!(f64, result[4], 1.);
+        assert_approx_eq!(f64, result.iter().sum::<f64>(), 3.);
+    }
+}
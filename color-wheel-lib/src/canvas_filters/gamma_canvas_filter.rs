@@ -0,0 +1,53 @@
+use crate::{canvas::Canvas, pixel::Pixel};
+
+use super::canvas_filter::CanvasFilter;
+
+// Raises each color channel to the power of 1 / gamma, so values above 1 brighten the
+// image and values below 1 darken it.
+pub struct GammaCanvasFilter {
+    pub gamma: f64,
+}
+
+impl CanvasFilter for GammaCanvasFilter {
+    fn execute(&self, canvas: &mut Canvas) {
+        let exponent = 1. / self.gamma;
+        let adjust = |value: u8| ((value as f64 / 255.).powf(exponent) * 255.).round() as u8;
+
+        let (width, height) = (canvas.width(), canvas.height());
+        canvas.map_pixels(0, 0, width, height, |pixel| {
+            Pixel::rgba(
+                adjust(pixel.red()),
+                adjust(pixel.green()),
+                adjust(pixel.blue()),
+                pixel.alpha(),
+            )
+        });
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::canvas::canvas;
+
+    use super::*;
+
+    #[test]
+    fn it_should_adjust_gamma() {
+        let mut canvas = canvas(1, 1);
+        canvas.set_pixel(0, 0, Pixel::rgba(0, 64, 255, 200));
+
+        GammaCanvasFilter { gamma: 2. }.execute(&mut canvas);
+
+        assert_eq!(canvas.get_pixel(0, 0), Pixel::rgba(0, 128, 255, 200));
+    }
+
+    #[test]
+    fn when_gamma_is_one_it_should_not_change_canvas() {
+        let mut canvas = canvas(1, 1);
+        canvas.set_pixel(0, 0, Pixel::rgb(10, 100, 200));
+
+        GammaCanvasFilter { gamma: 1. }.execute(&mut canvas);
+
+        assert_eq!(canvas.get_pixel(0, 0), Pixel::rgb(10, 100, 200));
+    }
+}
@@ -0,0 +1,43 @@
+use crate::{
+    canvas::Canvas,
+    color_vision_deficiency::{ColorVisionDeficiency, ColorVisionDeficiencySimulation},
+};
+
+use super::canvas_filter::CanvasFilter;
+
+// Converts each pixel to its relative luminance, which is the same as simulating
+// complete achromatopsia.
+pub struct GrayscaleCanvasFilter {}
+
+impl CanvasFilter for GrayscaleCanvasFilter {
+    fn execute(&self, canvas: &mut Canvas) {
+        let simulation = ColorVisionDeficiencySimulation {
+            deficiency: ColorVisionDeficiency::Achromatopsia,
+            severity: 1.,
+        };
+
+        let (width, height) = (canvas.width(), canvas.height());
+        canvas.map_pixels(0, 0, width, height, |pixel| simulation.simulate(pixel));
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{canvas::canvas, pixel::Pixel};
+
+    use super::*;
+
+    #[test]
+    fn it_should_convert_to_grayscale() {
+        let mut canvas = canvas(3, 1);
+        canvas.set_pixel(0, 0, Pixel::rgb(255, 0, 0));
+        canvas.set_pixel(1, 0, Pixel::rgb(0, 255, 0));
+        canvas.set_pixel(2, 0, Pixel::rgba(0, 0, 255, 100));
+
+        GrayscaleCanvasFilter {}.execute(&mut canvas);
+
+        assert_eq!(canvas.get_pixel(0, 0), Pixel::rgb(127, 127, 127));
+        assert_eq!(canvas.get_pixel(1, 0), Pixel::rgb(220, 220, 220));
+        assert_eq!(canvas.get_pixel(2, 0), Pixel::rgba(76, 76, 76, 100));
+    }
+}
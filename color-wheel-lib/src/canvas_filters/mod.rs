@@ -0,0 +1,23 @@
+mod background_fill_canvas_filter;
+mod canvas_filter;
+mod canvas_filter_pipeline;
+mod color_vision_deficiency_canvas_filter;
+mod dither_canvas_filter;
+mod drop_shadow_canvas_filter;
+mod gamma_canvas_filter;
+mod grayscale_canvas_filter;
+mod vignette_canvas_filter;
+
+pub use background_fill_canvas_filter::BackgroundFillCanvasFilter;
+pub use canvas_filter_pipeline::CanvasFilterPipeline;
+pub use color_vision_deficiency_canvas_filter::ColorVisionDeficiencyCanvasFilter;
+pub use dither_canvas_filter::DitherCanvasFilter;
+pub use drop_shadow_canvas_filter::DropShadowCanvasFilter;
+pub use gamma_canvas_filter::GammaCanvasFilter;
+pub use grayscale_canvas_filter::GrayscaleCanvasFilter;
+pub use vignette_canvas_filter::VignetteCanvasFilter;
+
+pub use canvas_filter::CanvasFilter;
+
+#[cfg(test)]
+pub(crate) use canvas_filter::MockCanvasFilter;
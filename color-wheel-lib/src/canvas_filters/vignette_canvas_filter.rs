@@ -0,0 +1,79 @@
+use crate::{canvas::Canvas, pixel::Pixel};
+
+use super::canvas_filter::CanvasFilter;
+
+// Darkens the canvas towards the corners. A strength of 0 has no effect, and a strength
+// of 1 darkens the corners to black.
+pub struct VignetteCanvasFilter {
+    pub strength: f64,
+}
+
+impl CanvasFilter for VignetteCanvasFilter {
+    fn execute(&self, canvas: &mut Canvas) {
+        let half_width = canvas.width() as f64 / 2.;
+        let half_height = canvas.height() as f64 / 2.;
+
+        for y in 0..canvas.height() {
+            for x in 0..canvas.width() {
+                let relative_x = (x as f64 + 0.5 - half_width) / half_width;
+                let relative_y = (y as f64 + 0.5 - half_height) / half_height;
+
+                // Normalized so that the corners of the canvas are at a distance of 1.
+                let distance_squared = (relative_x.powi(2) + relative_y.powi(2)) / 2.;
+                let factor = (1. - self.strength * distance_squared).clamp(0., 1.);
+
+                let darken = |value: u8| (value as f64 * factor).round() as u8;
+
+                let pixel = canvas.get_pixel(x, y);
+                canvas.set_pixel(
+                    x,
+                    y,
+                    Pixel::rgba(
+                        darken(pixel.red()),
+                        darken(pixel.green()),
+                        darken(pixel.blue()),
+                        pixel.alpha(),
+                    ),
+                );
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::canvas::canvas;
+
+    use super::*;
+
+    fn white_canvas(width: u32, height: u32) -> Canvas {
+        let mut canvas = canvas(width, height);
+        canvas.map_pixels(0, 0, width, height, |_| Pixel::rgb(255, 255, 255));
+        canvas
+    }
+
+    #[test]
+    fn it_should_darken_towards_corners() {
+        let mut canvas = white_canvas(9, 9);
+
+        VignetteCanvasFilter { strength: 0.5 }.execute(&mut canvas);
+
+        let center = canvas.get_pixel(4, 4);
+        let edge = canvas.get_pixel(8, 4);
+        let corner = canvas.get_pixel(8, 8);
+
+        assert_eq!(center, Pixel::rgb(255, 255, 255));
+        assert!(edge.red() < center.red());
+        assert!(corner.red() < edge.red());
+        assert_eq!(corner.alpha(), 255);
+    }
+
+    #[test]
+    fn when_strength_is_zero_it_should_not_change_canvas() {
+        let mut canvas = white_canvas(3, 3);
+
+        VignetteCanvasFilter { strength: 0. }.execute(&mut canvas);
+
+        assert!(canvas.iter_pixels().all(|p| p == Pixel::rgb(255, 255, 255)));
+    }
+}
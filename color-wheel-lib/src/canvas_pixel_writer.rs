@@ -1,7 +1,8 @@
 use crate::{
+    background::Background,
     canvas::Canvas,
     pixel::BYTES_PER_PIXEL,
-    row_pixel_writer::{DefaultRowPixelWriter, RowPixelWriter},
+    row_pixel_writer::{ColorRowPixelWriter, DefaultRowPixelWriter, RowPixelWriter},
 };
 
 pub trait CanvasPixelWriter {
@@ -22,6 +23,12 @@ impl DefaultCanvasPixelWriter {
             canvas: Canvas::new(width, height),
         }
     }
+
+    pub fn with_background(width: u32, height: u32, background: Background) -> Self {
+        Self {
+            canvas: Canvas::with_background(width, height, background),
+        }
+    }
 }
 
 impl CanvasPixelWriter for DefaultCanvasPixelWriter {
@@ -45,6 +52,44 @@ impl CanvasPixelWriter for DefaultCanvasPixelWriter {
     }
 }
 
+// Keeps the rendered colors at full precision, as normalized, gamma encoded RGBA, so they can
+// be dithered rather than rounded to 8 bits per channel.
+pub struct ColorCanvasPixelWriter {
+    pub width: u32,
+    pub height: u32,
+    pub data: Vec<f64>,
+}
+
+impl ColorCanvasPixelWriter {
+    pub fn with_background(width: u32, height: u32, background: Background) -> Self {
+        let canvas = Canvas::with_background(width, height, background);
+        Self {
+            width,
+            height,
+            data: canvas
+                .data()
+                .iter()
+                .map(|value| *value as f64 / 255.)
+                .collect(),
+        }
+    }
+}
+
+impl CanvasPixelWriter for ColorCanvasPixelWriter {
+    type RowPixelWriter<'canvas> = ColorRowPixelWriter<'canvas>;
+
+    fn rows_mut(&mut self) -> Vec<Self::RowPixelWriter<'_>> {
+        self.data
+            .chunks_mut(BYTES_PER_PIXEL * self.width as usize)
+            .enumerate()
+            .map(|(row_index, data)| ColorRowPixelWriter {
+                row_index: row_index.try_into().expect("Row index was too large."),
+                data,
+            })
+            .collect()
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use crate::pixel::Pixel;
@@ -59,10 +104,25 @@ mod tests {
 
         assert_eq!(rows.len(), 3);
 
-        rows[0].write_pixel(0, 0, Pixel::rgb(1, 2, 3));
-        rows[2].write_pixel(2, 2, Pixel::rgb(4, 5, 6));
+        rows[0].write_pixel(0, 0, Pixel::rgb(1, 2, 3)).unwrap();
+        rows[2].write_pixel(2, 2, Pixel::rgb(4, 5, 6)).unwrap();
 
         assert_eq!(pixel_writer.canvas.get_pixel(0, 0), Pixel::rgb(1, 2, 3));
         assert_eq!(pixel_writer.canvas.get_pixel(2, 2), Pixel::rgb(4, 5, 6));
     }
+
+    #[test]
+    fn it_should_write_colors_to_correct_buffer_locations() {
+        let mut pixel_writer =
+            ColorCanvasPixelWriter::with_background(2, 2, Background::Solid(Pixel::rgb(0, 0, 255)));
+
+        let mut rows = pixel_writer.rows_mut();
+        assert_eq!(rows.len(), 2);
+        rows[1]
+            .write_color(1, 1, crate::color::Color::srgb(0.1, 0.2, 0.3))
+            .unwrap();
+
+        assert_eq!(&pixel_writer.data[..4], &[0., 0., 1., 1.]);
+        assert_eq!(&pixel_writer.data[12..], &[0.1, 0.2, 0.3, 1.]);
+    }
 }
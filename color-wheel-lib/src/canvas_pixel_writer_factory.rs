@@ -1,4 +1,7 @@
-use crate::canvas_pixel_writer::{CanvasPixelWriter, DefaultCanvasPixelWriter};
+use crate::{
+    background::Background,
+    canvas_pixel_writer::{CanvasPixelWriter, ColorCanvasPixelWriter, DefaultCanvasPixelWriter},
+};
 
 pub trait CanvasPixelWriterFactory {
     type Result: CanvasPixelWriter;
@@ -6,27 +9,56 @@ pub trait CanvasPixelWriterFactory {
     fn create(&self, width: u32, height: u32) -> Self::Result;
 }
 
-pub struct DefaultCanvasPixelWriterFactory {}
+pub struct DefaultCanvasPixelWriterFactory {
+    pub background: Background,
+}
 
 impl CanvasPixelWriterFactory for DefaultCanvasPixelWriterFactory {
     type Result = DefaultCanvasPixelWriter;
 
     fn create(&self, width: u32, height: u32) -> Self::Result {
-        DefaultCanvasPixelWriter::new(width, height)
+        DefaultCanvasPixelWriter::with_background(width, height, self.background)
+    }
+}
+
+pub struct ColorCanvasPixelWriterFactory {
+    pub background: Background,
+}
+
+impl CanvasPixelWriterFactory for ColorCanvasPixelWriterFactory {
+    type Result = ColorCanvasPixelWriter;
+
+    fn create(&self, width: u32, height: u32) -> Self::Result {
+        ColorCanvasPixelWriter::with_background(width, height, self.background)
     }
 }
 
 #[cfg(test)]
 mod tests {
-    use crate::pixel::BYTES_PER_PIXEL;
+    use crate::pixel::{Pixel, BYTES_PER_PIXEL};
 
     use super::*;
 
     #[test]
     fn it_should_return_canvas_with_correctly_sized_buffer() {
-        let factory = DefaultCanvasPixelWriterFactory {};
+        let factory = DefaultCanvasPixelWriterFactory {
+            background: Background::Transparent,
+        };
         let canvas = factory.create(3, 4);
 
         assert_eq!(canvas.canvas.data().len(), 3 * 4 * BYTES_PER_PIXEL);
     }
+
+    #[test]
+    fn it_should_fill_canvas_with_background() {
+        let factory = DefaultCanvasPixelWriterFactory {
+            background: Background::Solid(Pixel::rgb(1, 2, 3)),
+        };
+        let canvas = factory.create(3, 4);
+
+        assert!(canvas
+            .canvas
+            .iter_pixels()
+            .all(|pixel| pixel == Pixel::rgb(1, 2, 3)));
+    }
 }
@@ -0,0 +1,153 @@
+use crate::{
+    linear_rgb::{linear_to_srgb, srgb_to_linear},
+    pixel::Pixel,
+};
+
+// The color space a color's components are in.
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+pub enum ColorSpace {
+    // Gamma encoded sRGB red, green and blue, from 0 to 1.
+    Srgb,
+    // OKLab lightness, a and b.
+    Oklab,
+}
+
+// A color at full precision, in the space it was generated in. Pixel generators return these so
+// that callers can choose how to convert or quantize them, rather than starting from 8 bits.
+#[derive(Debug, Copy, Clone, PartialEq)]
+pub struct Color {
+    pub space: ColorSpace,
+    pub components: [f64; 3],
+    // From 0 (transparent) to 1 (opaque).
+    pub alpha: f64,
+}
+
+impl Color {
+    pub fn srgb(red: f64, green: f64, blue: f64) -> Self {
+        Self {
+            space: ColorSpace::Srgb,
+            components: [red, green, blue],
+            alpha: 1.,
+        }
+    }
+
+    pub fn oklab(lightness: f64, a: f64, b: f64) -> Self {
+        Self {
+            space: ColorSpace::Oklab,
+            components: [lightness, a, b],
+            alpha: 1.,
+        }
+    }
+
+    pub fn with_alpha(self, alpha: f64) -> Self {
+        Self { alpha, ..self }
+    }
+
+    // Gamma encoded sRGB. Colors outside the sRGB gamut have components outside 0 to 1.
+    pub fn to_srgb(&self) -> [f64; 3] {
+        match self.space {
+            ColorSpace::Srgb => self.components,
+            ColorSpace::Oklab => self.to_linear_srgb().map(linear_to_srgb),
+        }
+    }
+
+    // Linear light sRGB, for blending and resampling.
+    pub fn to_linear_srgb(&self) -> [f64; 3] {
+        match self.space {
+            ColorSpace::Srgb => self.components.map(srgb_to_linear),
+            ColorSpace::Oklab => {
+                let [lightness, a, b] = self.components;
+                oklab_to_linear_srgb(lightness, a, b)
+            }
+        }
+    }
+
+    // Quantizes to 8 bits per channel, clamping colors outside the sRGB gamut.
+    pub fn to_pixel(&self) -> Pixel {
+        let alpha = (self.alpha.clamp(0., 1.) * 255.).round() as u8;
+
+        let [red, green, blue] = self.to_srgb();
+        Pixel::from_normalized(red, green, blue).with_alpha(alpha)
+    }
+}
+
+// https://bottosson.github.io/posts/oklab/
+fn oklab_to_linear_srgb(lightness: f64, a: f64, b: f64) -> [f64; 3] {
+    let l = (lightness + 0.3963377774 * a + 0.2158037573 * b).powi(3);
+    let m = (lightness - 0.1055613458 * a - 0.0638541728 * b).powi(3);
+    let s = (lightness - 0.0894841775 * a - 1.2914855480 * b).powi(3);
+
+    [
+        4.0767416621 * l - 3.3077115913 * m + 0.2309699292 * s,
+        -1.2684380046 * l + 2.6097574011 * m - 0.3413193965 * s,
+        -0.0041960863 * l - 0.7034186147 * m + 1.7076147010 * s,
+    ]
+}
+
+#[cfg(test)]
+mod tests {
+    use float_cmp::assert_approx_eq;
+
+    use super::*;
+
+    fn assert_components_approx_eq(actual: [f64; 3], expected: [f64; 3]) {
+        for (actual, expected) in actual.into_iter().zip(expected) {
+            assert_approx_eq!(f64, actual, expected, epsilon = 0.00001);
+        }
+    }
+
+    #[test]
+    fn srgb_colors_should_quantize_like_normalized_pixels() {
+        assert_eq!(
+            Color::srgb(1., 0.5, 0.2).to_pixel(),
+            Pixel::from_normalized(1., 0.5, 0.2)
+        );
+        assert_eq!(
+            Color::srgb(1., 0.5, 0.2).with_alpha(0.5).to_pixel(),
+            Pixel::rgba(255, 128, 51, 128)
+        );
+    }
+
+    #[test]
+    fn oklab_colors_should_quantize_from_full_precision_srgb() {
+        for (lightness, a, b) in [
+            (1., 0., 0.),
+            (0., 0., 0.),
+            (0.6, 0.1, -0.05),
+            (0.9, 0.3, 0.),
+        ] {
+            let color = Color::oklab(lightness, a, b);
+            let [red, green, blue] = color.to_srgb();
+            assert_eq!(color.to_pixel(), Pixel::from_normalized(red, green, blue));
+        }
+    }
+
+    #[test]
+    fn it_should_convert_oklab_to_srgb() {
+        assert_components_approx_eq(Color::oklab(1., 0., 0.).to_srgb(), [1., 1., 1.]);
+        assert_components_approx_eq(Color::oklab(0., 0., 0.).to_srgb(), [0., 0., 0.]);
+
+        // Pure red, from the reference implementation.
+        assert_components_approx_eq(
+            Color::oklab(0.62795536, 0.22486306, 0.1258463).to_srgb(),
+            [1., 0., 0.],
+        );
+    }
+
+    #[test]
+    fn it_should_keep_precision_beyond_eight_bits() {
+        let color = Color::srgb(0.5001, 0.5002, 0.5003);
+
+        assert_eq!(color.to_srgb(), [0.5001, 0.5002, 0.5003]);
+        assert_eq!(color.to_pixel(), Color::srgb(0.5, 0.5, 0.5).to_pixel());
+    }
+
+    #[test]
+    fn it_should_convert_to_linear_srgb() {
+        assert_components_approx_eq(
+            Color::srgb(0., 0.5, 1.).to_linear_srgb(),
+            [0., srgb_to_linear(0.5), 1.],
+        );
+        assert_components_approx_eq(Color::oklab(1., 0., 0.).to_linear_srgb(), [1., 1., 1.]);
+    }
+}
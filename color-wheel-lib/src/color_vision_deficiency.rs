@@ -0,0 +1,174 @@
+use crate::{
+    linear_rgb::{linear_to_srgb, srgb_to_linear},
+    pixel::Pixel,
+};
+
+#[derive(Debug, PartialEq, Eq, Hash, Clone, Copy)]
+pub enum ColorVisionDeficiency {
+    Protanopia,
+    Deuteranopia,
+    Tritanopia,
+    Achromatopsia,
+}
+
+type Matrix = [[f64; 3]; 3];
+
+// Machado, Oliveira and Fernandes (2009), "A Physiologically-based Model for Simulation
+// of Color Vision Deficiency", severity 1.0. These operate on linear RGB.
+// https://www.inf.ufrgs.br/~oliveira/pubs_files/CVD_Simulation/CVD_Simulation.html
+const PROTANOPIA: Matrix = [
+    [0.152286, 1.052583, -0.204868],
+    [0.114503, 0.786281, 0.099216],
+    [-0.003882, -0.048116, 1.051998],
+];
+
+const DEUTERANOPIA: Matrix = [
+    [0.367322, 0.860646, -0.227968],
+    [0.280085, 0.672501, 0.047413],
+    [-0.011820, 0.042940, 0.968881],
+];
+
+const TRITANOPIA: Matrix = [
+    [1.255528, -0.076749, -0.178779],
+    [-0.078411, 0.930809, 0.147602],
+    [0.004733, 0.691367, 0.303900],
+];
+
+// Rec. 709 relative luminance.
+const ACHROMATOPSIA: Matrix = [
+    [0.2126, 0.7152, 0.0722],
+    [0.2126, 0.7152, 0.0722],
+    [0.2126, 0.7152, 0.0722],
+];
+
+#[derive(Debug, PartialEq, Clone, Copy)]
+pub struct ColorVisionDeficiencySimulation {
+    pub deficiency: ColorVisionDeficiency,
+
+    // From 0 (normal vision) to 1 (complete loss of the affected cone type).
+    // Partial severities blend between the original and fully simulated color in linear light.
+    pub severity: f64,
+}
+
+impl ColorVisionDeficiencySimulation {
+    pub fn simulate(&self, pixel: Pixel) -> Pixel {
+        if pixel.alpha() == 0 {
+            return pixel;
+        }
+
+        let [red, green, blue] = self.simulate_normalized(
+            [pixel.red(), pixel.green(), pixel.blue()].map(|value| value as f64 / 255.),
+        );
+
+        Pixel::from_normalized(red, green, blue).with_alpha(pixel.alpha())
+    }
+
+    // Simulates a normalized, gamma encoded sRGB color, keeping full precision.
+    pub fn simulate_normalized(&self, rgb: [f64; 3]) -> [f64; 3] {
+        let matrix = match self.deficiency {
+            ColorVisionDeficiency::Protanopia => &PROTANOPIA,
+            ColorVisionDeficiency::Deuteranopia => &DEUTERANOPIA,
+            ColorVisionDeficiency::Tritanopia => &TRITANOPIA,
+            ColorVisionDeficiency::Achromatopsia => &ACHROMATOPSIA,
+        };
+
+        let severity = self.severity.clamp(0., 1.);
+        let linear = rgb.map(srgb_to_linear);
+
+        // Blends between the original and fully simulated color in linear light, as the matrices
+        // are, before encoding back to sRGB.
+        let simulated = matrix.map(|row| {
+            let value = row[0] * linear[0] + row[1] * linear[1] + row[2] * linear[2];
+            value.clamp(0., 1.)
+        });
+
+        [0, 1, 2].map(|index| {
+            let original = linear[index];
+            linear_to_srgb(original + (simulated[index] - original) * severity)
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn simulation(
+        deficiency: ColorVisionDeficiency,
+        severity: f64,
+    ) -> ColorVisionDeficiencySimulation {
+        ColorVisionDeficiencySimulation {
+            deficiency,
+            severity,
+        }
+    }
+
+    #[test]
+    fn it_should_preserve_grays() {
+        for deficiency in [
+            ColorVisionDeficiency::Protanopia,
+            ColorVisionDeficiency::Deuteranopia,
+            ColorVisionDeficiency::Tritanopia,
+            ColorVisionDeficiency::Achromatopsia,
+        ] {
+            let target = simulation(deficiency, 1.);
+            assert_eq!(target.simulate(Pixel::rgb(0, 0, 0)), Pixel::rgb(0, 0, 0));
+            assert_eq!(
+                target.simulate(Pixel::rgb(255, 255, 255)),
+                Pixel::rgb(255, 255, 255)
+            );
+        }
+    }
+
+    #[test]
+    fn it_should_not_change_colors_at_zero_severity() {
+        let target = simulation(ColorVisionDeficiency::Protanopia, 0.);
+        assert_eq!(
+            target.simulate(Pixel::rgb(200, 30, 90)),
+            Pixel::rgb(200, 30, 90)
+        );
+    }
+
+    #[test]
+    fn it_should_simulate_protanopia() {
+        let target = simulation(ColorVisionDeficiency::Protanopia, 1.);
+        let result = target.simulate(Pixel::rgb(255, 0, 0));
+
+        // Pure red loses most of its brightness and becomes a dark olive.
+        assert!(result.red() < 120);
+        assert!(result.green() < 120);
+        assert_eq!(result.blue(), 0);
+    }
+
+    #[test]
+    fn it_should_simulate_achromatopsia() {
+        let target = simulation(ColorVisionDeficiency::Achromatopsia, 1.);
+        let result = target.simulate(Pixel::rgb(0, 255, 0));
+
+        assert_eq!(result.red(), result.green());
+        assert_eq!(result.green(), result.blue());
+        assert_eq!(result, Pixel::rgb(220, 220, 220));
+    }
+
+    #[test]
+    fn it_should_preserve_alpha() {
+        let target = simulation(ColorVisionDeficiency::Deuteranopia, 1.);
+
+        assert_eq!(target.simulate(Pixel::transparent()), Pixel::transparent());
+        assert_eq!(target.simulate(Pixel::rgba(10, 20, 30, 128)).alpha(), 128);
+    }
+
+    #[test]
+    fn it_should_blend_partial_severities_in_linear_light() {
+        let target = simulation(ColorVisionDeficiency::Achromatopsia, 0.5);
+        let result = target.simulate(Pixel::rgb(0, 255, 0));
+
+        // Red is halfway between none and the green's luminance in linear light. Blending the
+        // encoded values would give 109.
+        assert_eq!(
+            result.red(),
+            (linear_to_srgb(0.7152 / 2.) * 255.).round() as u8
+        );
+        assert_eq!(result.red(), 161);
+    }
+}
@@ -1,4 +1,7 @@
-use crate::pixel_generators::PixelGenerator;
+use crate::{
+    bucket::BucketLayout, color_wheel_shape::ColorWheelShape, pixel_generators::PixelGenerator,
+    radial_mapping::RadialMapping, ring_options::RingOptions,
+};
 
 pub struct ColorWheelDefinition<TPixelGenerator>
 where
@@ -8,5 +11,67 @@ where
     pub margin_size: u32,
     pub angle_buckets: u32,
     pub distance_buckets: u32,
+    pub angle_bucket_layout: BucketLayout,
+    pub distance_bucket_layout: BucketLayout,
+    pub shape: ColorWheelShape,
+    pub radial_mapping: RadialMapping,
     pub pixel_generators: Vec<TPixelGenerator>,
+
+    // Options for the ring drawn by each pixel generator. Pixel generators without options
+    // use the defaults.
+    pub rings: Vec<RingOptions>,
+}
+
+impl<TPixelGenerator> ColorWheelDefinition<TPixelGenerator>
+where
+    TPixelGenerator: PixelGenerator,
+{
+    pub fn ring(&self, index: usize) -> RingOptions {
+        self.rings.get(index).copied().unwrap_or_default()
+    }
+
+    pub fn ring_angle_buckets(&self, index: usize) -> u32 {
+        self.ring(index).angle_buckets.unwrap_or(self.angle_buckets)
+    }
+
+    pub fn ring_distance_buckets(&self, index: usize) -> u32 {
+        self.ring(index)
+            .distance_buckets
+            .unwrap_or(self.distance_buckets)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::pixel_generators::MockPixelGenerator;
+
+    use super::*;
+
+    #[test]
+    fn rings_should_default_to_definition_buckets() {
+        let definition = ColorWheelDefinition::<MockPixelGenerator> {
+            image_size: 100,
+            margin_size: 0,
+            angle_buckets: 6,
+            distance_buckets: 4,
+            angle_bucket_layout: Default::default(),
+            distance_bucket_layout: Default::default(),
+            shape: Default::default(),
+            radial_mapping: Default::default(),
+            pixel_generators: vec![MockPixelGenerator::new(), MockPixelGenerator::new()],
+            rings: vec![RingOptions {
+                weight: 2.,
+                angle_buckets: Some(12),
+                distance_buckets: None,
+            }],
+        };
+
+        assert_eq!(definition.ring(0).weight, 2.);
+        assert_eq!(definition.ring_angle_buckets(0), 12);
+        assert_eq!(definition.ring_distance_buckets(0), 4);
+
+        assert_eq!(definition.ring(1), RingOptions::default());
+        assert_eq!(definition.ring_angle_buckets(1), 6);
+        assert_eq!(definition.ring_distance_buckets(1), 4);
+    }
 }
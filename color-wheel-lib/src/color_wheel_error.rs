@@ -0,0 +1,88 @@
+use std::{error::Error, fmt};
+
+#[derive(Clone, PartialEq, Debug)]
+pub enum ColorWheelError {
+    // There were no color wheel definitions to render.
+    NoColorWheels,
+
+    // The color wheels together are too wide to fit in a canvas.
+    ImageTooLarge,
+
+    // The margins and ring gaps leave less than a pixel for at least one ring.
+    ImageTooSmall,
+
+    // The canvas has fewer rows than the color wheel image.
+    NotEnoughRows {
+        expected: u32,
+        actual: usize,
+    },
+
+    // A row pixel writer was asked to write to a different row.
+    WrongRow {
+        expected: u32,
+        actual: u32,
+    },
+
+    // A pixel generator's fixed value is outside the range its color model allows.
+    FixedValueOutOfRange {
+        name: &'static str,
+        value: f64,
+        minimum: f64,
+        maximum: f64,
+    },
+
+    // A color wheel spec's option is outside the range which can be rendered.
+    OptionOutOfRange {
+        name: &'static str,
+        value: f64,
+        minimum: f64,
+        maximum: f64,
+    },
+
+    // The color wheel's arc or ring weights leave nothing to draw.
+    EmptyShape,
+
+    // Bucket boundaries or sample positions are out of range or order, samples fall outside
+    // their own bucket, or they're given in angles for perceptually spaced buckets.
+    InvalidBucketLayout,
+}
+
+impl fmt::Display for ColorWheelError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            ColorWheelError::NoColorWheels => write!(f, "No color wheels to render."),
+            ColorWheelError::ImageTooLarge => write!(f, "Image is too large."),
+            ColorWheelError::ImageTooSmall => write!(f, "Image is too small."),
+            ColorWheelError::NotEnoughRows { expected, actual } => write!(
+                f,
+                "Expected color wheel image height was {expected} but only {actual} canvas rows were returned."
+            ),
+            ColorWheelError::WrongRow { expected, actual } => write!(
+                f,
+                "RowPixelWriter asked to write to row {actual} when row {expected} was expected."
+            ),
+            ColorWheelError::FixedValueOutOfRange {
+                name,
+                value,
+                minimum,
+                maximum,
+            } => write!(
+                f,
+                "Fixed {name} of {value} is out of range, it must be between {minimum} and {maximum}."
+            ),
+            ColorWheelError::OptionOutOfRange {
+                name,
+                value,
+                minimum,
+                maximum,
+            } => write!(
+                f,
+                "{name} of {value} is out of range, it must be between {minimum} and {maximum}."
+            ),
+            ColorWheelError::EmptyShape => write!(f, "Color wheel shape is empty."),
+            ColorWheelError::InvalidBucketLayout => write!(f, "Bucket layout is invalid."),
+        }
+    }
+}
+
+impl Error for ColorWheelError {}
@@ -0,0 +1,272 @@
+use wide::f64x4;
+
+use crate::get_angle_degrees::{get_angle_degrees, get_angle_degrees_lanes};
+
+// Proportion of the wheel's radius used by the hue ring around the triangle.
+const TRIANGLE_RING_WIDTH: f64 = 0.2;
+
+// Proportion of the strip's width used by its height.
+const STRIP_ASPECT_RATIO: f64 = 0.25;
+
+// How pixels are laid out. Every projection maps a pixel to the angle and distance from
+// center it would have had on a polar color wheel, so that all pixel generators, shapes
+// and buckets work with every projection. This means every projection shows the same slice
+// of colors as the wheel, rather than the region of a single hue which color pickers show
+// beside their hue bar or inside their hue ring.
+#[derive(Debug, PartialEq, Eq, Clone, Copy, Default)]
+pub enum ColorWheelProjection {
+    #[default]
+    Polar,
+
+    // The wheel unrolled into a square. Angle increases from left to right and distance from
+    // center increases from top to bottom.
+    UnrolledSquare,
+
+    // As `UnrolledSquare`, but as a short horizontal strip.
+    UnrolledStrip,
+
+    // A ring of the outermost colors around the wheel unrolled into a triangle. The triangle's
+    // top corner is the center of the wheel, its bottom edge is the outside of the wheel, and
+    // angle increases from left to right across each row.
+    UnrolledTriangleInRing,
+}
+
+#[derive(Debug, PartialEq, Clone, Copy)]
+pub struct ProjectedPixel {
+    pub angle_degrees: f64,
+    pub distance_from_center: f64,
+}
+
+impl ColorWheelProjection {
+    // Projects a pixel relative to the center of the wheel. Returns `None` for pixels outside
+    // of the projection.
+    pub fn project(&self, relative_x: f64, relative_y: f64, radius: f64) -> Option<ProjectedPixel> {
+        match self {
+            ColorWheelProjection::Polar => project_polar(relative_x, relative_y, radius),
+            ColorWheelProjection::UnrolledSquare => {
+                project_rectangle(relative_x, relative_y, radius, radius)
+            }
+            ColorWheelProjection::UnrolledStrip => {
+                project_rectangle(relative_x, relative_y, radius, radius * STRIP_ASPECT_RATIO)
+            }
+            ColorWheelProjection::UnrolledTriangleInRing => {
+                project_triangle_in_ring(relative_x, relative_y, radius)
+            }
+        }
+    }
+
+    // Projects a row of pixels, as `project` does for each. Polar angles and distances from the
+    // center are worked out four pixels at a time, which gives exactly the same distances and
+    // angles within 1e-12 degrees.
+    pub fn project_row(
+        &self,
+        relative_xs: &[f64],
+        relative_y: f64,
+        radius: f64,
+    ) -> Vec<Option<ProjectedPixel>> {
+        if *self != ColorWheelProjection::Polar {
+            return relative_xs
+                .iter()
+                .map(|relative_x| self.project(*relative_x, relative_y, radius))
+                .collect();
+        }
+
+        let relative_y_lane = f64x4::splat(relative_y);
+        let mut projected_pixels = Vec::with_capacity(relative_xs.len());
+        for relative_xs in relative_xs.chunks(4) {
+            let mut lane = [0.; 4];
+            lane[..relative_xs.len()].copy_from_slice(relative_xs);
+            let relative_x_lane = f64x4::from(lane);
+            let distances = (relative_x_lane * relative_x_lane + relative_y_lane * relative_y_lane)
+                .sqrt()
+                .to_array();
+            let angles =
+                get_angle_degrees_lanes(0., 0., relative_x_lane, relative_y_lane).to_array();
+
+            projected_pixels.extend(
+                distances
+                    .into_iter()
+                    .zip(angles)
+                    .take(relative_xs.len())
+                    .map(|(distance_from_center, angle_degrees)| {
+                        (distance_from_center <= radius).then_some(ProjectedPixel {
+                            angle_degrees,
+                            distance_from_center,
+                        })
+                    }),
+            );
+        }
+
+        projected_pixels
+    }
+}
+
+fn project_polar(relative_x: f64, relative_y: f64, radius: f64) -> Option<ProjectedPixel> {
+    let distance_from_center = (relative_x.powi(2) + relative_y.powi(2)).sqrt();
+    if distance_from_center > radius {
+        return None;
+    }
+
+    Some(ProjectedPixel {
+        angle_degrees: get_angle_degrees(0., 0., relative_x, relative_y),
+        distance_from_center,
+    })
+}
+
+fn project_rectangle(
+    relative_x: f64,
+    relative_y: f64,
+    half_width: f64,
+    half_height: f64,
+) -> Option<ProjectedPixel> {
+    if relative_x.abs() > half_width || relative_y.abs() > half_height {
+        return None;
+    }
+
+    Some(ProjectedPixel {
+        angle_degrees: horizontal_angle_degrees(relative_x, half_width),
+        distance_from_center: (relative_y + half_height) / (2. * half_height) * half_width,
+    })
+}
+
+fn project_triangle_in_ring(
+    relative_x: f64,
+    relative_y: f64,
+    radius: f64,
+) -> Option<ProjectedPixel> {
+    let polar = project_polar(relative_x, relative_y, radius)?;
+
+    let triangle_radius = radius * (1. - TRIANGLE_RING_WIDTH);
+    if polar.distance_from_center >= triangle_radius {
+        return Some(ProjectedPixel {
+            angle_degrees: polar.angle_degrees,
+            distance_from_center: radius,
+        });
+    }
+
+    // An equilateral triangle inscribed in the ring, with its top corner at the top.
+    let top = -triangle_radius;
+    let bottom = triangle_radius / 2.;
+    if relative_y < top || relative_y > bottom {
+        return None;
+    }
+
+    let proportion_down = (relative_y - top) / (bottom - top);
+    let half_width = proportion_down * triangle_radius * 3_f64.sqrt() / 2.;
+    if relative_x.abs() > half_width {
+        return None;
+    }
+
+    Some(ProjectedPixel {
+        angle_degrees: horizontal_angle_degrees(relative_x, half_width),
+        distance_from_center: proportion_down * radius,
+    })
+}
+
+fn horizontal_angle_degrees(relative_x: f64, half_width: f64) -> f64 {
+    if half_width <= 0. {
+        return 0.;
+    }
+
+    ((relative_x + half_width) / (2. * half_width) * 360.) % 360.
+}
+
+#[cfg(test)]
+mod tests {
+    use float_cmp::assert_approx_eq;
+
+    use super::*;
+
+    fn assert_projected(result: Option<ProjectedPixel>, angle_degrees: f64, distance: f64) {
+        let result = result.unwrap();
+        assert_approx_eq!(f64, result.angle_degrees, angle_degrees, epsilon = 0.000001);
+        assert_approx_eq!(
+            f64,
+            result.distance_from_center,
+            distance,
+            epsilon = 0.000001
+        );
+    }
+
+    #[test]
+    fn polar_should_project_circle() {
+        let projection = ColorWheelProjection::Polar;
+        assert_projected(projection.project(3., -3., 10.), 45., 18_f64.sqrt());
+        assert_projected(projection.project(0., 10., 10.), 180., 10.);
+        assert!(projection.project(8., 8., 10.).is_none());
+    }
+
+    #[test]
+    fn unrolled_square_should_project_angle_horizontally_and_distance_vertically() {
+        let projection = ColorWheelProjection::UnrolledSquare;
+        assert_projected(projection.project(-10., -10., 10.), 0., 0.);
+        assert_projected(projection.project(0., 0., 10.), 180., 5.);
+        assert_projected(projection.project(5., 10., 10.), 270., 10.);
+        assert!(projection.project(10.5, 0., 10.).is_none());
+    }
+
+    #[test]
+    fn unrolled_strip_should_be_shorter_than_unrolled_square() {
+        let projection = ColorWheelProjection::UnrolledStrip;
+        assert_projected(projection.project(0., -2.5, 10.), 180., 0.);
+        assert_projected(projection.project(-5., 2.5, 10.), 90., 10.);
+        assert!(projection.project(0., 3., 10.).is_none());
+    }
+
+    #[test]
+    fn unrolled_triangle_in_ring_should_project_ring_at_full_distance() {
+        let projection = ColorWheelProjection::UnrolledTriangleInRing;
+        assert_projected(projection.project(9., 0., 10.), 90., 10.);
+        assert!(projection.project(11., 0., 10.).is_none());
+    }
+
+    #[test]
+    fn unrolled_triangle_in_ring_should_project_triangle() {
+        let projection = ColorWheelProjection::UnrolledTriangleInRing;
+
+        // Near the top corner.
+        assert_projected(projection.project(0., -7.4, 10.), 180., 0.5);
+
+        // Middle of bottom edge.
+        assert_projected(projection.project(0., 4., 10.), 180., 10.);
+
+        // Outside of the triangle but inside the ring.
+        assert!(projection.project(5., -4., 10.).is_none());
+        assert!(projection.project(0., 6., 10.).is_none());
+    }
+
+    #[test]
+    fn rows_should_project_like_each_pixel() {
+        let relative_xs = (0..101).map(|x| x as f64 - 50.5).collect::<Vec<_>>();
+
+        for projection in [
+            ColorWheelProjection::Polar,
+            ColorWheelProjection::UnrolledSquare,
+            ColorWheelProjection::UnrolledStrip,
+            ColorWheelProjection::UnrolledTriangleInRing,
+        ] {
+            for relative_y in [-50.5, -20., 0., 0.5, 33.25, 49.5] {
+                let row = projection.project_row(&relative_xs, relative_y, 50.);
+
+                assert_eq!(row.len(), relative_xs.len());
+                for (relative_x, projected) in relative_xs.iter().zip(row) {
+                    let expected = projection.project(*relative_x, relative_y, 50.);
+                    assert_eq!(projected.is_some(), expected.is_some());
+
+                    if let (Some(projected), Some(expected)) = (projected, expected) {
+                        assert_eq!(
+                            projected.distance_from_center,
+                            expected.distance_from_center
+                        );
+                        assert_approx_eq!(
+                            f64,
+                            projected.angle_degrees,
+                            expected.angle_degrees,
+                            epsilon = 1e-12
+                        );
+                    }
+                }
+            }
+        }
+    }
+}
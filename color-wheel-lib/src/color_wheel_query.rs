@@ -0,0 +1,342 @@
+use crate::{
+    bucket::{bucket_index, BucketSpacing},
+    color_wheel_definition::ColorWheelDefinition,
+    color_wheel_error::ColorWheelError,
+    color_wheel_projection::ColorWheelProjection,
+    get_pixel::DefaultGetPixel,
+    get_pixel_generator_and_variable_dimension::DefaultGetPixelGeneratorAndVariableDimension,
+    perceptual_distance::perceptual_distance,
+    pixel::Pixel,
+    pixel_generators::PixelGenerator,
+    render_pixel::{DefaultRenderPixel, RenderPixelData, RenderedPixel},
+};
+
+// The reverse lookup first samples the wheel at these steps, then refines the closest sample.
+const SEARCH_ANGLE_STEPS: u32 = 360;
+const SEARCH_RADIUS_STEPS: u32 = 128;
+const REFINEMENT_ITERATIONS: u32 = 24;
+
+// Where a color falls on the color wheel.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct ColorWheelPoint {
+    pub pixel: Pixel,
+    pub pixel_generator_index: usize,
+
+    // The buckets the color is drawn from, or `None` when that axis isn't bucketed.
+    pub angle_bucket: Option<u32>,
+    pub distance_bucket: Option<u32>,
+
+    // Clockwise from the top, before rotation.
+    pub angle_degrees: f64,
+
+    // From 0 at the center to 1 at the edge of the wheel.
+    pub radius: f64,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct ColorWheelMatch {
+    pub point: ColorWheelPoint,
+
+    // The perceptual distance (ΔE OK) between the requested color and the point's color.
+    pub distance: f64,
+}
+
+// Looks up colors on a color wheel the same way as rendering them, without rendering an image.
+pub struct ColorWheelQuery<'a, TPixelGenerator: PixelGenerator> {
+    definition: &'a ColorWheelDefinition<TPixelGenerator>,
+    data: RenderPixelData,
+    render_pixel: DefaultRenderPixel<DefaultGetPixelGeneratorAndVariableDimension, DefaultGetPixel>,
+}
+
+impl<'a, TPixelGenerator: PixelGenerator> ColorWheelQuery<'a, TPixelGenerator> {
+    pub fn new(
+        definition: &'a ColorWheelDefinition<TPixelGenerator>,
+    ) -> Result<Self, ColorWheelError> {
+        if definition.pixel_generators.is_empty() {
+            return Err(ColorWheelError::NoColorWheels);
+        }
+
+        Ok(ColorWheelQuery {
+            definition,
+            data: RenderPixelData::new(definition)?,
+            render_pixel: DefaultRenderPixel {
+                get_pixel_generator_and_variable_dimension:
+                    DefaultGetPixelGeneratorAndVariableDimension {},
+                get_pixel: DefaultGetPixel {},
+            },
+        })
+    }
+
+    // The color at a position on the definition's image, in pixels from the top left.
+    // Returns `None` outside of the wheel.
+    pub fn color_at_canvas_position(&self, x: f64, y: f64) -> Option<ColorWheelPoint> {
+        let rendered_pixel = self.render_pixel.render_relative_position(
+            x - self.data.center_x as f64,
+            y - self.data.center_y as f64,
+            &self.data,
+            self.definition,
+        )?;
+
+        Some(self.point(rendered_pixel))
+    }
+
+    // The color at an angle in degrees clockwise from the top, and a radius from 0 at the
+    // center to 1 at the edge of the wheel. Returns `None` outside of the wheel, such as
+    // in the gaps between rings.
+    pub fn color_at_polar_position(
+        &self,
+        angle_degrees: f64,
+        radius: f64,
+    ) -> Option<ColorWheelPoint> {
+        if !(0. ..=1.).contains(&radius) || !angle_degrees.is_finite() {
+            return None;
+        }
+
+        let rendered_pixel = self.render_pixel.render_polar_position(
+            angle_degrees.rem_euclid(360.),
+            radius * self.data.all_generators_size,
+            &self.data,
+            self.definition,
+        )?;
+
+        Some(self.point(rendered_pixel))
+    }
+
+    // Finds the point on the wheel whose color is perceptually closest to the given color.
+    pub fn closest_to(&self, color: Pixel) -> Option<ColorWheelMatch> {
+        let distance_to = |point: ColorWheelPoint| ColorWheelMatch {
+            point,
+            distance: perceptual_distance(color, point.pixel),
+        };
+        let closer = |best: Option<ColorWheelMatch>, candidate: ColorWheelMatch| match best {
+            Some(best) if best.distance <= candidate.distance => Some(best),
+            _ => Some(candidate),
+        };
+
+        let mut best = (0..SEARCH_ANGLE_STEPS)
+            .flat_map(|angle_step| {
+                (0..=SEARCH_RADIUS_STEPS).map(move |radius_step| {
+                    (
+                        angle_step as f64 * 360. / SEARCH_ANGLE_STEPS as f64,
+                        radius_step as f64 / SEARCH_RADIUS_STEPS as f64,
+                    )
+                })
+            })
+            .filter_map(|(angle_degrees, radius)| {
+                self.color_at_polar_position(angle_degrees, radius)
+            })
+            .map(distance_to)
+            .fold(None, closer)?;
+
+        // Moves towards closer neighbors, halving the step whenever none are closer.
+        let mut angle_step = 360. / SEARCH_ANGLE_STEPS as f64;
+        let mut radius_step = 1. / SEARCH_RADIUS_STEPS as f64;
+        for _ in 0..REFINEMENT_ITERATIONS {
+            let neighbors = [-1., 0., 1.].into_iter().flat_map(|angle_direction| {
+                [-1., 0., 1.].map(|radius_direction| {
+                    (
+                        best.point.angle_degrees + angle_direction * angle_step,
+                        (best.point.radius + radius_direction * radius_step).clamp(0., 1.),
+                    )
+                })
+            });
+
+            let closest_neighbor = neighbors
+                .filter_map(|(angle_degrees, radius)| {
+                    self.color_at_polar_position(angle_degrees, radius)
+                })
+                .map(distance_to)
+                .fold(None, closer);
+
+            match closest_neighbor {
+                Some(neighbor) if neighbor.distance < best.distance => best = neighbor,
+                _ => {
+                    angle_step /= 2.;
+                    radius_step /= 2.;
+                }
+            }
+        }
+
+        Some(best)
+    }
+
+    // Converts a polar position to a position on the definition's image. Only polar
+    // projections map positions this way, so other projections return `None`.
+    pub fn canvas_position(&self, angle_degrees: f64, radius: f64) -> Option<(f64, f64)> {
+        if self.definition.shape.projection != ColorWheelProjection::Polar {
+            return None;
+        }
+
+        let distance_from_center = radius * self.data.all_generators_size;
+        let angle_radians = angle_degrees.to_radians();
+        Some((
+            self.data.center_x as f64 + distance_from_center * angle_radians.sin(),
+            self.data.center_y as f64 - distance_from_center * angle_radians.cos(),
+        ))
+    }
+
+    fn point(&self, rendered_pixel: RenderedPixel) -> ColorWheelPoint {
+        let index = rendered_pixel.pixel_generator_index;
+        let configuration = self.definition.pixel_generators[index].configuration();
+
+        // Buckets are found the same way as when getting the pixel's color.
+        let angle_degrees = self.definition.shape.rotate(rendered_pixel.angle_degrees);
+        let angle_perceptual_scale = self
+            .data
+            .angle_perceptual_scales
+            .get(index)
+            .and_then(Option::as_ref);
+        let (angle_value, angle_maximum) = match angle_perceptual_scale {
+            Some(scale) if configuration.angle_bucket_spacing == BucketSpacing::Perceptual => {
+                (scale.proportion_at(angle_degrees / 360.), 1.)
+            }
+            _ => (angle_degrees, 360.),
+        };
+
+        ColorWheelPoint {
+            pixel: rendered_pixel.pixel,
+            pixel_generator_index: index,
+            angle_bucket: bucket_index(
+                angle_value,
+                angle_maximum,
+                self.definition.ring_angle_buckets(index),
+                configuration.angle_bucket_direction,
+                &self.definition.angle_bucket_layout,
+            ),
+            distance_bucket: bucket_index(
+                rendered_pixel.variable_dimension,
+                1.,
+                self.definition.ring_distance_buckets(index),
+                configuration.varying_dimension_bucket_direction,
+                &self.definition.distance_bucket_layout,
+            ),
+            angle_degrees: rendered_pixel.angle_degrees,
+            radius: rendered_pixel.distance_from_center / self.data.all_generators_size,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use float_cmp::assert_approx_eq;
+
+    use crate::{
+        bucket::BucketDirection,
+        color_wheel_shape::ColorWheelShape,
+        pixel_generators::{
+            hsl_to_pixel, HslFixedLightnessPixelGenerator, PixelGeneratorConfiguration,
+        },
+    };
+
+    use super::*;
+
+    fn definition(
+        lightnesses: &[f64],
+        angle_buckets: u32,
+        distance_buckets: u32,
+    ) -> ColorWheelDefinition<HslFixedLightnessPixelGenerator> {
+        let configuration = PixelGeneratorConfiguration {
+            is_angle_inverted: false,
+            is_varying_dimension_inverted: false,
+            angle_bucket_direction: BucketDirection::Down,
+            angle_bucket_spacing: BucketSpacing::Uniform,
+            varying_dimension_bucket_direction: BucketDirection::Up,
+        };
+
+        ColorWheelDefinition {
+            image_size: 110,
+            margin_size: 5,
+            angle_buckets,
+            distance_buckets,
+            angle_bucket_layout: Default::default(),
+            distance_bucket_layout: Default::default(),
+            shape: Default::default(),
+            radial_mapping: Default::default(),
+            pixel_generators: lightnesses
+                .iter()
+                .map(|lightness| HslFixedLightnessPixelGenerator {
+                    lightness: *lightness,
+                    configuration,
+                })
+                .collect(),
+            rings: vec![],
+        }
+    }
+
+    #[test]
+    fn it_should_get_the_color_at_a_polar_position() {
+        let definition = definition(&[0.5], 0, 0);
+        let query = ColorWheelQuery::new(&definition).unwrap();
+
+        let point = query.color_at_polar_position(120., 1.).unwrap();
+
+        assert_eq!(point.pixel, hsl_to_pixel(120., 1., 0.5));
+        assert_eq!(point.pixel_generator_index, 0);
+        assert_eq!(point.angle_bucket, None);
+        assert_eq!(point.distance_bucket, None);
+        assert!(query.color_at_polar_position(120., 1.1).is_none());
+    }
+
+    #[test]
+    fn it_should_get_the_color_and_buckets_at_a_canvas_position() {
+        let definition = definition(&[0.25, 0.75], 6, 4);
+        let query = ColorWheelQuery::new(&definition).unwrap();
+
+        // Right of the center, three quarters of the way out, in the outer ring.
+        let point = query.color_at_canvas_position(55. + 37.5, 55.).unwrap();
+
+        assert_eq!(point.pixel_generator_index, 1);
+        assert_approx_eq!(f64, point.angle_degrees, 90.);
+        assert_approx_eq!(f64, point.radius, 0.75);
+        assert_eq!(point.angle_bucket, Some(1));
+        assert_eq!(point.distance_bucket, Some(1));
+        assert_eq!(point.pixel, hsl_to_pixel(60., 0.5, 0.75));
+        assert!(query.color_at_canvas_position(0., 0.).is_none());
+    }
+
+    #[test]
+    fn it_should_skip_colors_outside_of_the_visible_arc() {
+        let mut definition = definition(&[0.5], 0, 0);
+        definition.shape = ColorWheelShape {
+            start_angle_degrees: 0.,
+            end_angle_degrees: 180.,
+            ..Default::default()
+        };
+        let query = ColorWheelQuery::new(&definition).unwrap();
+
+        assert!(query.color_at_polar_position(90., 0.5).is_some());
+        assert!(query.color_at_polar_position(270., 0.5).is_none());
+    }
+
+    #[test]
+    fn it_should_find_the_closest_position_to_a_color() {
+        let definition = definition(&[0.5], 0, 0);
+        let query = ColorWheelQuery::new(&definition).unwrap();
+        let color = hsl_to_pixel(200., 0.6, 0.5);
+
+        let closest = query.closest_to(color).unwrap();
+
+        assert!(closest.distance < 0.005);
+        assert_approx_eq!(f64, closest.point.angle_degrees, 200., epsilon = 1.);
+        assert_approx_eq!(f64, closest.point.radius, 0.6, epsilon = 0.02);
+    }
+
+    #[test]
+    fn it_should_convert_polar_positions_to_the_canvas() {
+        let definition = definition(&[0.5], 0, 0);
+        let query = ColorWheelQuery::new(&definition).unwrap();
+
+        let (x, y) = query.canvas_position(90., 1.).unwrap();
+        assert_approx_eq!(f64, x, 105.);
+        assert_approx_eq!(f64, y, 55.);
+    }
+
+    #[test]
+    fn when_there_are_no_pixel_generators_it_should_return_error() {
+        let definition = definition(&[], 0, 0);
+        assert!(matches!(
+            ColorWheelQuery::new(&definition),
+            Err(ColorWheelError::NoColorWheels)
+        ));
+    }
+}
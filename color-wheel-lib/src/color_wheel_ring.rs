@@ -0,0 +1,23 @@
+use crate::{bucket::BucketDirection, ring_options::RingOptions};
+
+// One ring of a color wheel made of rings, each drawn at its own fixed value.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct ColorWheelRing {
+    pub fixed: f64,
+    pub options: RingOptions,
+
+    // Overrides the color wheel's bucket directions for this ring.
+    pub angle_bucket_direction: Option<BucketDirection>,
+    pub varying_dimension_bucket_direction: Option<BucketDirection>,
+}
+
+impl ColorWheelRing {
+    pub fn new(fixed: f64) -> Self {
+        Self {
+            fixed,
+            options: Default::default(),
+            angle_bucket_direction: None,
+            varying_dimension_bucket_direction: None,
+        }
+    }
+}
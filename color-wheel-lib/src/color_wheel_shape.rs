@@ -0,0 +1,113 @@
+use crate::color_wheel_projection::ColorWheelProjection;
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct ColorWheelShape {
+    pub projection: ColorWheelProjection,
+
+    // Radius of the hole in the middle of the wheel, as a fraction of the wheel radius.
+    pub inner_radius: f64,
+
+    // The visible arc, in degrees clockwise from the top. The arc may wrap past 360 degrees,
+    // for example from 270 to 90 gives the top half of the wheel. Equal angles give an empty
+    // arc, which only contains the start angle itself.
+    pub start_angle_degrees: f64,
+    pub end_angle_degrees: f64,
+
+    // Gap in pixels between each pixel generator's ring.
+    pub ring_gap: u32,
+
+    // Rotates the colors clockwise around the wheel without moving the visible arc.
+    pub rotation_degrees: f64,
+}
+
+impl Default for ColorWheelShape {
+    fn default() -> Self {
+        Self {
+            projection: ColorWheelProjection::Polar,
+            inner_radius: 0.,
+            start_angle_degrees: 0.,
+            end_angle_degrees: 360.,
+            ring_gap: 0,
+            rotation_degrees: 0.,
+        }
+    }
+}
+
+impl ColorWheelShape {
+    pub fn contains_angle(&self, angle_degrees: f64) -> bool {
+        let arc_degrees = self.end_angle_degrees - self.start_angle_degrees;
+        if arc_degrees >= 360. {
+            return true;
+        }
+
+        let arc_degrees = arc_degrees.rem_euclid(360.);
+        (angle_degrees - self.start_angle_degrees).rem_euclid(360.) <= arc_degrees
+    }
+
+    // Converts an angle on the wheel to the angle of the color drawn there.
+    pub fn rotate(&self, angle_degrees: f64) -> f64 {
+        // Unrotated angles are left alone so the right edge of rectangular projections
+        // keeps its 360 degree color.
+        if self.rotation_degrees == 0. {
+            return angle_degrees;
+        }
+
+        (angle_degrees - self.rotation_degrees).rem_euclid(360.)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn arc(start_angle_degrees: f64, end_angle_degrees: f64) -> ColorWheelShape {
+        ColorWheelShape {
+            start_angle_degrees,
+            end_angle_degrees,
+            ..Default::default()
+        }
+    }
+
+    #[test]
+    fn full_wheel_should_contain_all_angles() {
+        let shape = ColorWheelShape::default();
+        assert!(shape.contains_angle(0.));
+        assert!(shape.contains_angle(180.));
+        assert!(shape.contains_angle(359.9));
+    }
+
+    #[test]
+    fn it_should_contain_angles_within_arc() {
+        let shape = arc(90., 180.);
+        assert!(shape.contains_angle(90.));
+        assert!(shape.contains_angle(135.));
+        assert!(shape.contains_angle(180.));
+        assert!(!shape.contains_angle(45.));
+        assert!(!shape.contains_angle(270.));
+    }
+
+    #[test]
+    fn it_should_wrap_arcs_past_360_degrees() {
+        let shape = arc(270., 90.);
+        assert!(shape.contains_angle(300.));
+        assert!(shape.contains_angle(0.));
+        assert!(shape.contains_angle(60.));
+        assert!(!shape.contains_angle(180.));
+    }
+
+    #[test]
+    fn it_should_rotate_angles() {
+        let shape = ColorWheelShape {
+            rotation_degrees: 90.,
+            ..Default::default()
+        };
+        assert_eq!(shape.rotate(135.), 45.);
+        assert_eq!(shape.rotate(45.), 315.);
+    }
+
+    #[test]
+    fn it_should_not_change_unrotated_angles() {
+        let shape = ColorWheelShape::default();
+        assert_eq!(shape.rotate(360.), 360.);
+    }
+}
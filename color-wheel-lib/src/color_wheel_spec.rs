@@ -0,0 +1,469 @@
+use crate::{
+    background::Background,
+    bucket::{BucketDirection, BucketLayout, BucketSpacing},
+    color_vision_deficiency::ColorVisionDeficiency,
+    color_wheel_definition::ColorWheelDefinition,
+    color_wheel_error::ColorWheelError,
+    color_wheel_ring::ColorWheelRing,
+    color_wheel_shape::ColorWheelShape,
+    dither::DitherMethod,
+    pixel_generators::{PixelGenerator, PixelGeneratorConfiguration, PixelGeneratorRegistration},
+    radial_mapping::RadialMapping,
+    render_thread_pool::RenderThreadPool,
+    resample::ResampleFilter,
+    ring_options::RingOptions,
+};
+
+pub const MINIMUM_DIAMETER: u32 = 5;
+pub const MAXIMUM_DIAMETER: u32 = 8192;
+pub const MAXIMUM_BUCKETS: u32 = 1000;
+pub const MAXIMUM_SUPERSAMPLING: u32 = 8;
+
+// Describes a complete image of color wheels, from which the command line and every binding
+// render. Sizes are in output pixels, and are scaled up when supersampling.
+#[derive(Clone, Debug)]
+pub struct ColorWheelSpec {
+    pub registration: PixelGeneratorRegistration,
+
+    // Diameter of each color wheel and the margin around it.
+    pub diameter: u32,
+    pub margin: u32,
+
+    // Each fixed value is rendered as its own color wheel, side by side, or as a ring around
+    // the previous value when expanded.
+    pub fixed: Vec<f64>,
+    pub expand: bool,
+
+    // When not empty, a single color wheel of these rings is rendered instead of the fixed values.
+    pub rings: Vec<ColorWheelRing>,
+
+    // Zero gives a smooth output.
+    pub angle_buckets: u32,
+    pub distance_buckets: u32,
+    pub angle_bucket_layout: BucketLayout,
+    pub distance_bucket_layout: BucketLayout,
+    pub angle_bucket_spacing: BucketSpacing,
+    pub angle_bucket_direction: BucketDirection,
+
+    // Defaults to `Up`, which reversing either the colors or the bucketing flips.
+    pub varying_dimension_bucket_direction: Option<BucketDirection>,
+    pub is_varying_dimension_inverted: bool,
+    pub is_distance_bucketing_reversed: bool,
+
+    pub shape: ColorWheelShape,
+    pub radial_mapping: RadialMapping,
+
+    // Each output pixel is rendered as this many pixels squared, then downsampled in linear light.
+    pub supersampling: u32,
+    pub resample_filter: ResampleFilter,
+
+    // Dithers the full precision colors to 8 bits per channel, rather than rounding them.
+    pub dither: Option<DitherMethod>,
+    pub background: Background,
+
+    // Each simulation renders another copy of the color wheels to the right, after an
+    // unsimulated copy when comparing.
+    pub simulations: Vec<ColorVisionDeficiency>,
+    pub simulation_severity: f64,
+    pub compare_simulations: bool,
+
+    // The threads which render and downsample the rows.
+    pub thread_pool: RenderThreadPool,
+}
+
+impl ColorWheelSpec {
+    // Creates a spec with the same defaults as the command line.
+    pub fn new(registration: PixelGeneratorRegistration) -> Self {
+        Self {
+            registration,
+            diameter: 980,
+            margin: 10,
+            fixed: vec![registration.domain.default_fixed],
+            expand: false,
+            rings: vec![],
+            angle_buckets: 0,
+            distance_buckets: 0,
+            angle_bucket_layout: Default::default(),
+            distance_bucket_layout: Default::default(),
+            angle_bucket_spacing: BucketSpacing::Uniform,
+            angle_bucket_direction: BucketDirection::Down,
+            varying_dimension_bucket_direction: None,
+            is_varying_dimension_inverted: false,
+            is_distance_bucketing_reversed: false,
+            shape: Default::default(),
+            radial_mapping: Default::default(),
+            supersampling: 4,
+            resample_filter: Default::default(),
+            dither: None,
+            background: Background::Transparent,
+            simulations: vec![],
+            simulation_severity: 1.,
+            compare_simulations: false,
+            thread_pool: RenderThreadPool::global(),
+        }
+    }
+
+    pub fn validate(&self) -> Result<(), ColorWheelError> {
+        if self.rings.is_empty() && self.fixed.is_empty() {
+            return Err(ColorWheelError::NoColorWheels);
+        }
+
+        let domain = self.registration.domain;
+        for fixed in self
+            .fixed
+            .iter()
+            .chain(self.rings.iter().map(|ring| &ring.fixed))
+        {
+            domain.validate_fixed(*fixed)?;
+        }
+
+        validate_range(
+            "Diameter",
+            self.diameter,
+            MINIMUM_DIAMETER,
+            MAXIMUM_DIAMETER,
+        )?;
+        validate_range("Margin", self.margin, 0, MAXIMUM_DIAMETER)?;
+        validate_range(
+            "Supersampling",
+            self.supersampling,
+            1,
+            MAXIMUM_SUPERSAMPLING,
+        )?;
+
+        let ring_options = self.rings.iter().map(|ring| ring.options);
+        let angle_bucket_counts = std::iter::once(self.angle_buckets)
+            .chain(ring_options.clone().filter_map(|ring| ring.angle_buckets))
+            .collect::<Vec<_>>();
+        let distance_bucket_counts = std::iter::once(self.distance_buckets)
+            .chain(ring_options.filter_map(|ring| ring.distance_buckets))
+            .collect::<Vec<_>>();
+        for buckets in angle_bucket_counts.iter().chain(&distance_bucket_counts) {
+            validate_range("Buckets", *buckets, 0, MAXIMUM_BUCKETS)?;
+        }
+
+        validate_fraction("Inner radius", self.shape.inner_radius)?;
+        validate_fraction("Simulation severity", self.simulation_severity)?;
+
+        // An arc whose ends meet without going all the way round would render nothing.
+        let arc_degrees = self.shape.end_angle_degrees - self.shape.start_angle_degrees;
+        let is_empty_arc = arc_degrees < 360. && arc_degrees.rem_euclid(360.) == 0.;
+        let is_empty_ring = self
+            .rings
+            .iter()
+            .any(|ring| !(ring.options.weight > 0. && ring.options.weight.is_finite()));
+        if !arc_degrees.is_finite() || is_empty_arc || is_empty_ring {
+            return Err(ColorWheelError::EmptyShape);
+        }
+
+        // Perceptual spacing buckets by the proportion of the perceptual distance around the
+        // wheel, which boundaries and samples given as proportions of the angle don't match.
+        let is_perceptual_layout = self.angle_bucket_spacing == BucketSpacing::Perceptual
+            && self.angle_bucket_layout != BucketLayout::default();
+        if is_perceptual_layout
+            || !is_valid_bucket_layout(&self.angle_bucket_layout, &angle_bucket_counts)
+            || !is_valid_bucket_layout(&self.distance_bucket_layout, &distance_bucket_counts)
+        {
+            return Err(ColorWheelError::InvalidBucketLayout);
+        }
+
+        let supersampled_width = self.supersampling as u64
+            * self.wheel_size() as u64
+            * self.wheel_count() as u64
+            * self.set_count() as u64;
+        if supersampled_width > u32::MAX as u64 {
+            return Err(ColorWheelError::ImageTooLarge);
+        }
+
+        Ok(())
+    }
+
+    // Size of each color wheel including its margin.
+    pub fn wheel_size(&self) -> u32 {
+        self.diameter.saturating_add(self.margin.saturating_mul(2))
+    }
+
+    // The number of color wheels in each set.
+    pub fn wheel_count(&self) -> u32 {
+        if !self.rings.is_empty() || self.expand {
+            1
+        } else {
+            self.fixed.len() as u32
+        }
+    }
+
+    // The number of times the full set of color wheels is rendered side by side, once for
+    // each simulation.
+    pub fn set_count(&self) -> u32 {
+        if self.simulations.is_empty() {
+            return 1;
+        }
+
+        self.simulations.len() as u32 + u32::from(self.compare_simulations)
+    }
+
+    pub fn image_width(&self) -> u32 {
+        self.wheel_size()
+            .saturating_mul(self.wheel_count())
+            .saturating_mul(self.set_count())
+    }
+
+    pub fn image_height(&self) -> u32 {
+        self.wheel_size()
+    }
+
+    pub fn configuration(&self) -> PixelGeneratorConfiguration {
+        let is_default_direction_flipped =
+            self.is_varying_dimension_inverted != self.is_distance_bucketing_reversed;
+        let default_direction = if is_default_direction_flipped {
+            BucketDirection::Down
+        } else {
+            BucketDirection::Up
+        };
+
+        PixelGeneratorConfiguration {
+            is_angle_inverted: false,
+            is_varying_dimension_inverted: self.is_varying_dimension_inverted,
+            angle_bucket_direction: self.angle_bucket_direction,
+            angle_bucket_spacing: self.angle_bucket_spacing,
+            varying_dimension_bucket_direction: self
+                .varying_dimension_bucket_direction
+                .unwrap_or(default_direction),
+        }
+    }
+
+    // The supersampled definitions of one set of color wheels.
+    pub fn color_wheel_definitions(&self) -> Vec<ColorWheelDefinition<Box<dyn PixelGenerator>>> {
+        let configuration = self.configuration();
+        let create = |fixed: f64| self.registration.create(fixed, configuration);
+
+        if !self.rings.is_empty() {
+            let pixel_generators = self
+                .rings
+                .iter()
+                .map(|ring| {
+                    let configuration = PixelGeneratorConfiguration {
+                        angle_bucket_direction: ring
+                            .angle_bucket_direction
+                            .unwrap_or(configuration.angle_bucket_direction),
+                        varying_dimension_bucket_direction: ring
+                            .varying_dimension_bucket_direction
+                            .unwrap_or(configuration.varying_dimension_bucket_direction),
+                        ..configuration
+                    };
+                    self.registration.create(ring.fixed, configuration)
+                })
+                .collect();
+            let rings = self.rings.iter().map(|ring| ring.options).collect();
+
+            vec![self.color_wheel_definition(pixel_generators, rings)]
+        } else if self.expand {
+            let pixel_generators = self.fixed.iter().map(|fixed| create(*fixed)).collect();
+            vec![self.color_wheel_definition(pixel_generators, vec![])]
+        } else {
+            self.fixed
+                .iter()
+                .map(|fixed| self.color_wheel_definition(vec![create(*fixed)], vec![]))
+                .collect()
+        }
+    }
+
+    // Scales the checkerboard squares so they are the requested size after downsampling.
+    pub fn supersampled_background(&self) -> Background {
+        match self.background {
+            Background::Checkerboard {
+                size,
+                first,
+                second,
+            } => Background::Checkerboard {
+                size: size * self.supersampling,
+                first,
+                second,
+            },
+            background => background,
+        }
+    }
+
+    fn color_wheel_definition(
+        &self,
+        pixel_generators: Vec<Box<dyn PixelGenerator>>,
+        rings: Vec<RingOptions>,
+    ) -> ColorWheelDefinition<Box<dyn PixelGenerator>> {
+        ColorWheelDefinition {
+            image_size: self.supersampling * self.wheel_size(),
+            margin_size: self.supersampling * self.margin,
+            angle_buckets: self.angle_buckets,
+            distance_buckets: self.distance_buckets,
+            angle_bucket_layout: self.angle_bucket_layout.clone(),
+            distance_bucket_layout: self.distance_bucket_layout.clone(),
+            shape: ColorWheelShape {
+                ring_gap: self.supersampling * self.shape.ring_gap,
+                ..self.shape
+            },
+            radial_mapping: self.radial_mapping,
+            pixel_generators,
+            rings,
+        }
+    }
+}
+
+fn validate_range(
+    name: &'static str,
+    value: u32,
+    minimum: u32,
+    maximum: u32,
+) -> Result<(), ColorWheelError> {
+    if !(minimum..=maximum).contains(&value) {
+        return Err(ColorWheelError::OptionOutOfRange {
+            name,
+            value: value as f64,
+            minimum: minimum as f64,
+            maximum: maximum as f64,
+        });
+    }
+
+    Ok(())
+}
+
+fn validate_fraction(name: &'static str, value: f64) -> Result<(), ColorWheelError> {
+    if !(0. ..=1.).contains(&value) {
+        return Err(ColorWheelError::OptionOutOfRange {
+            name,
+            value,
+            minimum: 0.,
+            maximum: 1.,
+        });
+    }
+
+    Ok(())
+}
+
+// Boundaries must be ascending proportions, and each sample must fall inside its own bucket
+// for every number of buckets the layout is used with.
+fn is_valid_bucket_layout(layout: &BucketLayout, bucket_counts: &[u32]) -> bool {
+    let is_proportion = |position: &f64| (0. ..=1.).contains(position);
+
+    layout.boundaries.iter().all(is_proportion)
+        && layout.sample_positions.iter().all(is_proportion)
+        && layout.boundaries.windows(2).all(|w| w[0] < w[1])
+        && bucket_counts
+            .iter()
+            .all(|buckets| layout.are_samples_within_buckets(*buckets))
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::pixel_generators::HsvFixedValuePixelGenerator;
+
+    use super::*;
+
+    fn spec() -> ColorWheelSpec {
+        ColorWheelSpec::new(HsvFixedValuePixelGenerator::REGISTRATION)
+    }
+
+    #[test]
+    fn it_should_lay_out_color_wheels() {
+        let spec = ColorWheelSpec {
+            diameter: 100,
+            margin: 5,
+            fixed: vec![0.5, 1.],
+            simulations: vec![ColorVisionDeficiency::Protanopia],
+            compare_simulations: true,
+            ..spec()
+        };
+
+        assert_eq!(spec.validate(), Ok(()));
+        assert_eq!(spec.set_count(), 2);
+        assert_eq!((spec.image_width(), spec.image_height()), (440, 110));
+
+        let definitions = spec.color_wheel_definitions();
+        assert_eq!(definitions.len(), 2);
+        assert_eq!(definitions[0].image_size, 440);
+        assert_eq!(definitions[0].margin_size, 20);
+
+        let expanded = ColorWheelSpec {
+            expand: true,
+            ..spec
+        };
+        assert_eq!(expanded.image_width(), 220);
+        assert_eq!(expanded.color_wheel_definitions().len(), 1);
+    }
+
+    #[test]
+    fn it_should_flip_the_default_varying_dimension_bucket_direction_when_reversed() {
+        let direction = |is_varying_dimension_inverted, is_distance_bucketing_reversed| {
+            ColorWheelSpec {
+                is_varying_dimension_inverted,
+                is_distance_bucketing_reversed,
+                ..spec()
+            }
+            .configuration()
+            .varying_dimension_bucket_direction
+        };
+
+        assert_eq!(direction(false, false), BucketDirection::Up);
+        assert_eq!(direction(true, false), BucketDirection::Down);
+        assert_eq!(direction(false, true), BucketDirection::Down);
+        assert_eq!(direction(true, true), BucketDirection::Up);
+    }
+
+    #[test]
+    fn it_should_reject_invalid_specs() {
+        let is_invalid = |spec: ColorWheelSpec| spec.validate().is_err();
+
+        assert!(is_invalid(ColorWheelSpec {
+            fixed: vec![],
+            ..spec()
+        }));
+        assert!(is_invalid(ColorWheelSpec {
+            fixed: vec![1.5],
+            ..spec()
+        }));
+        assert!(is_invalid(ColorWheelSpec {
+            diameter: 4,
+            ..spec()
+        }));
+        assert!(is_invalid(ColorWheelSpec {
+            distance_buckets: 1001,
+            ..spec()
+        }));
+        assert!(is_invalid(ColorWheelSpec {
+            supersampling: 0,
+            ..spec()
+        }));
+        assert!(is_invalid(ColorWheelSpec {
+            shape: ColorWheelShape {
+                start_angle_degrees: 90.,
+                end_angle_degrees: 90.,
+                ..Default::default()
+            },
+            ..spec()
+        }));
+        assert!(is_invalid(ColorWheelSpec {
+            rings: vec![ColorWheelRing {
+                options: RingOptions {
+                    weight: 0.,
+                    ..Default::default()
+                },
+                ..ColorWheelRing::new(0.5)
+            }],
+            ..spec()
+        }));
+        assert!(is_invalid(ColorWheelSpec {
+            angle_bucket_spacing: BucketSpacing::Perceptual,
+            angle_bucket_layout: BucketLayout {
+                boundaries: vec![0.5],
+                sample_positions: vec![],
+            },
+            ..spec()
+        }));
+        assert!(is_invalid(ColorWheelSpec {
+            distance_buckets: 4,
+            distance_bucket_layout: BucketLayout {
+                boundaries: vec![],
+                sample_positions: vec![0.9],
+            },
+            ..spec()
+        }));
+    }
+}
@@ -0,0 +1,399 @@
+use std::sync::OnceLock;
+
+use crate::{canvas::Canvas, pixel::Pixel};
+
+#[derive(Debug, PartialEq, Eq, Hash, Clone, Copy)]
+pub enum DitherMethod {
+    Bayer,
+    BlueNoise,
+    FloydSteinberg,
+}
+
+const BAYER_BITS: u32 = 3;
+const BAYER_SIZE: u32 = 1 << BAYER_BITS;
+
+const BLUE_NOISE_SIZE: usize = 64;
+const BLUE_NOISE_SIGMA: f64 = 1.5;
+
+// Quantizes normalized RGBA color data to a canvas, reducing each color channel to the given
+// number of evenly spaced levels. Passing 256 levels quantizes to the full 8-bit range.
+// Every method is deterministic, so the same input always produces the same output.
+// Only the crate's own renderers call this, always with data for a whole width by height image.
+pub(crate) fn dither(
+    width: u32,
+    height: u32,
+    data: &[f64],
+    levels: u32,
+    method: DitherMethod,
+) -> Canvas {
+    if data.len() != width as usize * height as usize * 4 {
+        panic!("Dither data was not the expected size for a {width}x{height} image.");
+    }
+
+    let steps = (levels.max(2) - 1) as f64;
+
+    let pixels = match method {
+        DitherMethod::Bayer | DitherMethod::BlueNoise => ordered_dither(width, data, steps, method),
+        DitherMethod::FloydSteinberg => floyd_steinberg_dither(width, height, data, steps),
+    };
+
+    let mut canvas = Canvas::new(width, height);
+    for (index, pixel) in pixels.into_iter().enumerate() {
+        canvas.set_pixel(index as u32 % width, index as u32 / width, pixel);
+    }
+
+    canvas
+}
+
+// Returns the ordered dither threshold for the given location, in the range -0.5 to 0.5.
+pub fn ordered_dither_threshold(method: DitherMethod, x: u32, y: u32) -> f64 {
+    match method {
+        DitherMethod::Bayer => {
+            let size = BAYER_SIZE;
+            (bayer_index(x % size, y % size) as f64 + 0.5) / (size * size) as f64 - 0.5
+        }
+        DitherMethod::BlueNoise => {
+            let size = BLUE_NOISE_SIZE;
+            let rank = blue_noise_ranks()[(x as usize % size) + (y as usize % size) * size];
+            (rank as f64 + 0.5) / (size * size) as f64 - 0.5
+        }
+        DitherMethod::FloydSteinberg => 0.,
+    }
+}
+
+fn ordered_dither(width: u32, data: &[f64], steps: f64, method: DitherMethod) -> Vec<Pixel> {
+    data.chunks(4)
+        .enumerate()
+        .map(|(index, color)| {
+            let x = index as u32 % width;
+            let y = index as u32 / width;
+            let threshold = ordered_dither_threshold(method, x, y);
+
+            to_pixel(
+                color
+                    .iter()
+                    .take(3)
+                    .map(|v| quantize(*v + threshold / steps, steps)),
+                color[3],
+            )
+        })
+        .collect()
+}
+
+fn floyd_steinberg_dither(width: u32, height: u32, data: &[f64], steps: f64) -> Vec<Pixel> {
+    let width = width as usize;
+    let height = height as usize;
+
+    let mut values = data.to_vec();
+    let mut pixels = Vec::with_capacity(width * height);
+
+    for y in 0..height {
+        for x in 0..width {
+            let index = (x + y * width) * 4;
+
+            let mut quantized = [0.; 3];
+            for channel in 0..3 {
+                let value = values[index + channel];
+                quantized[channel] = quantize(value, steps);
+
+                if values[index + 3] == 0. {
+                    continue;
+                }
+
+                let error = value - quantized[channel];
+                let mut diffuse = |target_x: usize, target_y: usize, weight: f64| {
+                    if target_x < width && target_y < height {
+                        values[(target_x + target_y * width) * 4 + channel] += error * weight;
+                    }
+                };
+
+                diffuse(x + 1, y, 7. / 16.);
+                if x > 0 {
+                    diffuse(x - 1, y + 1, 3. / 16.);
+                }
+                diffuse(x, y + 1, 5. / 16.);
+                diffuse(x + 1, y + 1, 1. / 16.);
+            }
+
+            pixels.push(to_pixel(quantized.into_iter(), values[index + 3]));
+        }
+    }
+
+    pixels
+}
+
+fn quantize(value: f64, steps: f64) -> f64 {
+    (value * steps).round().clamp(0., steps) / steps
+}
+
+fn to_pixel(mut color: impl Iterator<Item = f64>, alpha: f64) -> Pixel {
+    let alpha = (alpha.clamp(0., 1.) * 255.).round() as u8;
+    if alpha == 0 {
+        return Pixel::transparent();
+    }
+
+    let mut next = || color.next().unwrap();
+    Pixel::from_normalized(next(), next(), next()).with_alpha(alpha)
+}
+
+// Interleaves the bits of `x ^ y` and `y` in reverse order, which gives the recursive
+// Bayer matrix (0 2 / 3 1, and so on).
+fn bayer_index(x: u32, y: u32) -> u32 {
+    let mut result = 0;
+    let xor = x ^ y;
+    for bit in 0..BAYER_BITS {
+        result = (result << 2) | (((xor >> bit) & 1) << 1) | ((y >> bit) & 1);
+    }
+
+    result
+}
+
+fn blue_noise_ranks() -> &'static [usize] {
+    static RANKS: OnceLock<Vec<usize>> = OnceLock::new();
+    RANKS.get_or_init(|| generate_blue_noise_ranks(BLUE_NOISE_SIZE))
+}
+
+// Ulichney's void-and-cluster method, seeded with a fixed random pattern so the
+// resulting texture is always the same.
+// https://cv.ulichney.com/papers/1993-void-cluster.pdf
+fn generate_blue_noise_ranks(size: usize) -> Vec<usize> {
+    let count = size * size;
+
+    let kernel = (0..count)
+        .map(|index| {
+            let wrap = |d: usize| d.min(size - d) as f64;
+            let dx = wrap(index % size);
+            let dy = wrap(index / size);
+            (-(dx * dx + dy * dy) / (2. * BLUE_NOISE_SIGMA * BLUE_NOISE_SIGMA)).exp()
+        })
+        .collect::<Vec<_>>();
+
+    let toggle = |energy: &mut [f64], index: usize, sign: f64| {
+        let (x, y) = (index % size, index / size);
+        for (target, value) in energy.iter_mut().enumerate() {
+            let dx = (target % size + size - x) % size;
+            let dy = (target / size + size - y) % size;
+            *value += sign * kernel[dx + dy * size];
+        }
+    };
+
+    let find = |energy: &[f64], pattern: &[bool], want: bool, tightest: bool| {
+        let mut best: Option<usize> = None;
+        for index in (0..count).filter(|i| pattern[*i] == want) {
+            let is_better = match best {
+                None => true,
+                Some(best) if tightest => energy[index] > energy[best],
+                Some(best) => energy[index] < energy[best],
+            };
+            if is_better {
+                best = Some(index);
+            }
+        }
+        best.unwrap()
+    };
+
+    // Initial random pattern, with a fixed xorshift seed.
+    let initial_count = count / 10;
+    let mut pattern = vec![false; count];
+    let mut energy = vec![0.; count];
+    let mut state: u64 = 0x2545_f491_4f6c_dd1d;
+    let mut placed = 0;
+    while placed < initial_count {
+        state ^= state << 13;
+        state ^= state >> 7;
+        state ^= state << 17;
+        let index = (state % count as u64) as usize;
+        if !pattern[index] {
+            pattern[index] = true;
+            toggle(&mut energy, index, 1.);
+            placed += 1;
+        }
+    }
+
+    // Move points from the tightest clusters to the largest voids until stable.
+    for _ in 0..count {
+        let cluster = find(&energy, &pattern, true, true);
+        pattern[cluster] = false;
+        toggle(&mut energy, cluster, -1.);
+
+        let void = find(&energy, &pattern, false, false);
+        pattern[void] = true;
+        toggle(&mut energy, void, 1.);
+
+        if void == cluster {
+            break;
+        }
+    }
+
+    let mut ranks = vec![0; count];
+
+    // Phase 1: rank the initial points by repeatedly removing the tightest cluster.
+    let mut prototype = pattern.clone();
+    let mut prototype_energy = energy.clone();
+    for rank in (0..initial_count).rev() {
+        let cluster = find(&prototype_energy, &prototype, true, true);
+        prototype[cluster] = false;
+        toggle(&mut prototype_energy, cluster, -1.);
+        ranks[cluster] = rank;
+    }
+
+    // Phase 2: fill the largest voids until half the points are set.
+    for rank in initial_count..count / 2 {
+        let void = find(&energy, &pattern, false, false);
+        pattern[void] = true;
+        toggle(&mut energy, void, 1.);
+        ranks[void] = rank;
+    }
+
+    // Phase 3: the unset points are now the minority, so fill the tightest clusters of them.
+    let mut minority_energy = vec![0.; count];
+    for index in (0..count).filter(|i| !pattern[*i]) {
+        toggle(&mut minority_energy, index, 1.);
+    }
+
+    for rank in count / 2..count {
+        let cluster = find(&minority_energy, &pattern, false, true);
+        pattern[cluster] = true;
+        toggle(&mut minority_energy, cluster, -1.);
+        ranks[cluster] = rank;
+    }
+
+    ranks
+}
+
+#[cfg(test)]
+mod tests {
+    use float_cmp::assert_approx_eq;
+
+    use super::*;
+
+    fn solid(width: u32, height: u32, color: [f64; 4]) -> Vec<f64> {
+        color
+            .iter()
+            .copied()
+            .cycle()
+            .take(width as usize * height as usize * 4)
+            .collect()
+    }
+
+    fn average_red(canvas: &Canvas) -> f64 {
+        canvas.iter_pixels().map(|p| p.red() as f64).sum::<f64>()
+            / (canvas.width() * canvas.height()) as f64
+    }
+
+    #[test]
+    fn bayer_index_should_match_bayer_matrix() {
+        assert_eq!(bayer_index(0, 0), 0);
+        assert_eq!(bayer_index(1, 0), 32);
+        assert_eq!(bayer_index(0, 1), 48);
+        assert_eq!(bayer_index(1, 1), 16);
+
+        let mut indices = (0..BAYER_SIZE)
+            .flat_map(|y| (0..BAYER_SIZE).map(move |x| bayer_index(x, y)))
+            .collect::<Vec<_>>();
+        indices.sort();
+        assert_eq!(indices, (0..BAYER_SIZE * BAYER_SIZE).collect::<Vec<_>>());
+    }
+
+    #[test]
+    fn blue_noise_should_contain_every_rank_once() {
+        let mut ranks = blue_noise_ranks().to_vec();
+        ranks.sort();
+        assert_eq!(
+            ranks,
+            (0..BLUE_NOISE_SIZE * BLUE_NOISE_SIZE).collect::<Vec<_>>()
+        );
+    }
+
+    #[test]
+    fn ordered_thresholds_should_be_centered() {
+        for method in [DitherMethod::Bayer, DitherMethod::BlueNoise] {
+            let size = if method == DitherMethod::Bayer {
+                BAYER_SIZE
+            } else {
+                BLUE_NOISE_SIZE as u32
+            };
+
+            let thresholds = (0..size)
+                .flat_map(|y| (0..size).map(move |x| ordered_dither_threshold(method, x, y)))
+                .collect::<Vec<_>>();
+
+            assert!(thresholds.iter().all(|t| (-0.5..0.5).contains(t)));
+            assert_approx_eq!(f64, thresholds.iter().sum::<f64>(), 0., epsilon = 0.000001);
+        }
+    }
+
+    #[test]
+    fn it_should_preserve_exact_values() {
+        let data = solid(16, 16, [100. / 255., 0., 1., 1.]);
+
+        for method in [
+            DitherMethod::Bayer,
+            DitherMethod::BlueNoise,
+            DitherMethod::FloydSteinberg,
+        ] {
+            let canvas = dither(16, 16, &data, 256, method);
+            assert!(canvas.iter_pixels().all(|p| p == Pixel::rgb(100, 0, 255)));
+        }
+    }
+
+    #[test]
+    fn it_should_preserve_average_between_levels() {
+        let value = 100.25 / 255.;
+        let data = solid(64, 64, [value, 0., 0., 1.]);
+
+        for method in [
+            DitherMethod::Bayer,
+            DitherMethod::BlueNoise,
+            DitherMethod::FloydSteinberg,
+        ] {
+            let canvas = dither(64, 64, &data, 256, method);
+
+            assert!(canvas
+                .iter_pixels()
+                .all(|p| p.red() == 100 || p.red() == 101));
+            assert_approx_eq!(f64, average_red(&canvas), 100.25, epsilon = 0.01);
+        }
+    }
+
+    #[test]
+    fn it_should_reduce_to_levels() {
+        let data = solid(8, 8, [0.5, 0., 0., 1.]);
+
+        let canvas = dither(8, 8, &data, 2, DitherMethod::Bayer);
+
+        assert!(canvas.iter_pixels().all(|p| p.red() == 0 || p.red() == 255));
+        assert_approx_eq!(f64, average_red(&canvas), 127.5);
+    }
+
+    #[test]
+    fn it_should_be_deterministic() {
+        let data = (0..32 * 32 * 4)
+            .map(|i| (i % 97) as f64 / 97.)
+            .collect::<Vec<_>>();
+
+        for method in [
+            DitherMethod::Bayer,
+            DitherMethod::BlueNoise,
+            DitherMethod::FloydSteinberg,
+        ] {
+            let first = dither(32, 32, &data, 256, method);
+            let second = dither(32, 32, &data, 256, method);
+            assert_eq!(first.data(), second.data());
+        }
+    }
+
+    #[test]
+    fn it_should_keep_transparent_pixels_transparent() {
+        let data = solid(4, 4, [0.3, 0.3, 0.3, 0.]);
+
+        for method in [
+            DitherMethod::Bayer,
+            DitherMethod::BlueNoise,
+            DitherMethod::FloydSteinberg,
+        ] {
+            let canvas = dither(4, 4, &data, 256, method);
+            assert!(canvas.iter_pixels().all(|p| p == Pixel::transparent()));
+        }
+    }
+}
@@ -1,12 +1,37 @@
 use std::f64::consts::PI;
 
+use wide::{f64x4, CmpGe};
+
 use libm::atan2;
 
+use crate::atan2::atan2_lanes;
+
 pub fn get_angle_degrees(center_x: f64, center_y: f64, relative_x: f64, relative_y: f64) -> f64 {
     let angle_radians = PI + atan2(-relative_x + center_x, relative_y - center_y);
     (angle_radians * 180. / PI) % 360.
 }
 
+// As `get_angle_degrees` for four pixels at once, to within 1e-12 degrees.
+pub(crate) fn get_angle_degrees_lanes(
+    center_x: f64,
+    center_y: f64,
+    relative_x: f64x4,
+    relative_y: f64x4,
+) -> f64x4 {
+    let angle_radians = f64x4::splat(PI)
+        + atan2_lanes(
+            -relative_x + f64x4::splat(center_x),
+            relative_y - f64x4::splat(center_y),
+        );
+    let angle_degrees = angle_radians * f64x4::splat(180.) / f64x4::splat(PI);
+
+    // The angle is from 0 to 360, so `% 360.` only ever subtracts 360 once, which is exact.
+    let full_turn = f64x4::splat(360.);
+    angle_degrees
+        .cmp_ge(full_turn)
+        .blend(angle_degrees - full_turn, angle_degrees)
+}
+
 #[cfg(test)]
 mod tests {
     use float_cmp::assert_approx_eq;
@@ -26,4 +51,30 @@ mod tests {
         assert_approx_eq!(f64, 225., get_angle_degrees(0., 0., -1., 1.));
         assert_approx_eq!(f64, 315., get_angle_degrees(0., 0., -1., -1.));
     }
+
+    #[test]
+    fn lanes_should_match_each_pixel() {
+        let values = (-40..=40)
+            .map(|value| value as f64 * 0.5)
+            .collect::<Vec<_>>();
+
+        for relative_y in &values {
+            for relative_xs in values.chunks(4) {
+                let mut lane = [0.; 4];
+                lane[..relative_xs.len()].copy_from_slice(relative_xs);
+                let angles =
+                    get_angle_degrees_lanes(0.5, -1., f64x4::from(lane), f64x4::splat(*relative_y))
+                        .to_array();
+
+                for (relative_x, angle) in lane.into_iter().zip(angles) {
+                    assert_approx_eq!(
+                        f64,
+                        angle,
+                        get_angle_degrees(0.5, -1., relative_x, *relative_y),
+                        epsilon = 1e-12
+                    );
+                }
+            }
+        }
+    }
 }
@@ -1,40 +1,102 @@
-use crate::{bucket::bucket, pixel::Pixel, pixel_generators::PixelGenerator};
+use crate::{
+    bucket::{bucket_with_layout, BucketSpacing},
+    color::Color,
+    color_wheel_definition::ColorWheelDefinition,
+    perceptual_scale::PerceptualScale,
+    pixel::Pixel,
+    pixel_generators::PixelGenerator,
+};
 
 pub trait GetPixel: Sync {
     fn execute<TPixelGenerator: PixelGenerator>(
         &self,
         pixel_generator: &TPixelGenerator,
+        angle_perceptual_scale: Option<&PerceptualScale>,
         angle_degrees: f64,
         variable_dimension: f64,
-        angle_buckets: u32,
-        distance_buckets: u32,
+        pixel_generator_index: usize,
+        definition: &ColorWheelDefinition<TPixelGenerator>,
     ) -> Pixel;
+
+    // As `execute`, but the color at full precision.
+    fn execute_color<TPixelGenerator: PixelGenerator>(
+        &self,
+        pixel_generator: &TPixelGenerator,
+        angle_perceptual_scale: Option<&PerceptualScale>,
+        angle_degrees: f64,
+        variable_dimension: f64,
+        pixel_generator_index: usize,
+        definition: &ColorWheelDefinition<TPixelGenerator>,
+    ) -> Color;
+
+    // Gets the pixels for a run of positions in the same ring, as `execute` does for each.
+    #[allow(clippy::too_many_arguments)]
+    fn execute_batch<TPixelGenerator: PixelGenerator>(
+        &self,
+        pixel_generator: &TPixelGenerator,
+        angle_perceptual_scale: Option<&PerceptualScale>,
+        angles_degrees: &[f64],
+        variable_dimensions: &[f64],
+        pixel_generator_index: usize,
+        definition: &ColorWheelDefinition<TPixelGenerator>,
+        pixels: &mut [Pixel],
+    ) {
+        for ((angle_degrees, variable_dimension), pixel) in
+            angles_degrees.iter().zip(variable_dimensions).zip(pixels)
+        {
+            *pixel = self.execute(
+                pixel_generator,
+                angle_perceptual_scale,
+                *angle_degrees,
+                *variable_dimension,
+                pixel_generator_index,
+                definition,
+            );
+        }
+    }
 }
 
 pub struct DefaultGetPixel {}
-impl GetPixel for DefaultGetPixel {
-    fn execute<TPixelGenerator: PixelGenerator>(
+impl DefaultGetPixel {
+    // The angle and varying dimension to get the pixel at, after bucketing and inverting.
+    fn bucket_and_invert<TPixelGenerator: PixelGenerator>(
         &self,
         pixel_generator: &TPixelGenerator,
+        angle_perceptual_scale: Option<&PerceptualScale>,
         mut angle_degrees: f64,
         mut variable_dimension: f64,
-        angle_buckets: u32,
-        distance_buckets: u32,
-    ) -> Pixel {
+        pixel_generator_index: usize,
+        definition: &ColorWheelDefinition<TPixelGenerator>,
+    ) -> (f64, f64) {
         let configuration = pixel_generator.configuration();
 
-        angle_degrees = bucket(
-            angle_degrees,
-            360.,
-            angle_buckets,
-            configuration.angle_bucket_direction,
-        );
-
-        variable_dimension = bucket(
+        angle_degrees = match angle_perceptual_scale {
+            // Bucket by the proportion of the perceptual distance around the circle.
+            Some(scale) if configuration.angle_bucket_spacing == BucketSpacing::Perceptual => {
+                let proportion = bucket_with_layout(
+                    scale.proportion_at(angle_degrees / 360.),
+                    1.,
+                    definition.ring_angle_buckets(pixel_generator_index),
+                    configuration.angle_bucket_direction,
+                    &definition.angle_bucket_layout,
+                );
+                scale.position_at(proportion) * 360.
+            }
+            _ => bucket_with_layout(
+                angle_degrees,
+                360.,
+                definition.ring_angle_buckets(pixel_generator_index),
+                configuration.angle_bucket_direction,
+                &definition.angle_bucket_layout,
+            ),
+        };
+
+        variable_dimension = bucket_with_layout(
             variable_dimension,
             1.,
-            distance_buckets,
+            definition.ring_distance_buckets(pixel_generator_index),
             configuration.varying_dimension_bucket_direction,
+            &definition.distance_bucket_layout,
         );
 
         if configuration.is_angle_inverted {
@@ -45,20 +107,111 @@ impl GetPixel for DefaultGetPixel {
             variable_dimension = 1. - variable_dimension;
         }
 
+        (angle_degrees, variable_dimension)
+    }
+}
+
+impl GetPixel for DefaultGetPixel {
+    fn execute<TPixelGenerator: PixelGenerator>(
+        &self,
+        pixel_generator: &TPixelGenerator,
+        angle_perceptual_scale: Option<&PerceptualScale>,
+        angle_degrees: f64,
+        variable_dimension: f64,
+        pixel_generator_index: usize,
+        definition: &ColorWheelDefinition<TPixelGenerator>,
+    ) -> Pixel {
+        let (angle_degrees, variable_dimension) = self.bucket_and_invert(
+            pixel_generator,
+            angle_perceptual_scale,
+            angle_degrees,
+            variable_dimension,
+            pixel_generator_index,
+            definition,
+        );
+
         pixel_generator.get_pixel(angle_degrees, variable_dimension)
     }
+
+    fn execute_color<TPixelGenerator: PixelGenerator>(
+        &self,
+        pixel_generator: &TPixelGenerator,
+        angle_perceptual_scale: Option<&PerceptualScale>,
+        angle_degrees: f64,
+        variable_dimension: f64,
+        pixel_generator_index: usize,
+        definition: &ColorWheelDefinition<TPixelGenerator>,
+    ) -> Color {
+        let (angle_degrees, variable_dimension) = self.bucket_and_invert(
+            pixel_generator,
+            angle_perceptual_scale,
+            angle_degrees,
+            variable_dimension,
+            pixel_generator_index,
+            definition,
+        );
+
+        pixel_generator.get_color(angle_degrees, variable_dimension)
+    }
+
+    // Buckets every position first, so the pixel generator can convert them together.
+    fn execute_batch<TPixelGenerator: PixelGenerator>(
+        &self,
+        pixel_generator: &TPixelGenerator,
+        angle_perceptual_scale: Option<&PerceptualScale>,
+        angles_degrees: &[f64],
+        variable_dimensions: &[f64],
+        pixel_generator_index: usize,
+        definition: &ColorWheelDefinition<TPixelGenerator>,
+        pixels: &mut [Pixel],
+    ) {
+        let (angles_degrees, variable_dimensions): (Vec<_>, Vec<_>) = angles_degrees
+            .iter()
+            .zip(variable_dimensions)
+            .map(|(angle_degrees, variable_dimension)| {
+                self.bucket_and_invert(
+                    pixel_generator,
+                    angle_perceptual_scale,
+                    *angle_degrees,
+                    *variable_dimension,
+                    pixel_generator_index,
+                    definition,
+                )
+            })
+            .unzip();
+
+        pixel_generator.get_pixels(&angles_degrees, &variable_dimensions, pixels);
+    }
 }
 
 #[cfg(test)]
 mod tests {
     use crate::{
-        bucket::BucketDirection,
+        bucket::{BucketDirection, BucketLayout},
         pixel_generators::{MockPixelGenerator, PixelGeneratorConfiguration},
     };
     use mockall::predicate::*;
 
     use super::*;
 
+    fn definition(
+        angle_buckets: u32,
+        distance_buckets: u32,
+    ) -> ColorWheelDefinition<MockPixelGenerator> {
+        ColorWheelDefinition {
+            image_size: 100,
+            margin_size: 0,
+            angle_buckets,
+            distance_buckets,
+            angle_bucket_layout: Default::default(),
+            distance_bucket_layout: Default::default(),
+            shape: Default::default(),
+            radial_mapping: Default::default(),
+            pixel_generators: vec![],
+            rings: vec![],
+        }
+    }
+
     fn setup(
         pixel_generator: &mut MockPixelGenerator,
         angle_bucket_direction: BucketDirection,
@@ -73,6 +226,7 @@ mod tests {
             .return_const(PixelGeneratorConfiguration {
                 varying_dimension_bucket_direction,
                 angle_bucket_direction,
+                angle_bucket_spacing: BucketSpacing::Uniform,
                 is_varying_dimension_inverted,
                 is_angle_inverted,
             });
@@ -105,7 +259,7 @@ mod tests {
         );
 
         let get_pixel = DefaultGetPixel {};
-        let pixel = get_pixel.execute(&pixel_generator, 135., 0.2, 0, 0);
+        let pixel = get_pixel.execute(&pixel_generator, None, 135., 0.2, 0, &definition(0, 0));
 
         assert_eq!(pixel, expected_pixel);
     }
@@ -125,7 +279,7 @@ mod tests {
         );
 
         let get_pixel = DefaultGetPixel {};
-        let pixel = get_pixel.execute(&pixel_generator, 135., 0.2, 4, 0);
+        let pixel = get_pixel.execute(&pixel_generator, None, 135., 0.2, 0, &definition(4, 0));
 
         assert_eq!(pixel, expected_pixel);
     }
@@ -145,7 +299,7 @@ mod tests {
         );
 
         let get_pixel = DefaultGetPixel {};
-        let pixel = get_pixel.execute(&pixel_generator, 135., 0.2, 4, 0);
+        let pixel = get_pixel.execute(&pixel_generator, None, 135., 0.2, 0, &definition(4, 0));
 
         assert_eq!(pixel, expected_pixel);
     }
@@ -165,7 +319,7 @@ mod tests {
         );
 
         let get_pixel = DefaultGetPixel {};
-        let pixel = get_pixel.execute(&pixel_generator, 135., 0.2, 0, 4);
+        let pixel = get_pixel.execute(&pixel_generator, None, 135., 0.2, 0, &definition(0, 4));
 
         assert_eq!(pixel, expected_pixel);
     }
@@ -185,7 +339,7 @@ mod tests {
         );
 
         let get_pixel = DefaultGetPixel {};
-        let pixel = get_pixel.execute(&pixel_generator, 135., 0.2, 0, 4);
+        let pixel = get_pixel.execute(&pixel_generator, None, 135., 0.2, 0, &definition(0, 4));
 
         assert_eq!(pixel, expected_pixel);
     }
@@ -205,7 +359,7 @@ mod tests {
         );
 
         let get_pixel = DefaultGetPixel {};
-        let pixel = get_pixel.execute(&pixel_generator, 135., 0.2, 0, 0);
+        let pixel = get_pixel.execute(&pixel_generator, None, 135., 0.2, 0, &definition(0, 0));
 
         assert_eq!(pixel, expected_pixel);
     }
@@ -225,7 +379,7 @@ mod tests {
         );
 
         let get_pixel = DefaultGetPixel {};
-        let pixel = get_pixel.execute(&pixel_generator, 135., 0.2, 0, 0);
+        let pixel = get_pixel.execute(&pixel_generator, None, 135., 0.2, 0, &definition(0, 0));
 
         assert_eq!(pixel, expected_pixel);
     }
@@ -245,8 +399,140 @@ mod tests {
         );
 
         let get_pixel = DefaultGetPixel {};
-        let pixel = get_pixel.execute(&pixel_generator, 135., 0.2, 4, 4);
+        let pixel = get_pixel.execute(&pixel_generator, None, 135., 0.2, 0, &definition(4, 4));
 
         assert_eq!(pixel, expected_pixel);
     }
+
+    #[test]
+    fn it_should_bucket_angle_perceptually() {
+        let mut pixel_generator = MockPixelGenerator::new();
+        pixel_generator
+            .expect_configuration()
+            .return_const(PixelGeneratorConfiguration {
+                varying_dimension_bucket_direction: BucketDirection::Up,
+                angle_bucket_direction: BucketDirection::Down,
+                angle_bucket_spacing: BucketSpacing::Perceptual,
+                is_varying_dimension_inverted: false,
+                is_angle_inverted: false,
+            });
+
+        // The first quarter of the circle is three quarters of the perceptual distance.
+        let scale = PerceptualScale::from_step_distances(&[3., 1., 0., 0.]);
+
+        let expected_pixel = Pixel::rgb(1, 2, 3);
+        pixel_generator
+            .expect_get_pixel()
+            .with(float::is_close(60.), float::is_close(0.2))
+            .once()
+            .return_const(expected_pixel);
+
+        let get_pixel = DefaultGetPixel {};
+        let pixel = get_pixel.execute(
+            &pixel_generator,
+            Some(&scale),
+            80.,
+            0.2,
+            0,
+            &definition(2, 0),
+        );
+
+        assert_eq!(pixel, expected_pixel);
+    }
+
+    #[test]
+    fn it_should_bucket_with_layouts() {
+        let mut pixel_generator = MockPixelGenerator::new();
+
+        let expected_pixel = setup(
+            &mut pixel_generator,
+            BucketDirection::Center,
+            BucketDirection::Down,
+            false,
+            false,
+            45.,
+            0.3,
+        );
+
+        let mut definition = definition(4, 0);
+        definition.distance_bucket_layout = BucketLayout {
+            boundaries: vec![0.1, 0.5],
+            sample_positions: vec![0., 0.3],
+        };
+
+        let get_pixel = DefaultGetPixel {};
+        let pixel = get_pixel.execute(&pixel_generator, None, 80., 0.2, 0, &definition);
+
+        assert_eq!(pixel, expected_pixel);
+    }
+
+    #[test]
+    fn it_should_bucket_and_invert_batches_before_getting_pixels() {
+        let mut pixel_generator = MockPixelGenerator::new();
+        pixel_generator
+            .expect_configuration()
+            .return_const(PixelGeneratorConfiguration {
+                varying_dimension_bucket_direction: BucketDirection::Up,
+                angle_bucket_direction: BucketDirection::Down,
+                angle_bucket_spacing: BucketSpacing::Uniform,
+                is_varying_dimension_inverted: true,
+                is_angle_inverted: false,
+            });
+
+        let expected_pixels = [Pixel::rgb(1, 2, 3), Pixel::rgb(4, 5, 6)];
+        pixel_generator
+            .expect_get_pixels()
+            .withf(|angles_degrees, varying_dimension_values, pixels| {
+                angles_degrees == [90., 270.]
+                    && varying_dimension_values == [0.75, 0.25]
+                    && pixels.len() == 2
+            })
+            .once()
+            .returning(move |_, _, pixels| pixels.copy_from_slice(&expected_pixels));
+
+        let mut pixels = [Pixel::transparent(); 2];
+        DefaultGetPixel {}.execute_batch(
+            &pixel_generator,
+            None,
+            &[135., 300.],
+            &[0.2, 0.6],
+            0,
+            &definition(4, 4),
+            &mut pixels,
+        );
+
+        assert_eq!(pixels, expected_pixels);
+    }
+
+    #[test]
+    fn it_should_bucket_and_invert_before_getting_colors() {
+        let mut pixel_generator = MockPixelGenerator::new();
+        pixel_generator
+            .expect_configuration()
+            .return_const(PixelGeneratorConfiguration {
+                varying_dimension_bucket_direction: BucketDirection::Up,
+                angle_bucket_direction: BucketDirection::Down,
+                angle_bucket_spacing: BucketSpacing::Uniform,
+                is_varying_dimension_inverted: false,
+                is_angle_inverted: true,
+            });
+
+        let expected_color = Color::srgb(0.1, 0.2, 0.3);
+        pixel_generator
+            .expect_get_color()
+            .with(float::is_close(270.), float::is_close(0.25))
+            .once()
+            .return_const(expected_color);
+
+        let color = DefaultGetPixel {}.execute_color(
+            &pixel_generator,
+            None,
+            135.,
+            0.2,
+            0,
+            &definition(4, 4),
+        );
+
+        assert_eq!(color, expected_color);
+    }
 }
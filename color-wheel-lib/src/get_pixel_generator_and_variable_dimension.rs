@@ -1,6 +1,10 @@
-use crate::{color_wheel_definition::ColorWheelDefinition, pixel_generators::PixelGenerator};
+use crate::{
+    color_wheel_definition::ColorWheelDefinition, pixel_generators::PixelGenerator,
+    radial_mapping::RadialMapping, render_pixel::RenderPixelData,
+};
 
 pub struct PixelGeneratorAndVariableDimension<'a, TPixelGenerator: PixelGenerator> {
+    pub pixel_generator_index: usize,
     pub pixel_generator: &'a TPixelGenerator,
     pub variable_dimension: f64,
 }
@@ -8,7 +12,7 @@ pub struct PixelGeneratorAndVariableDimension<'a, TPixelGenerator: PixelGenerato
 pub trait GetPixelGeneratorAndVariableDimension: Sync {
     fn execute<'a, TPixelGenerator: PixelGenerator>(
         &self,
-        generator_size: f64,
+        data: &RenderPixelData,
         definition: &'a ColorWheelDefinition<TPixelGenerator>,
         distance_from_center: f64,
     ) -> Option<PixelGeneratorAndVariableDimension<'a, TPixelGenerator>>;
@@ -18,28 +22,41 @@ pub struct DefaultGetPixelGeneratorAndVariableDimension {}
 impl GetPixelGeneratorAndVariableDimension for DefaultGetPixelGeneratorAndVariableDimension {
     fn execute<'a, TPixelGenerator: PixelGenerator>(
         &self,
-        generator_size: f64,
+        data: &RenderPixelData,
         definition: &'a ColorWheelDefinition<TPixelGenerator>,
         distance_from_center: f64,
     ) -> Option<PixelGeneratorAndVariableDimension<'a, TPixelGenerator>> {
         let mut variable_dimension = 1.;
-        let mut generator_inner_distance = 0.;
-        let mut generator_outer_distance = generator_size;
+        let mut generator_inner_distance = data.inner_radius;
         let mut pixel_generator = None;
+        let mut pixel_generator_index = 0;
 
-        for current_pixel_generator in definition.pixel_generators.iter() {
-            if distance_from_center <= generator_outer_distance {
-                variable_dimension =
-                    (distance_from_center - generator_inner_distance) / generator_size;
+        for (index, current_pixel_generator) in definition.pixel_generators.iter().enumerate() {
+            // Inside the hole in the middle of the wheel, or the gap before this ring.
+            if distance_from_center < generator_inner_distance {
+                break;
+            }
+
+            let generator_size = data.generator_sizes[index];
+            if distance_from_center <= generator_inner_distance + generator_size {
+                let distance = (distance_from_center - generator_inner_distance) / generator_size;
+
+                variable_dimension = match definition.radial_mapping {
+                    RadialMapping::Perceptual => {
+                        data.radial_perceptual_scales[index].position_at(distance)
+                    }
+                    radial_mapping => radial_mapping.map(distance),
+                };
                 pixel_generator = Some(current_pixel_generator);
+                pixel_generator_index = index;
                 break;
             }
 
-            generator_inner_distance = generator_outer_distance;
-            generator_outer_distance += generator_size;
+            generator_inner_distance += generator_size + data.ring_gap;
         }
 
         pixel_generator.map(|pixel_generator| PixelGeneratorAndVariableDimension {
+            pixel_generator_index,
             pixel_generator,
             variable_dimension,
         })
@@ -50,30 +67,48 @@ impl GetPixelGeneratorAndVariableDimension for DefaultGetPixelGeneratorAndVariab
 mod tests {
     use float_cmp::assert_approx_eq;
 
-    use crate::{pixel::Pixel, pixel_generators::PixelGeneratorConfiguration};
+    use crate::{
+        color::Color,
+        perceptual_scale::PerceptualScale,
+        pixel_generators::{PixelGeneratorConfiguration, PixelGeneratorDomain},
+    };
 
     use super::*;
 
     struct Setup {
         pub target: DefaultGetPixelGeneratorAndVariableDimension,
-        pub generator_size: f64,
+        pub data: RenderPixelData,
         pub definition: ColorWheelDefinition<MockPixelGenerator>,
     }
 
     fn setup() -> Setup {
         Setup {
             target: DefaultGetPixelGeneratorAndVariableDimension {},
-            generator_size: 40.,
+            data: RenderPixelData {
+                center_x: 70,
+                center_y: 70,
+                all_generators_size: 120.,
+                generator_sizes: vec![40., 40., 40.],
+                inner_radius: 0.,
+                ring_gap: 0.,
+                radial_perceptual_scales: vec![],
+                angle_perceptual_scales: vec![],
+            },
             definition: ColorWheelDefinition {
                 image_size: 140,
                 margin_size: 10,
                 angle_buckets: 10,
                 distance_buckets: 10,
+                angle_bucket_layout: Default::default(),
+                distance_bucket_layout: Default::default(),
+                shape: Default::default(),
+                radial_mapping: Default::default(),
                 pixel_generators: vec![
                     MockPixelGenerator { id: 1 },
                     MockPixelGenerator { id: 2 },
                     MockPixelGenerator { id: 3 },
                 ],
+                rings: vec![],
             },
         }
     }
@@ -82,9 +117,7 @@ mod tests {
     fn outside_generators() {
         let setup = setup();
 
-        let result = setup
-            .target
-            .execute(setup.generator_size, &setup.definition, 121.);
+        let result = setup.target.execute(&setup.data, &setup.definition, 121.);
 
         assert!(result.is_none());
     }
@@ -93,9 +126,7 @@ mod tests {
     fn within_inner_generator() {
         let setup = setup();
 
-        let result = setup
-            .target
-            .execute(setup.generator_size, &setup.definition, 38.);
+        let result = setup.target.execute(&setup.data, &setup.definition, 38.);
 
         let result = result.unwrap();
         assert_eq!(result.pixel_generator.id, 1);
@@ -106,11 +137,10 @@ mod tests {
     fn within_middle_generator() {
         let setup = setup();
 
-        let result = setup
-            .target
-            .execute(setup.generator_size, &setup.definition, 75.);
+        let result = setup.target.execute(&setup.data, &setup.definition, 75.);
 
         let result = result.unwrap();
+        assert_eq!(result.pixel_generator_index, 1);
         assert_eq!(result.pixel_generator.id, 2);
         assert_approx_eq!(f64, result.variable_dimension, 35. / 40.);
     }
@@ -119,15 +149,105 @@ mod tests {
     fn within_outer_generator() {
         let setup = setup();
 
-        let result = setup
-            .target
-            .execute(setup.generator_size, &setup.definition, 119.);
+        let result = setup.target.execute(&setup.data, &setup.definition, 119.);
 
         let result = result.unwrap();
         assert_eq!(result.pixel_generator.id, 3);
         assert_approx_eq!(f64, result.variable_dimension, 39. / 40.);
     }
 
+    #[test]
+    fn within_inner_radius() {
+        let mut setup = setup();
+        setup.data.inner_radius = 30.;
+        setup.data.generator_sizes = vec![30., 30., 30.];
+
+        let result = setup.target.execute(&setup.data, &setup.definition, 29.);
+        assert!(result.is_none());
+
+        let result = setup
+            .target
+            .execute(&setup.data, &setup.definition, 36.)
+            .unwrap();
+        assert_eq!(result.pixel_generator.id, 1);
+        assert_approx_eq!(f64, result.variable_dimension, 6. / 30.);
+    }
+
+    #[test]
+    fn within_ring_gap() {
+        let mut setup = setup();
+        setup.data.generator_sizes = vec![36., 36., 36.];
+        setup.data.ring_gap = 6.;
+
+        let result = setup.target.execute(&setup.data, &setup.definition, 40.);
+        assert!(result.is_none());
+
+        let result = setup
+            .target
+            .execute(&setup.data, &setup.definition, 42.)
+            .unwrap();
+        assert_eq!(result.pixel_generator.id, 2);
+        assert_approx_eq!(f64, result.variable_dimension, 0.);
+
+        let result = setup
+            .target
+            .execute(&setup.data, &setup.definition, 120.)
+            .unwrap();
+        assert_eq!(result.pixel_generator.id, 3);
+        assert_approx_eq!(f64, result.variable_dimension, 1.);
+    }
+
+    #[test]
+    fn within_weighted_rings() {
+        let mut setup = setup();
+        setup.data.generator_sizes = vec![20., 80., 20.];
+
+        let result = setup
+            .target
+            .execute(&setup.data, &setup.definition, 30.)
+            .unwrap();
+        assert_eq!(result.pixel_generator.id, 2);
+        assert_approx_eq!(f64, result.variable_dimension, 10. / 80.);
+
+        let result = setup
+            .target
+            .execute(&setup.data, &setup.definition, 110.)
+            .unwrap();
+        assert_eq!(result.pixel_generator.id, 3);
+        assert_approx_eq!(f64, result.variable_dimension, 10. / 20.);
+    }
+
+    #[test]
+    fn it_should_apply_radial_mapping() {
+        let mut setup = setup();
+        setup.definition.radial_mapping = RadialMapping::Sqrt;
+
+        let result = setup
+            .target
+            .execute(&setup.data, &setup.definition, 60.)
+            .unwrap();
+        assert_eq!(result.pixel_generator.id, 2);
+        assert_approx_eq!(f64, result.variable_dimension, 0.25);
+    }
+
+    #[test]
+    fn it_should_apply_perceptual_radial_mapping_per_generator() {
+        let mut setup = setup();
+        setup.definition.radial_mapping = RadialMapping::Perceptual;
+        setup.data.radial_perceptual_scales = vec![
+            PerceptualScale::from_step_distances(&[1., 1.]),
+            PerceptualScale::from_step_distances(&[3., 1.]),
+            PerceptualScale::from_step_distances(&[1., 1.]),
+        ];
+
+        let result = setup
+            .target
+            .execute(&setup.data, &setup.definition, 70.)
+            .unwrap();
+        assert_eq!(result.pixel_generator.id, 2);
+        assert_approx_eq!(f64, result.variable_dimension, 0.5);
+    }
+
     struct MockPixelGenerator {
         id: usize,
     }
@@ -136,7 +256,15 @@ mod tests {
             unimplemented!()
         }
 
-        fn get_pixel(&self, _angle_degrees: f64, _varying_dimension_value: f64) -> Pixel {
+        fn domain(&self) -> PixelGeneratorDomain {
+            unimplemented!()
+        }
+
+        fn fixed(&self) -> f64 {
+            unimplemented!()
+        }
+
+        fn get_color(&self, _angle_degrees: f64, _varying_dimension_value: f64) -> Color {
             unimplemented!()
         }
     }
@@ -1,17 +1,37 @@
+pub mod atan2;
+pub mod background;
 pub mod bucket;
 pub mod canvas;
+pub mod canvas_filters;
 pub mod canvas_pixel_writer;
 pub mod canvas_pixel_writer_factory;
+pub mod color;
+pub mod color_vision_deficiency;
 pub mod color_wheel_definition;
+pub mod color_wheel_error;
+pub mod color_wheel_projection;
+pub mod color_wheel_query;
+pub mod color_wheel_ring;
+pub mod color_wheel_shape;
+pub mod color_wheel_spec;
+pub mod dither;
 pub mod get_angle_degrees;
 pub mod get_pixel;
 pub mod get_pixel_generator_and_variable_dimension;
+pub mod linear_rgb;
 pub mod offset_canvas_pixel_writer;
 pub mod offset_row_pixel_writer;
+pub mod perceptual_distance;
+pub mod perceptual_scale;
 pub mod pixel;
 pub mod pixel_generators;
+pub mod radial_mapping;
 pub mod render_color_wheel;
 pub mod render_color_wheel_rows;
 pub mod render_color_wheel_set;
+pub mod render_color_wheel_spec;
 pub mod render_pixel;
+pub mod render_thread_pool;
+pub mod resample;
+pub mod ring_options;
 pub mod row_pixel_writer;
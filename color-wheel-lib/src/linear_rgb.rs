@@ -0,0 +1,44 @@
+// https://en.wikipedia.org/wiki/SRGB#Transfer_function_(%22gamma%22)
+pub fn srgb_to_linear(value: f64) -> f64 {
+    if value <= 0.04045 {
+        value / 12.92
+    } else {
+        ((value + 0.055) / 1.055).powf(2.4)
+    }
+}
+
+pub fn linear_to_srgb(value: f64) -> f64 {
+    if value <= 0.0031308 {
+        value * 12.92
+    } else {
+        1.055 * value.powf(1. / 2.4) - 0.055
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use float_cmp::assert_approx_eq;
+
+    use super::*;
+
+    #[test]
+    fn it_should_convert_srgb_to_linear() {
+        assert_approx_eq!(f64, srgb_to_linear(0.), 0.);
+        assert_approx_eq!(f64, srgb_to_linear(1.), 1.);
+        assert_approx_eq!(f64, srgb_to_linear(0.02), 0.02 / 12.92);
+        assert_approx_eq!(f64, srgb_to_linear(0.5), 0.21404, epsilon = 0.00001);
+    }
+
+    #[test]
+    fn it_should_round_trip() {
+        for i in 0..=255 {
+            let value = i as f64 / 255.;
+            assert_approx_eq!(
+                f64,
+                linear_to_srgb(srgb_to_linear(value)),
+                value,
+                epsilon = 0.000001
+            );
+        }
+    }
+}
@@ -16,8 +16,10 @@ impl<'canvas, TCanvasPixelWriter> CanvasPixelWriter
 where
     TCanvasPixelWriter: CanvasPixelWriter + 'canvas,
 {
-    type RowPixelWriter<'inner> = OffsetRowPixelWriter<TCanvasPixelWriter::RowPixelWriter<'inner>>
-        where 'canvas : 'inner;
+    type RowPixelWriter<'inner>
+        = OffsetRowPixelWriter<TCanvasPixelWriter::RowPixelWriter<'inner>>
+    where
+        'canvas: 'inner;
 
     fn rows_mut(&mut self) -> Vec<Self::RowPixelWriter<'_>> {
         self.canvas_pixel_writer
@@ -86,8 +88,8 @@ mod tests {
 
         assert_eq!(rows.len(), 2);
 
-        rows[0].write_pixel(0, 0, Pixel::rgb(1, 2, 3));
-        rows[1].write_pixel(2, 1, Pixel::rgb(4, 5, 6));
+        rows[0].write_pixel(0, 0, Pixel::rgb(1, 2, 3)).unwrap();
+        rows[1].write_pixel(2, 1, Pixel::rgb(4, 5, 6)).unwrap();
 
         assert_eq!(
             canvas_pixel_writer.canvas.get_pixel(1, 2),
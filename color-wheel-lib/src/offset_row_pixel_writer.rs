@@ -1,4 +1,7 @@
-use crate::{pixel::Pixel, row_pixel_writer::RowPixelWriter};
+use crate::{
+    color::Color, color_wheel_error::ColorWheelError, pixel::Pixel,
+    row_pixel_writer::RowPixelWriter,
+};
 
 pub struct OffsetRowPixelWriter<TRowPixelWriter>
 where
@@ -13,9 +16,16 @@ impl<TRowPixelWriter> RowPixelWriter for OffsetRowPixelWriter<TRowPixelWriter>
 where
     TRowPixelWriter: RowPixelWriter,
 {
-    fn write_pixel(&mut self, x: u32, y: u32, pixel: Pixel) {
+    const FULL_PRECISION: bool = TRowPixelWriter::FULL_PRECISION;
+
+    fn write_pixel(&mut self, x: u32, y: u32, pixel: Pixel) -> Result<(), ColorWheelError> {
+        self.row_pixel_writer
+            .write_pixel(x + self.offset_x, y + self.offset_y, pixel)
+    }
+
+    fn write_color(&mut self, x: u32, y: u32, color: Color) -> Result<(), ColorWheelError> {
         self.row_pixel_writer
-            .write_pixel(x + self.offset_x, y + self.offset_y, pixel);
+            .write_color(x + self.offset_x, y + self.offset_y, color)
     }
 }
 
@@ -39,7 +49,7 @@ mod tests {
                 mockall::predicate::eq(pixel),
             )
             .once()
-            .return_const(());
+            .return_const(Ok(()));
 
         let mut offset_pixel_writer = OffsetRowPixelWriter {
             row_pixel_writer: pixel_writer,
@@ -47,6 +57,6 @@ mod tests {
             offset_y: 20,
         };
 
-        offset_pixel_writer.write_pixel(1, 2, pixel);
+        assert_eq!(offset_pixel_writer.write_pixel(1, 2, pixel), Ok(()));
     }
 }
@@ -0,0 +1,37 @@
+use oklab::{srgb_to_oklab, RGB};
+
+use crate::pixel::Pixel;
+
+// Euclidean distance in OKLab (ΔE OK), ignoring alpha.
+pub fn perceptual_distance(a: Pixel, b: Pixel) -> f64 {
+    let a = srgb_to_oklab(RGB::new(a.red(), a.green(), a.blue()));
+    let b = srgb_to_oklab(RGB::new(b.red(), b.green(), b.blue()));
+
+    (((a.l - b.l).powi(2) + (a.a - b.a).powi(2) + (a.b - b.b).powi(2)) as f64).sqrt()
+}
+
+#[cfg(test)]
+mod tests {
+    use float_cmp::assert_approx_eq;
+
+    use super::*;
+
+    #[test]
+    fn identical_colors_should_have_no_distance() {
+        assert_approx_eq!(
+            f64,
+            perceptual_distance(Pixel::rgb(10, 200, 30), Pixel::rgb(10, 200, 30)),
+            0.
+        );
+    }
+
+    #[test]
+    fn black_and_white_should_differ_by_lightness() {
+        assert_approx_eq!(
+            f64,
+            perceptual_distance(Pixel::rgb(0, 0, 0), Pixel::rgb(255, 255, 255)),
+            1.,
+            epsilon = 0.001
+        );
+    }
+}
@@ -0,0 +1,224 @@
+use crate::{
+    perceptual_distance::perceptual_distance, pixel::Pixel, pixel_generators::PixelGenerator,
+};
+
+// Number of steps sampled along the scale.
+const STEPS: usize = 256;
+
+// Number of samples taken across the other dimension, which are averaged.
+const ACROSS_SAMPLES: usize = 36;
+
+// Maps a proportion of the total perceptual distance along a scale back to a position on
+// the scale, so that equal steps in proportion give equal perceptual steps in color.
+#[derive(Debug, Clone, PartialEq)]
+pub struct PerceptualScale {
+    // Cumulative perceptual distance at each step, starting at zero.
+    cumulative_distances: Vec<f64>,
+}
+
+impl PerceptualScale {
+    // The distance between each consecutive pair of samples taken evenly along the scale.
+    pub fn from_step_distances(step_distances: &[f64]) -> Self {
+        let mut cumulative_distances = Vec::with_capacity(step_distances.len() + 1);
+        let mut total = 0.;
+        cumulative_distances.push(total);
+        for distance in step_distances {
+            total += distance.max(0.);
+            cumulative_distances.push(total);
+        }
+
+        Self {
+            cumulative_distances,
+        }
+    }
+
+    // A scale along the pixel generator's variable dimension, averaged over all angles.
+    pub fn variable_dimension<TPixelGenerator: PixelGenerator>(
+        pixel_generator: &TPixelGenerator,
+    ) -> Self {
+        let is_inverted = pixel_generator
+            .configuration()
+            .is_varying_dimension_inverted;
+
+        Self::sample(|position, across| {
+            let value = if is_inverted { 1. - position } else { position };
+            pixel_generator.get_pixel(across * 360., value)
+        })
+    }
+
+    // A scale around the pixel generator's angle, averaged over the variable dimension.
+    pub fn angle<TPixelGenerator: PixelGenerator>(pixel_generator: &TPixelGenerator) -> Self {
+        let is_inverted = pixel_generator.configuration().is_angle_inverted;
+
+        Self::sample(|position, across| {
+            let angle_degrees = position * 360.;
+            let angle_degrees = if is_inverted {
+                360. - angle_degrees
+            } else {
+                angle_degrees
+            };
+            pixel_generator.get_pixel(angle_degrees, across)
+        })
+    }
+
+    // Samples colors along the scale, averaging the distance between steps over samples taken
+    // across the other dimension. Both arguments to `get_pixel` are from 0 to 1.
+    fn sample(get_pixel: impl Fn(f64, f64) -> Pixel) -> Self {
+        let step_distances = (1..=STEPS)
+            .map(|step| {
+                let previous_position = (step - 1) as f64 / STEPS as f64;
+                let position = step as f64 / STEPS as f64;
+
+                (0..ACROSS_SAMPLES)
+                    .map(|sample| {
+                        let across = (sample as f64 + 0.5) / ACROSS_SAMPLES as f64;
+                        perceptual_distance(
+                            get_pixel(previous_position, across),
+                            get_pixel(position, across),
+                        )
+                    })
+                    .sum::<f64>()
+                    / ACROSS_SAMPLES as f64
+            })
+            .collect::<Vec<_>>();
+
+        Self::from_step_distances(&step_distances)
+    }
+
+    // The inverse of `position_at`.
+    pub fn proportion_at(&self, position: f64) -> f64 {
+        let steps = self.cumulative_distances.len() - 1;
+        let total = self.cumulative_distances[steps];
+        if steps == 0 || total <= 0. {
+            return position;
+        }
+
+        let step_position = position.clamp(0., 1.) * steps as f64;
+        let step = (step_position.floor() as usize).min(steps - 1);
+        let start = self.cumulative_distances[step];
+        let end = self.cumulative_distances[step + 1];
+
+        (start + (end - start) * (step_position - step as f64)) / total
+    }
+
+    // Both the proportion and the returned position are from 0 to 1.
+    pub fn position_at(&self, proportion: f64) -> f64 {
+        let steps = self.cumulative_distances.len() - 1;
+        let total = self.cumulative_distances[steps];
+        if steps == 0 || total <= 0. {
+            return proportion;
+        }
+
+        let target = proportion.clamp(0., 1.) * total;
+        let step = self
+            .cumulative_distances
+            .partition_point(|distance| *distance < target)
+            .clamp(1, steps);
+
+        let start = self.cumulative_distances[step - 1];
+        let end = self.cumulative_distances[step];
+        let within_step = if end > start {
+            (target - start) / (end - start)
+        } else {
+            0.
+        };
+
+        (step as f64 - 1. + within_step) / steps as f64
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use float_cmp::assert_approx_eq;
+
+    use crate::{
+        bucket::{BucketDirection, BucketSpacing},
+        pixel_generators::{MockPixelGenerator, PixelGeneratorConfiguration},
+    };
+
+    use super::*;
+
+    #[test]
+    fn uniform_steps_should_be_linear() {
+        let scale = PerceptualScale::from_step_distances(&[1., 1., 1., 1.]);
+        assert_approx_eq!(f64, scale.position_at(0.), 0.);
+        assert_approx_eq!(f64, scale.position_at(0.3), 0.3);
+        assert_approx_eq!(f64, scale.position_at(1.), 1.);
+    }
+
+    #[test]
+    fn it_should_spend_longer_on_larger_steps() {
+        let scale = PerceptualScale::from_step_distances(&[3., 1.]);
+        assert_approx_eq!(f64, scale.position_at(0.375), 0.25);
+        assert_approx_eq!(f64, scale.position_at(0.75), 0.5);
+        assert_approx_eq!(f64, scale.position_at(0.875), 0.75);
+    }
+
+    #[test]
+    fn no_change_should_be_linear() {
+        let scale = PerceptualScale::from_step_distances(&[0., 0.]);
+        assert_approx_eq!(f64, scale.position_at(0.4), 0.4);
+    }
+
+    #[test]
+    fn proportion_should_invert_position() {
+        let scale = PerceptualScale::from_step_distances(&[3., 1., 0., 2.]);
+        for proportion in [0., 0.1, 0.5, 0.6, 0.9, 1.] {
+            assert_approx_eq!(
+                f64,
+                scale.proportion_at(scale.position_at(proportion)),
+                proportion,
+                epsilon = 0.000001
+            );
+        }
+    }
+
+    #[test]
+    fn it_should_sample_variable_dimension() {
+        let mut pixel_generator = MockPixelGenerator::new();
+        pixel_generator
+            .expect_configuration()
+            .return_const(PixelGeneratorConfiguration {
+                is_angle_inverted: false,
+                is_varying_dimension_inverted: false,
+                angle_bucket_direction: BucketDirection::Down,
+                angle_bucket_spacing: BucketSpacing::Uniform,
+                varying_dimension_bucket_direction: BucketDirection::Up,
+            });
+
+        // Colors only change in the second half of the variable dimension.
+        pixel_generator.expect_get_pixel().returning(|_, value| {
+            let gray = ((value - 0.5).max(0.) * 2. * 255.).round() as u8;
+            Pixel::rgb(gray, gray, gray)
+        });
+
+        let scale = PerceptualScale::variable_dimension(&pixel_generator);
+        assert!(scale.position_at(0.01) > 0.5);
+        assert!(scale.position_at(0.5) < scale.position_at(0.6));
+        assert_approx_eq!(f64, scale.position_at(1.), 1.);
+    }
+
+    #[test]
+    fn it_should_sample_angle() {
+        let mut pixel_generator = MockPixelGenerator::new();
+        pixel_generator
+            .expect_configuration()
+            .return_const(PixelGeneratorConfiguration {
+                is_angle_inverted: false,
+                is_varying_dimension_inverted: false,
+                angle_bucket_direction: BucketDirection::Down,
+                angle_bucket_spacing: BucketSpacing::Perceptual,
+                varying_dimension_bucket_direction: BucketDirection::Up,
+            });
+
+        // Colors only change in the first quarter of the circle.
+        pixel_generator.expect_get_pixel().returning(|angle, _| {
+            let gray = ((angle / 90.).min(1.) * 255.).round() as u8;
+            Pixel::rgb(gray, gray, gray)
+        });
+
+        let scale = PerceptualScale::angle(&pixel_generator);
+        assert!(scale.position_at(0.99) < 0.25);
+        assert_approx_eq!(f64, scale.proportion_at(0.5), 1.);
+    }
+}
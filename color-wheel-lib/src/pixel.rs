@@ -35,6 +35,35 @@ impl Pixel {
         Self::rgb(denormalize(red), denormalize(green), denormalize(blue))
     }
 
+    pub fn with_alpha(&self, alpha: u8) -> Self {
+        Self::rgba(self.red(), self.green(), self.blue(), alpha)
+    }
+
+    // Composites this pixel over the background pixel, using straight (not premultiplied) alpha.
+    pub fn over(&self, background: Pixel) -> Self {
+        let alpha = self.alpha() as f64 / 255.;
+        let background_alpha = (background.alpha() as f64 / 255.) * (1. - alpha);
+        let result_alpha = alpha + background_alpha;
+
+        if result_alpha == 0. {
+            return Self::transparent();
+        }
+
+        let blend = |index: usize| {
+            let value = (self.data[index] as f64 * alpha
+                + background.data[index] as f64 * background_alpha)
+                / result_alpha;
+            value.round() as u8
+        };
+
+        Self::rgba(
+            blend(0),
+            blend(1),
+            blend(2),
+            (result_alpha * 255.).round() as u8,
+        )
+    }
+
     pub fn red(&self) -> u8 {
         self.data[0]
     }
@@ -108,4 +137,32 @@ mod tests {
             Pixel::rgba(191, 191, 191, OPAQUE)
         );
     }
+
+    #[test]
+    fn with_alpha() {
+        assert_eq!(Pixel::rgb(1, 2, 3).with_alpha(4), Pixel::rgba(1, 2, 3, 4));
+    }
+
+    #[test]
+    fn over() {
+        let background = Pixel::rgb(0, 0, 255);
+
+        assert_eq!(
+            Pixel::rgb(255, 0, 0).over(background),
+            Pixel::rgb(255, 0, 0)
+        );
+        assert_eq!(Pixel::transparent().over(background), background);
+        assert_eq!(
+            Pixel::rgba(255, 0, 0, 128).over(background),
+            Pixel::rgb(128, 0, 127)
+        );
+        assert_eq!(
+            Pixel::rgba(255, 0, 0, 128).over(Pixel::transparent()),
+            Pixel::rgba(255, 0, 0, 128)
+        );
+        assert_eq!(
+            Pixel::transparent().over(Pixel::transparent()),
+            Pixel::transparent()
+        );
+    }
 }
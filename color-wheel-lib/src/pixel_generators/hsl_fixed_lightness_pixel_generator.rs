@@ -1,8 +1,14 @@
-use crate::pixel::Pixel;
+use wide::f64x4;
+
+use crate::{color::Color, pixel::Pixel};
 
 use super::{
-    hsl_to_pixel::hsl_to_pixel, pixel_generator::PixelGenerator,
+    hsl_to_pixel::{hsl_to_color, hsl_to_pixels},
+    pixel_generator::PixelGenerator,
     pixel_generator_configuration::PixelGeneratorConfiguration,
+    pixel_generator_domain::{ColorDimension, PixelGeneratorDomain},
+    pixel_generator_registration::PixelGeneratorRegistration,
+    pixel_lanes::convert_in_lanes,
 };
 
 pub struct HslFixedLightnessPixelGenerator {
@@ -10,12 +16,55 @@ pub struct HslFixedLightnessPixelGenerator {
     pub configuration: PixelGeneratorConfiguration,
 }
 
+impl HslFixedLightnessPixelGenerator {
+    pub const DOMAIN: PixelGeneratorDomain = PixelGeneratorDomain {
+        fixed: ColorDimension::new("lightness", 0., 1.),
+        default_fixed: 0.5,
+        varying: ColorDimension::new("saturation", 0., 1.),
+    };
+
+    pub const REGISTRATION: PixelGeneratorRegistration = PixelGeneratorRegistration {
+        name: "hsl-fixed-lightness",
+        description:
+            "HSL hues around the wheel and saturation from the center, at fixed lightnesses",
+        domain: Self::DOMAIN,
+        factory: |lightness, configuration| {
+            Box::new(HslFixedLightnessPixelGenerator {
+                lightness,
+                configuration,
+            })
+        },
+    };
+}
+
 impl PixelGenerator for HslFixedLightnessPixelGenerator {
     fn configuration(&self) -> PixelGeneratorConfiguration {
         self.configuration
     }
 
-    fn get_pixel(&self, angle_degrees: f64, varying_dimension_value: f64) -> Pixel {
-        hsl_to_pixel(angle_degrees, varying_dimension_value, self.lightness)
+    fn domain(&self) -> PixelGeneratorDomain {
+        Self::DOMAIN
+    }
+
+    fn fixed(&self) -> f64 {
+        self.lightness
+    }
+
+    fn get_color(&self, angle_degrees: f64, varying_dimension_value: f64) -> Color {
+        hsl_to_color(angle_degrees, varying_dimension_value, self.lightness)
+    }
+
+    fn get_pixels(
+        &self,
+        angles_degrees: &[f64],
+        varying_dimension_values: &[f64],
+        pixels: &mut [Pixel],
+    ) {
+        convert_in_lanes(
+            angles_degrees,
+            varying_dimension_values,
+            pixels,
+            |angles, values| hsl_to_pixels(angles, values, f64x4::splat(self.lightness)),
+        );
     }
 }
@@ -1,8 +1,14 @@
-use crate::pixel::Pixel;
+use wide::f64x4;
+
+use crate::{color::Color, pixel::Pixel};
 
 use super::{
-    hsl_to_pixel::hsl_to_pixel, pixel_generator::PixelGenerator,
+    hsl_to_pixel::{hsl_to_color, hsl_to_pixels},
+    pixel_generator::PixelGenerator,
     pixel_generator_configuration::PixelGeneratorConfiguration,
+    pixel_generator_domain::{ColorDimension, PixelGeneratorDomain},
+    pixel_generator_registration::PixelGeneratorRegistration,
+    pixel_lanes::convert_in_lanes,
 };
 
 pub struct HslFixedSaturationPixelGenerator {
@@ -10,12 +16,55 @@ pub struct HslFixedSaturationPixelGenerator {
     pub configuration: PixelGeneratorConfiguration,
 }
 
+impl HslFixedSaturationPixelGenerator {
+    pub const DOMAIN: PixelGeneratorDomain = PixelGeneratorDomain {
+        fixed: ColorDimension::new("saturation", 0., 1.),
+        default_fixed: 1.,
+        varying: ColorDimension::new("lightness", 0., 1.),
+    };
+
+    pub const REGISTRATION: PixelGeneratorRegistration = PixelGeneratorRegistration {
+        name: "hsl-fixed-saturation",
+        description:
+            "HSL hues around the wheel and lightness from the center, at fixed saturations",
+        domain: Self::DOMAIN,
+        factory: |saturation, configuration| {
+            Box::new(HslFixedSaturationPixelGenerator {
+                saturation,
+                configuration,
+            })
+        },
+    };
+}
+
 impl PixelGenerator for HslFixedSaturationPixelGenerator {
     fn configuration(&self) -> PixelGeneratorConfiguration {
         self.configuration
     }
 
-    fn get_pixel(&self, angle_degrees: f64, varying_dimension_value: f64) -> Pixel {
-        hsl_to_pixel(angle_degrees, self.saturation, varying_dimension_value)
+    fn domain(&self) -> PixelGeneratorDomain {
+        Self::DOMAIN
+    }
+
+    fn fixed(&self) -> f64 {
+        self.saturation
+    }
+
+    fn get_color(&self, angle_degrees: f64, varying_dimension_value: f64) -> Color {
+        hsl_to_color(angle_degrees, self.saturation, varying_dimension_value)
+    }
+
+    fn get_pixels(
+        &self,
+        angles_degrees: &[f64],
+        varying_dimension_values: &[f64],
+        pixels: &mut [Pixel],
+    ) {
+        convert_in_lanes(
+            angles_degrees,
+            varying_dimension_values,
+            pixels,
+            |angles, values| hsl_to_pixels(angles, f64x4::splat(self.saturation), values),
+        );
     }
 }
@@ -1,8 +1,14 @@
-use crate::pixel::Pixel;
+use wide::f64x4;
+
+use crate::{color::Color, pixel::Pixel};
 
 use super::{
-    hsv_to_pixel::hsv_to_pixel, pixel_generator::PixelGenerator,
+    hsv_to_pixel::{hsv_to_color, hsv_to_pixels},
+    pixel_generator::PixelGenerator,
     pixel_generator_configuration::PixelGeneratorConfiguration,
+    pixel_generator_domain::{ColorDimension, PixelGeneratorDomain},
+    pixel_generator_registration::PixelGeneratorRegistration,
+    pixel_lanes::convert_in_lanes,
 };
 
 pub struct HsvFixedValuePixelGenerator {
@@ -10,12 +16,54 @@ pub struct HsvFixedValuePixelGenerator {
     pub configuration: PixelGeneratorConfiguration,
 }
 
+impl HsvFixedValuePixelGenerator {
+    pub const DOMAIN: PixelGeneratorDomain = PixelGeneratorDomain {
+        fixed: ColorDimension::new("value", 0., 1.),
+        default_fixed: 1.,
+        varying: ColorDimension::new("saturation", 0., 1.),
+    };
+
+    pub const REGISTRATION: PixelGeneratorRegistration = PixelGeneratorRegistration {
+        name: "hsv-fixed-value",
+        description: "HSV hues around the wheel and saturation from the center, at fixed values",
+        domain: Self::DOMAIN,
+        factory: |value, configuration| {
+            Box::new(HsvFixedValuePixelGenerator {
+                value,
+                configuration,
+            })
+        },
+    };
+}
+
 impl PixelGenerator for HsvFixedValuePixelGenerator {
     fn configuration(&self) -> PixelGeneratorConfiguration {
         self.configuration
     }
 
-    fn get_pixel(&self, angle_degrees: f64, varying_dimension_value: f64) -> Pixel {
-        hsv_to_pixel(angle_degrees, varying_dimension_value, self.value)
+    fn domain(&self) -> PixelGeneratorDomain {
+        Self::DOMAIN
+    }
+
+    fn fixed(&self) -> f64 {
+        self.value
+    }
+
+    fn get_color(&self, angle_degrees: f64, varying_dimension_value: f64) -> Color {
+        hsv_to_color(angle_degrees, varying_dimension_value, self.value)
+    }
+
+    fn get_pixels(
+        &self,
+        angles_degrees: &[f64],
+        varying_dimension_values: &[f64],
+        pixels: &mut [Pixel],
+    ) {
+        convert_in_lanes(
+            angles_degrees,
+            varying_dimension_values,
+            pixels,
+            |angles, values| hsv_to_pixels(angles, values, f64x4::splat(self.value)),
+        );
     }
 }
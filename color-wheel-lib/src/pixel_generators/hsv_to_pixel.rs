@@ -1,8 +1,13 @@
-use crate::pixel::Pixel;
+use wide::f64x4;
 
-use super::intermediate_cxm_to_pixel::intermediate_cxm_to_pixel;
+use crate::{color::Color, pixel::Pixel};
 
-pub fn hsv_to_pixel(mut hue: f64, mut saturation: f64, mut value: f64) -> Pixel {
+use super::{
+    intermediate_cxm_to_color::{intermediate_cxm_to_color, intermediate_cxm_to_pixels},
+    pixel_lanes::{clamp, remainder_of_two, PIXEL_LANES},
+};
+
+pub fn hsv_to_color(mut hue: f64, mut saturation: f64, mut value: f64) -> Color {
     hue = hue.clamp(0., 360.);
     saturation = saturation.clamp(0., 1.);
     value = value.clamp(0., 1.);
@@ -12,7 +17,26 @@ pub fn hsv_to_pixel(mut hue: f64, mut saturation: f64, mut value: f64) -> Pixel
     let x = c * (1. - ((hue / 60.) % 2. - 1.).abs());
     let m = value - c;
 
-    intermediate_cxm_to_pixel(hue, c, x, m)
+    intermediate_cxm_to_color(hue, c, x, m)
+}
+
+pub fn hsv_to_pixel(hue: f64, saturation: f64, value: f64) -> Pixel {
+    hsv_to_color(hue, saturation, value).to_pixel()
+}
+
+// As `hsv_to_pixel`, for a lane of pixels at once. Each step matches the scalar conversion, so
+// the pixels are exactly the same.
+pub(crate) fn hsv_to_pixels(hue: f64x4, saturation: f64x4, value: f64x4) -> [Pixel; PIXEL_LANES] {
+    let hue = clamp(hue, 0., 360.);
+    let saturation = clamp(saturation, 0., 1.);
+    let value = clamp(value, 0., 1.);
+
+    let one = f64x4::ONE;
+    let c = value * saturation;
+    let x = c * (one - (remainder_of_two(hue / f64x4::splat(60.)) - one).abs());
+    let m = value - c;
+
+    intermediate_cxm_to_pixels(hue, c, x, m)
 }
 
 #[cfg(test)]
@@ -55,4 +79,35 @@ mod tests {
 
         assert_eq!(hsv_to_pixel(123., 0.35, 0.69), Pixel::rgb(114, 176, 117));
     }
+
+    #[test]
+    fn lanes_should_convert_exactly_like_the_scalar_conversion() {
+        let mut inputs = Vec::new();
+        for hue in 0..=1440 {
+            for second in 0..=40 {
+                for third in [0., 0.1, 0.25, 0.5, 0.69, 1.] {
+                    inputs.push((hue as f64 / 4., second as f64 / 40., third));
+                }
+            }
+        }
+        // Outside the domain, and values which scale to halfway between two bytes.
+        inputs.extend([
+            (-10., 0.5, 0.5),
+            (400., 1.5, -0.5),
+            (f64::NAN, 0.5, 0.5),
+            (30., 0.5 / 255., 126.5 / 255.),
+            (90., 1., 0.5 / 255.),
+        ]);
+
+        for inputs in inputs.chunks_exact(PIXEL_LANES) {
+            let lane = |component: fn(&(f64, f64, f64)) -> f64| {
+                f64x4::from([0, 1, 2, 3].map(|index| component(&inputs[index])))
+            };
+            let pixels = hsv_to_pixels(lane(|i| i.0), lane(|i| i.1), lane(|i| i.2));
+
+            for (input, pixel) in inputs.iter().zip(pixels) {
+                assert_eq!(pixel, hsv_to_pixel(input.0, input.1, input.2), "{input:?}");
+            }
+        }
+    }
 }
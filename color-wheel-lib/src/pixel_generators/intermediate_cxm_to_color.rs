@@ -0,0 +1,51 @@
+use wide::{f64x4, CmpLt};
+
+use crate::{color::Color, pixel::Pixel};
+
+use super::pixel_lanes::{pixels_from_normalized, PIXEL_LANES};
+
+pub fn intermediate_cxm_to_color(hue: f64, c: f64, x: f64, m: f64) -> Color {
+    let i = match hue {
+        h if h < 60. => (c, x, 0.),
+        h if h < 120. => (x, c, 0.),
+        h if h < 180. => (0., c, x),
+        h if h < 240. => (0., x, c),
+        h if h < 300. => (x, 0., c),
+        _ => (c, 0., x),
+    };
+
+    let r = i.0 + m;
+    let g = i.1 + m;
+    let b = i.2 + m;
+
+    Color::srgb(r, g, b)
+}
+
+// As `intermediate_cxm_to_color` followed by `to_pixel`, for a lane of pixels at once.
+pub(crate) fn intermediate_cxm_to_pixels(
+    hue: f64x4,
+    c: f64x4,
+    x: f64x4,
+    m: f64x4,
+) -> [Pixel; PIXEL_LANES] {
+    let zero = f64x4::ZERO;
+    let mut i = (c, zero, x);
+
+    // From the last sector down, so each lane ends up with the first sector it's below.
+    for (limit, sector) in [
+        (300., (x, zero, c)),
+        (240., (zero, x, c)),
+        (180., (zero, c, x)),
+        (120., (x, c, zero)),
+        (60., (c, x, zero)),
+    ] {
+        let below = hue.cmp_lt(f64x4::splat(limit));
+        i = (
+            below.blend(sector.0, i.0),
+            below.blend(sector.1, i.1),
+            below.blend(sector.2, i.2),
+        );
+    }
+
+    pixels_from_normalized(i.0 + m, i.1 + m, i.2 + m)
+}
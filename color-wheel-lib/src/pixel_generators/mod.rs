@@ -4,11 +4,16 @@ mod hsl_to_pixel;
 mod hsv_fixed_saturation_pixel_generator;
 mod hsv_fixed_value_pixel_generator;
 mod hsv_to_pixel;
-mod intermediate_cxm_to_pixel;
+mod intermediate_cxm_to_color;
 mod oklab_fixed_chroma;
 mod oklab_fixed_lightness;
+mod oklab_to_pixel;
 mod pixel_generator;
 mod pixel_generator_configuration;
+mod pixel_generator_domain;
+mod pixel_generator_registration;
+mod pixel_generator_registry;
+mod pixel_lanes;
 
 pub use hsl_fixed_lightness_pixel_generator::HslFixedLightnessPixelGenerator;
 pub use hsl_fixed_saturation_pixel_generator::HslFixedSaturationPixelGenerator;
@@ -17,8 +22,15 @@ pub use hsv_fixed_value_pixel_generator::HsvFixedValuePixelGenerator;
 pub use oklab_fixed_chroma::OklabFixedChromaPixelGenerator;
 pub use oklab_fixed_lightness::OklabFixedLightnessPixelGenerator;
 
+pub use hsl_to_pixel::{hsl_to_color, hsl_to_pixel};
+pub use hsv_to_pixel::{hsv_to_color, hsv_to_pixel};
+pub use oklab_to_pixel::oklab_to_pixel;
+
 pub use pixel_generator::PixelGenerator;
 pub use pixel_generator_configuration::PixelGeneratorConfiguration;
+pub use pixel_generator_domain::{ColorDimension, PixelGeneratorDomain};
+pub use pixel_generator_registration::{PixelGeneratorFactory, PixelGeneratorRegistration};
+pub use pixel_generator_registry::{PixelGeneratorRegistry, BUILT_IN_PIXEL_GENERATORS};
 
 #[cfg(test)]
 pub(crate) use pixel_generator::MockPixelGenerator;
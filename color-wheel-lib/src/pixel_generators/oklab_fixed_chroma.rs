@@ -1,9 +1,10 @@
-use oklab::{oklab_to_srgb, Oklab};
-
-use crate::pixel::Pixel;
+use crate::color::Color;
 
 use super::{
-    pixel_generator::PixelGenerator, pixel_generator_configuration::PixelGeneratorConfiguration,
+    pixel_generator::PixelGenerator,
+    pixel_generator_configuration::PixelGeneratorConfiguration,
+    pixel_generator_domain::{ColorDimension, PixelGeneratorDomain},
+    pixel_generator_registration::PixelGeneratorRegistration,
 };
 
 pub struct OklabFixedChromaPixelGenerator {
@@ -11,22 +12,48 @@ pub struct OklabFixedChromaPixelGenerator {
     pub configuration: PixelGeneratorConfiguration,
 }
 
+impl OklabFixedChromaPixelGenerator {
+    pub const DOMAIN: PixelGeneratorDomain = PixelGeneratorDomain {
+        fixed: ColorDimension::new("chroma", 0., 0.5),
+        default_fixed: 0.15,
+        varying: ColorDimension::new("lightness", 0., 1.),
+    };
+
+    pub const REGISTRATION: PixelGeneratorRegistration = PixelGeneratorRegistration {
+        name: "oklab-fixed-chroma",
+        description: "OKLab hues around the wheel and lightness from the center, at fixed chromas",
+        domain: Self::DOMAIN,
+        factory: |chroma, configuration| {
+            Box::new(OklabFixedChromaPixelGenerator {
+                chroma,
+                configuration,
+            })
+        },
+    };
+}
+
 impl PixelGenerator for OklabFixedChromaPixelGenerator {
     fn configuration(&self) -> PixelGeneratorConfiguration {
         self.configuration
     }
 
-    fn get_pixel(&self, angle_degrees: f64, varying_dimension_value: f64) -> Pixel {
-        let angle_rad = angle_degrees.to_radians();
+    fn domain(&self) -> PixelGeneratorDomain {
+        Self::DOMAIN
+    }
 
-        let c = self.chroma;
+    fn fixed(&self) -> f64 {
+        self.chroma
+    }
 
-        let l = varying_dimension_value as f32;
-        let a = (c * angle_rad.cos()) as f32;
-        let b = (c * angle_rad.sin()) as f32;
+    fn get_color(&self, angle_degrees: f64, varying_dimension_value: f64) -> Color {
+        let angle_rad = angle_degrees.to_radians();
 
-        let srgb = oklab_to_srgb(Oklab { l, a, b });
+        let c = self.chroma;
 
-        Pixel::rgb(srgb.r, srgb.g, srgb.b)
+        Color::oklab(
+            varying_dimension_value,
+            c * angle_rad.cos(),
+            c * angle_rad.sin(),
+        )
     }
 }
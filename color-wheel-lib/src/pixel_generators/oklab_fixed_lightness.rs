@@ -1,9 +1,10 @@
-use oklab::{oklab_to_srgb, Oklab};
-
-use crate::pixel::Pixel;
+use crate::color::Color;
 
 use super::{
-    pixel_generator::PixelGenerator, pixel_generator_configuration::PixelGeneratorConfiguration,
+    pixel_generator::PixelGenerator,
+    pixel_generator_configuration::PixelGeneratorConfiguration,
+    pixel_generator_domain::{ColorDimension, PixelGeneratorDomain},
+    pixel_generator_registration::PixelGeneratorRegistration,
 };
 
 pub struct OklabFixedLightnessPixelGenerator {
@@ -11,22 +12,44 @@ pub struct OklabFixedLightnessPixelGenerator {
     pub configuration: PixelGeneratorConfiguration,
 }
 
+impl OklabFixedLightnessPixelGenerator {
+    pub const DOMAIN: PixelGeneratorDomain = PixelGeneratorDomain {
+        fixed: ColorDimension::new("lightness", 0., 1.),
+        default_fixed: 1.,
+        varying: ColorDimension::new("chroma", 0., 0.5),
+    };
+
+    pub const REGISTRATION: PixelGeneratorRegistration = PixelGeneratorRegistration {
+        name: "oklab-fixed-lightness",
+        description: "OKLab hues around the wheel and chroma from the center, at fixed lightnesses",
+        domain: Self::DOMAIN,
+        factory: |lightness, configuration| {
+            Box::new(OklabFixedLightnessPixelGenerator {
+                lightness,
+                configuration,
+            })
+        },
+    };
+}
+
 impl PixelGenerator for OklabFixedLightnessPixelGenerator {
     fn configuration(&self) -> PixelGeneratorConfiguration {
         self.configuration
     }
 
-    fn get_pixel(&self, angle_degrees: f64, varying_dimension_value: f64) -> Pixel {
-        let angle_rad = angle_degrees.to_radians();
+    fn domain(&self) -> PixelGeneratorDomain {
+        Self::DOMAIN
+    }
 
-        let c = varying_dimension_value / 2.;
+    fn fixed(&self) -> f64 {
+        self.lightness
+    }
 
-        let l = self.lightness as f32;
-        let a = (c * angle_rad.cos()) as f32;
-        let b = (c * angle_rad.sin()) as f32;
+    fn get_color(&self, angle_degrees: f64, varying_dimension_value: f64) -> Color {
+        let angle_rad = angle_degrees.to_radians();
 
-        let srgb = oklab_to_srgb(Oklab { l, a, b });
+        let c = varying_dimension_value / 2.;
 
-        Pixel::rgb(srgb.r, srgb.g, srgb.b)
+        Color::oklab(self.lightness, c * angle_rad.cos(), c * angle_rad.sin())
     }
 }
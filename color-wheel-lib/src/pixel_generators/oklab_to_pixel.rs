@@ -0,0 +1,20 @@
+use crate::{color::Color, pixel::Pixel};
+
+pub fn oklab_to_pixel(lightness: f64, a: f64, b: f64) -> Pixel {
+    Color::oklab(lightness, a, b).to_pixel()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn it_should_convert_white() {
+        assert_eq!(oklab_to_pixel(1., 0., 0.), Pixel::rgb(255, 255, 255));
+    }
+
+    #[test]
+    fn it_should_convert_black() {
+        assert_eq!(oklab_to_pixel(0., 0., 0.), Pixel::rgb(0, 0, 0));
+    }
+}
@@ -1,10 +1,73 @@
-use crate::pixel::Pixel;
+use crate::{color::Color, pixel::Pixel};
 
-use super::pixel_generator_configuration::PixelGeneratorConfiguration;
+use super::{
+    pixel_generator_configuration::PixelGeneratorConfiguration,
+    pixel_generator_domain::PixelGeneratorDomain,
+};
 
 #[cfg_attr(test, mockall::automock)]
 pub trait PixelGenerator: Sync {
     fn configuration(&self) -> PixelGeneratorConfiguration;
 
-    fn get_pixel(&self, angle_degrees: f64, varying_dimension_value: f64) -> Pixel;
+    fn domain(&self) -> PixelGeneratorDomain;
+
+    // The value of the dimension held fixed, within `domain().fixed`.
+    fn fixed(&self) -> f64;
+
+    // The color at full precision, for anything which needs more than 8 bits per channel.
+    fn get_color(&self, angle_degrees: f64, varying_dimension_value: f64) -> Color;
+
+    fn get_pixel(&self, angle_degrees: f64, varying_dimension_value: f64) -> Pixel {
+        self.get_color(angle_degrees, varying_dimension_value)
+            .to_pixel()
+    }
+
+    // Gets the pixel for each pair of angle and varying dimension value, for rendering a row at
+    // once. Generators override this where they can convert several pixels at a time.
+    fn get_pixels(
+        &self,
+        angles_degrees: &[f64],
+        varying_dimension_values: &[f64],
+        pixels: &mut [Pixel],
+    ) {
+        for ((angle_degrees, varying_dimension_value), pixel) in angles_degrees
+            .iter()
+            .zip(varying_dimension_values)
+            .zip(pixels)
+        {
+            *pixel = self.get_pixel(*angle_degrees, *varying_dimension_value);
+        }
+    }
+}
+
+// Lets pixel generators chosen at runtime, such as from a registry, render like any other.
+impl<T: PixelGenerator + ?Sized> PixelGenerator for Box<T> {
+    fn configuration(&self) -> PixelGeneratorConfiguration {
+        (**self).configuration()
+    }
+
+    fn domain(&self) -> PixelGeneratorDomain {
+        (**self).domain()
+    }
+
+    fn fixed(&self) -> f64 {
+        (**self).fixed()
+    }
+
+    fn get_color(&self, angle_degrees: f64, varying_dimension_value: f64) -> Color {
+        (**self).get_color(angle_degrees, varying_dimension_value)
+    }
+
+    fn get_pixel(&self, angle_degrees: f64, varying_dimension_value: f64) -> Pixel {
+        (**self).get_pixel(angle_degrees, varying_dimension_value)
+    }
+
+    fn get_pixels(
+        &self,
+        angles_degrees: &[f64],
+        varying_dimension_values: &[f64],
+        pixels: &mut [Pixel],
+    ) {
+        (**self).get_pixels(angles_degrees, varying_dimension_values, pixels)
+    }
 }
@@ -1,9 +1,10 @@
-use crate::bucket::BucketDirection;
+use crate::bucket::{BucketDirection, BucketSpacing};
 
 #[derive(Copy, Clone, Eq, PartialEq, Debug)]
 pub struct PixelGeneratorConfiguration {
     pub is_angle_inverted: bool,
     pub is_varying_dimension_inverted: bool,
     pub angle_bucket_direction: BucketDirection,
+    pub angle_bucket_spacing: BucketSpacing,
     pub varying_dimension_bucket_direction: BucketDirection,
 }
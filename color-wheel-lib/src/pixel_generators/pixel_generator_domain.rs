@@ -0,0 +1,79 @@
+use crate::color_wheel_error::ColorWheelError;
+
+// A color model dimension, such as saturation, and the range of values it can take.
+#[derive(Copy, Clone, PartialEq, Debug)]
+pub struct ColorDimension {
+    pub name: &'static str,
+    pub minimum: f64,
+    pub maximum: f64,
+}
+
+impl ColorDimension {
+    pub const fn new(name: &'static str, minimum: f64, maximum: f64) -> Self {
+        ColorDimension {
+            name,
+            minimum,
+            maximum,
+        }
+    }
+
+    pub fn contains(&self, value: f64) -> bool {
+        (self.minimum..=self.maximum).contains(&value)
+    }
+}
+
+// Describes the dimension a pixel generator holds fixed and the dimension it varies from the
+// center of the wheel to the edge.
+#[derive(Copy, Clone, PartialEq, Debug)]
+pub struct PixelGeneratorDomain {
+    pub fixed: ColorDimension,
+    pub default_fixed: f64,
+    pub varying: ColorDimension,
+}
+
+impl PixelGeneratorDomain {
+    pub fn validate_fixed(&self, value: f64) -> Result<(), ColorWheelError> {
+        if !self.fixed.contains(value) {
+            return Err(ColorWheelError::FixedValueOutOfRange {
+                name: self.fixed.name,
+                value,
+                minimum: self.fixed.minimum,
+                maximum: self.fixed.maximum,
+            });
+        }
+
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const DOMAIN: PixelGeneratorDomain = PixelGeneratorDomain {
+        fixed: ColorDimension::new("chroma", 0., 0.5),
+        default_fixed: 0.15,
+        varying: ColorDimension::new("lightness", 0., 1.),
+    };
+
+    #[test]
+    fn it_should_accept_fixed_values_in_range() {
+        assert_eq!(DOMAIN.validate_fixed(0.), Ok(()));
+        assert_eq!(DOMAIN.validate_fixed(0.5), Ok(()));
+    }
+
+    #[test]
+    fn it_should_reject_fixed_values_out_of_range() {
+        assert_eq!(
+            DOMAIN.validate_fixed(5.),
+            Err(ColorWheelError::FixedValueOutOfRange {
+                name: "chroma",
+                value: 5.,
+                minimum: 0.,
+                maximum: 0.5
+            })
+        );
+        assert!(DOMAIN.validate_fixed(-0.1).is_err());
+        assert!(DOMAIN.validate_fixed(f64::NAN).is_err());
+    }
+}
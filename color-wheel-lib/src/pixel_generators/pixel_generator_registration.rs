@@ -0,0 +1,28 @@
+use super::{
+    pixel_generator::PixelGenerator, pixel_generator_configuration::PixelGeneratorConfiguration,
+    pixel_generator_domain::PixelGeneratorDomain,
+};
+
+// Creates a pixel generator which holds its dimension at the given fixed value.
+pub type PixelGeneratorFactory = fn(f64, PixelGeneratorConfiguration) -> Box<dyn PixelGenerator>;
+
+// Describes a kind of pixel generator, so that applications can list, validate and create
+// pixel generators by name.
+#[derive(Copy, Clone, Debug)]
+pub struct PixelGeneratorRegistration {
+    // The name used to select the pixel generator, such as `hsl-fixed-lightness`.
+    pub name: &'static str,
+    pub description: &'static str,
+    pub domain: PixelGeneratorDomain,
+    pub factory: PixelGeneratorFactory,
+}
+
+impl PixelGeneratorRegistration {
+    pub fn create(
+        &self,
+        fixed: f64,
+        configuration: PixelGeneratorConfiguration,
+    ) -> Box<dyn PixelGenerator> {
+        (self.factory)(fixed, configuration)
+    }
+}
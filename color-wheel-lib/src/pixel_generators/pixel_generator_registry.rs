@@ -0,0 +1,194 @@
+use super::{
+    hsl_fixed_lightness_pixel_generator::HslFixedLightnessPixelGenerator,
+    hsl_fixed_saturation_pixel_generator::HslFixedSaturationPixelGenerator,
+    hsv_fixed_saturation_pixel_generator::HsvFixedSaturationPixelGenerator,
+    hsv_fixed_value_pixel_generator::HsvFixedValuePixelGenerator,
+    oklab_fixed_chroma::OklabFixedChromaPixelGenerator,
+    oklab_fixed_lightness::OklabFixedLightnessPixelGenerator,
+    pixel_generator_registration::PixelGeneratorRegistration,
+};
+
+pub const BUILT_IN_PIXEL_GENERATORS: [PixelGeneratorRegistration; 6] = [
+    HslFixedSaturationPixelGenerator::REGISTRATION,
+    HslFixedLightnessPixelGenerator::REGISTRATION,
+    HsvFixedSaturationPixelGenerator::REGISTRATION,
+    HsvFixedValuePixelGenerator::REGISTRATION,
+    OklabFixedLightnessPixelGenerator::REGISTRATION,
+    OklabFixedChromaPixelGenerator::REGISTRATION,
+];
+
+// The pixel generators which can be selected by name, in the order they were registered.
+// The default registry holds the built in pixel generators.
+#[derive(Clone, Debug)]
+pub struct PixelGeneratorRegistry {
+    registrations: Vec<PixelGeneratorRegistration>,
+}
+
+impl PixelGeneratorRegistry {
+    pub fn empty() -> Self {
+        PixelGeneratorRegistry {
+            registrations: vec![],
+        }
+    }
+
+    // Adds a pixel generator, replacing any already registered with the same name.
+    pub fn register(&mut self, registration: PixelGeneratorRegistration) {
+        match self
+            .registrations
+            .iter_mut()
+            .find(|existing| existing.name == registration.name)
+        {
+            Some(existing) => *existing = registration,
+            None => self.registrations.push(registration),
+        }
+    }
+
+    pub fn get(&self, name: &str) -> Option<&PixelGeneratorRegistration> {
+        self.registrations
+            .iter()
+            .find(|registration| registration.name == name)
+    }
+
+    pub fn registrations(&self) -> &[PixelGeneratorRegistration] {
+        &self.registrations
+    }
+}
+
+impl Default for PixelGeneratorRegistry {
+    fn default() -> Self {
+        PixelGeneratorRegistry {
+            registrations: BUILT_IN_PIXEL_GENERATORS.to_vec(),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{
+        bucket::{BucketDirection, BucketSpacing},
+        color::Color,
+        pixel::Pixel,
+        pixel_generators::{
+            ColorDimension, PixelGenerator, PixelGeneratorConfiguration, PixelGeneratorDomain,
+        },
+    };
+
+    use super::*;
+
+    struct GrayPixelGenerator {
+        lightness: f64,
+        configuration: PixelGeneratorConfiguration,
+    }
+
+    const GRAY_DOMAIN: PixelGeneratorDomain = PixelGeneratorDomain {
+        fixed: ColorDimension::new("lightness", 0., 1.),
+        default_fixed: 0.5,
+        varying: ColorDimension::new("alpha", 0., 1.),
+    };
+
+    impl PixelGenerator for GrayPixelGenerator {
+        fn configuration(&self) -> PixelGeneratorConfiguration {
+            self.configuration
+        }
+
+        fn domain(&self) -> PixelGeneratorDomain {
+            GRAY_DOMAIN
+        }
+
+        fn fixed(&self) -> f64 {
+            self.lightness
+        }
+
+        fn get_color(&self, _angle_degrees: f64, varying_dimension_value: f64) -> Color {
+            Color::srgb(self.lightness, self.lightness, self.lightness)
+                .with_alpha(varying_dimension_value)
+        }
+    }
+
+    fn gray_registration(name: &'static str) -> PixelGeneratorRegistration {
+        PixelGeneratorRegistration {
+            name,
+            description: "Grays with alpha from the center",
+            domain: GRAY_DOMAIN,
+            factory: |lightness, configuration| {
+                Box::new(GrayPixelGenerator {
+                    lightness,
+                    configuration,
+                })
+            },
+        }
+    }
+
+    fn configuration() -> PixelGeneratorConfiguration {
+        PixelGeneratorConfiguration {
+            is_angle_inverted: false,
+            is_varying_dimension_inverted: false,
+            angle_bucket_direction: BucketDirection::Down,
+            angle_bucket_spacing: BucketSpacing::Uniform,
+            varying_dimension_bucket_direction: BucketDirection::Up,
+        }
+    }
+
+    #[test]
+    fn it_should_hold_the_built_in_pixel_generators_by_default() {
+        let registry = PixelGeneratorRegistry::default();
+        let names = registry
+            .registrations()
+            .iter()
+            .map(|registration| registration.name)
+            .collect::<Vec<_>>();
+
+        assert_eq!(
+            names,
+            vec![
+                "hsl-fixed-saturation",
+                "hsl-fixed-lightness",
+                "hsv-fixed-saturation",
+                "hsv-fixed-value",
+                "oklab-fixed-lightness",
+                "oklab-fixed-chroma"
+            ]
+        );
+    }
+
+    #[test]
+    fn it_should_create_pixel_generators_by_name() {
+        let registry = PixelGeneratorRegistry::default();
+        let registration = registry.get("hsl-fixed-lightness").unwrap();
+        let pixel_generator = registration.create(0.25, configuration());
+
+        assert_eq!(
+            pixel_generator.domain(),
+            HslFixedLightnessPixelGenerator::DOMAIN
+        );
+        assert_eq!(pixel_generator.fixed(), 0.25);
+        assert_eq!(pixel_generator.configuration(), configuration());
+        assert!(registry.get("rgb").is_none());
+    }
+
+    #[test]
+    fn it_should_register_other_pixel_generators() {
+        let mut registry = PixelGeneratorRegistry::default();
+        registry.register(gray_registration("gray"));
+
+        let pixel_generator = registry.get("gray").unwrap().create(1., configuration());
+        assert_eq!(registry.registrations().len(), 7);
+        assert_eq!(
+            pixel_generator.get_pixel(0., 1.),
+            Pixel::rgba(255, 255, 255, 255)
+        );
+    }
+
+    #[test]
+    fn it_should_replace_pixel_generators_with_the_same_name() {
+        let mut registry = PixelGeneratorRegistry::empty();
+        registry.register(gray_registration("gray"));
+        registry.register(PixelGeneratorRegistration {
+            description: "Replacement",
+            ..gray_registration("gray")
+        });
+
+        assert_eq!(registry.registrations().len(), 1);
+        assert_eq!(registry.get("gray").unwrap().description, "Replacement");
+    }
+}
@@ -0,0 +1,160 @@
+use wide::{f64x4, CmpGe, CmpGt, CmpLt};
+
+use crate::pixel::{Pixel, OPAQUE};
+
+// How many pixels the vectorized color conversions convert at once.
+pub const PIXEL_LANES: usize = 4;
+
+// Converts pixels a lane at a time. The last lanes are padded by repeating the last pixel.
+pub(crate) fn convert_in_lanes(
+    angles_degrees: &[f64],
+    varying_dimension_values: &[f64],
+    pixels: &mut [Pixel],
+    convert: impl Fn(f64x4, f64x4) -> [Pixel; PIXEL_LANES],
+) {
+    let lane = |values: &[f64]| {
+        let mut lane = [values[values.len() - 1]; PIXEL_LANES];
+        lane[..values.len()].copy_from_slice(values);
+        f64x4::from(lane)
+    };
+
+    for ((angles, values), pixels) in angles_degrees
+        .chunks(PIXEL_LANES)
+        .zip(varying_dimension_values.chunks(PIXEL_LANES))
+        .zip(pixels.chunks_mut(PIXEL_LANES))
+    {
+        let converted = convert(lane(angles), lane(values));
+        pixels.copy_from_slice(&converted[..pixels.len()]);
+    }
+}
+
+// Converts normalized red, green and blue to opaque pixels, rounding exactly as
+// `Pixel::from_normalized` does.
+pub(crate) fn pixels_from_normalized(
+    red: f64x4,
+    green: f64x4,
+    blue: f64x4,
+) -> [Pixel; PIXEL_LANES] {
+    let [red, green, blue] = [red, green, blue].map(denormalize);
+
+    [0, 1, 2, 3].map(|index| Pixel {
+        data: [red[index], green[index], blue[index], OPAQUE],
+    })
+}
+
+// Rounds halves away from zero as `f64::round` does, as the SIMD rounding rounds them to even.
+// Converting each lane with `as` then saturates and maps NaN to zero in the same way.
+fn denormalize(value: f64x4) -> [u8; PIXEL_LANES] {
+    let scaled = value * f64x4::splat(255.);
+    let floor = floor(scaled);
+    let rounded = (scaled - floor)
+        .cmp_ge(f64x4::splat(0.5))
+        .blend(floor + f64x4::ONE, floor);
+
+    rounded.to_array().map(|value| value as u8)
+}
+
+// `value % 2.` for values of at least zero, which wide doesn't provide. Each step is exact, so
+// this gives exactly the same result.
+pub(crate) fn remainder_of_two(value: f64x4) -> f64x4 {
+    value - floor(value * f64x4::splat(0.5)) * f64x4::splat(2.)
+}
+
+// As `f64::floor` for each lane. Without SSE4.1, wide's `floor` calls the scalar one for each
+// lane, so instead this rounds with the 2^52 trick, which is exact, then corrects values which
+// rounded up. Values of at least 2^52 are already whole, and NaN and infinity are kept.
+pub(crate) fn floor(value: f64x4) -> f64x4 {
+    let two_to_the_52 = f64x4::splat(4503599627370496.);
+    let absolute = value.abs();
+    let rounded = ((absolute + two_to_the_52) - two_to_the_52).copysign(value);
+    let floor = rounded.cmp_gt(value).blend(rounded - f64x4::ONE, rounded);
+
+    absolute.cmp_lt(two_to_the_52).blend(floor, value)
+}
+
+// As `f64::clamp`, which keeps NaN, unlike wide's `max` and `min`.
+pub(crate) fn clamp(value: f64x4, min: f64, max: f64) -> f64x4 {
+    let (min, max) = (f64x4::splat(min), f64x4::splat(max));
+    let value = value.cmp_lt(min).blend(min, value);
+    value.cmp_gt(max).blend(max, value)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn it_should_floor_exactly_like_the_scalar_floor() {
+        let mut values = (-2000..=2000)
+            .map(|value| value as f64 * 0.25)
+            .collect::<Vec<_>>();
+        values.extend([
+            -0.,
+            0.49999999999999994,
+            -0.49999999999999994,
+            4503599627370495.5,
+            -4503599627370495.5,
+            4503599627370497.,
+            1e300,
+            -1e-300,
+            f64::INFINITY,
+            f64::NEG_INFINITY,
+            f64::NAN,
+            f64::MIN_POSITIVE,
+        ]);
+
+        for values in values.chunks(PIXEL_LANES) {
+            let mut lane = [0.; PIXEL_LANES];
+            lane[..values.len()].copy_from_slice(values);
+            let floors = floor(f64x4::from(lane)).to_array();
+
+            for (value, floor) in lane.into_iter().zip(floors) {
+                assert_eq!(floor.to_bits(), value.floor().to_bits(), "{value}");
+            }
+        }
+    }
+
+    #[test]
+    fn it_should_round_like_the_scalar_conversion() {
+        let values = [0., 0.5 / 255., 1.5 / 255., 126.5 / 255., 0.2, 1., 1.1, -0.3];
+        for values in values.chunks(PIXEL_LANES) {
+            let lane = f64x4::from(<[f64; 4]>::try_from(values).unwrap());
+            let pixels = pixels_from_normalized(lane, lane, lane);
+
+            for (value, pixel) in values.iter().zip(pixels) {
+                assert_eq!(pixel, Pixel::from_normalized(*value, *value, *value));
+            }
+        }
+    }
+
+    #[test]
+    fn it_should_take_remainders_like_the_scalar_operator() {
+        for index in 0..=600 {
+            let value = index as f64 / 100.;
+            let lane = f64x4::splat(value);
+            assert_eq!(remainder_of_two(lane).to_array()[0], value % 2.);
+        }
+    }
+
+    #[test]
+    fn it_should_clamp_like_the_scalar_method() {
+        let values = [-1., 0.5, 2., f64::NAN];
+        let clamped = clamp(f64x4::from(values), 0., 1.).to_array();
+
+        assert_eq!(&clamped[..3], &[0., 0.5, 1.]);
+        assert!(clamped[3].is_nan());
+    }
+
+    #[test]
+    fn it_should_convert_partial_lanes() {
+        let angles = [1., 2., 3., 4., 5., 6.];
+        let values = [0.; 6];
+        let mut pixels = [Pixel::transparent(); 6];
+
+        convert_in_lanes(&angles, &values, &mut pixels, |angles, _| {
+            angles.to_array().map(|angle| Pixel::rgb(angle as u8, 0, 0))
+        });
+
+        assert_eq!(pixels.map(|pixel| pixel.red()), [1, 2, 3, 4, 5, 6]);
+    }
+}
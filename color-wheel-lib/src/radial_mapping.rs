@@ -0,0 +1,97 @@
+use std::str::FromStr;
+
+// How the distance from the center of a ring maps to the pixel generator's variable dimension.
+#[derive(Debug, PartialEq, Clone, Copy, Default)]
+pub enum RadialMapping {
+    #[default]
+    Linear,
+
+    // Gives each value of the variable dimension an equal area of the wheel, which stops
+    // the colors near the center being squeezed into a small area.
+    Sqrt,
+
+    // Raises the distance to the given power.
+    Gamma(f64),
+
+    // Gives equal OKLab distances between colors per pixel. This depends on the pixel
+    // generator, so is mapped using a `PerceptualScale`.
+    Perceptual,
+}
+
+impl RadialMapping {
+    // Maps a proportion of the distance through a ring to the variable dimension.
+    // `Perceptual` is returned unchanged.
+    pub fn map(&self, distance: f64) -> f64 {
+        match self {
+            RadialMapping::Linear | RadialMapping::Perceptual => distance,
+            RadialMapping::Sqrt => distance.powi(2),
+            RadialMapping::Gamma(gamma) => distance.powf(*gamma),
+        }
+    }
+}
+
+// Parses `linear`, `sqrt`, `gamma:N` or `perceptual`.
+impl FromStr for RadialMapping {
+    type Err = String;
+
+    fn from_str(value: &str) -> Result<Self, Self::Err> {
+        match value.split_once(':') {
+            None => match value {
+                "linear" => Ok(RadialMapping::Linear),
+                "sqrt" => Ok(RadialMapping::Sqrt),
+                "perceptual" => Ok(RadialMapping::Perceptual),
+                _ => Err(format!(
+                    "`{value}` isn't one of linear, sqrt, gamma:N or perceptual"
+                )),
+            },
+            Some(("gamma", gamma)) => match gamma.parse::<f64>() {
+                Ok(gamma) if gamma > 0. && gamma.is_finite() => Ok(RadialMapping::Gamma(gamma)),
+                _ => Err(format!("`{gamma}` isn't a positive number")),
+            },
+            Some(_) => Err(format!(
+                "`{value}` isn't one of linear, sqrt, gamma:N or perceptual"
+            )),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use float_cmp::assert_approx_eq;
+
+    use super::*;
+
+    #[test]
+    fn linear_should_not_change_distance() {
+        assert_approx_eq!(f64, RadialMapping::Linear.map(0.3), 0.3);
+    }
+
+    #[test]
+    fn sqrt_should_give_equal_areas() {
+        // Half of the wheel's area is within 1/sqrt(2) of its radius.
+        assert_approx_eq!(f64, RadialMapping::Sqrt.map(0.5_f64.sqrt()), 0.5);
+        assert_approx_eq!(f64, RadialMapping::Sqrt.map(1.), 1.);
+    }
+
+    #[test]
+    fn gamma_should_raise_to_power() {
+        assert_approx_eq!(f64, RadialMapping::Gamma(3.).map(0.5), 0.125);
+        assert_approx_eq!(f64, RadialMapping::Gamma(0.5).map(0.25), 0.5);
+    }
+
+    #[test]
+    fn it_should_parse_mappings() {
+        assert_eq!("linear".parse(), Ok(RadialMapping::Linear));
+        assert_eq!("sqrt".parse(), Ok(RadialMapping::Sqrt));
+        assert_eq!("perceptual".parse(), Ok(RadialMapping::Perceptual));
+        assert_eq!("gamma:2.2".parse(), Ok(RadialMapping::Gamma(2.2)));
+    }
+
+    #[test]
+    fn it_should_reject_invalid_mappings() {
+        assert!("cubic".parse::<RadialMapping>().is_err());
+        assert!("gamma".parse::<RadialMapping>().is_err());
+        assert!("gamma:0".parse::<RadialMapping>().is_err());
+        assert!("sqrt:2".parse::<RadialMapping>().is_err());
+    }
+}
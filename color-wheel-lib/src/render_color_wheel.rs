@@ -1,9 +1,7 @@
-use std::cmp::min;
-
 use crate::{
     canvas_pixel_writer::CanvasPixelWriter, color_wheel_definition::ColorWheelDefinition,
-    pixel_generators::PixelGenerator, render_color_wheel_rows::RenderColorWheelRows,
-    render_pixel::RenderPixelData,
+    color_wheel_error::ColorWheelError, pixel_generators::PixelGenerator,
+    render_color_wheel_rows::RenderColorWheelRows, render_pixel::RenderPixelData,
 };
 
 // We're putting the `PixelWriter` as a generic parameter on the `RenderColorWheel` trait
@@ -16,7 +14,7 @@ where
         &self,
         definition: &ColorWheelDefinition<TPixelGenerator>,
         canvas_pixel_writer: &mut TCanvasPixelWriter,
-    );
+    ) -> Result<(), ColorWheelError>;
 }
 
 pub struct DefaultRenderColorWheel<TRenderColorWheelRows: RenderColorWheelRows>
@@ -36,40 +34,20 @@ where
         &self,
         definition: &ColorWheelDefinition<TPixelGenerator>,
         canvas_pixel_writer: &mut TCanvasPixelWriter,
-    ) {
+    ) -> Result<(), ColorWheelError> {
         if definition.pixel_generators.is_empty() {
-            return;
-        }
-
-        let image_width = definition.image_size;
-        let image_height = definition.image_size;
-
-        let wheel_diameter = min(image_width, image_height) - (definition.margin_size * 2);
-
-        let center_x = image_width / 2;
-        let center_y = image_height / 2;
-
-        let all_generators_size = wheel_diameter as f64 / 2.;
-
-        let generator_size = all_generators_size / definition.pixel_generators.len() as f64;
-        if generator_size < 1. {
-            panic!("Image is too small.");
+            return Ok(());
         }
 
-        let data = RenderPixelData {
-            center_x,
-            center_y,
-            all_generators_size,
-            generator_size,
-        };
+        let data = RenderPixelData::new(definition)?;
 
         self.render_color_wheel_rows.execute(
-            image_width,
-            image_height,
+            definition.image_size,
+            definition.image_size,
             &data,
             definition,
             canvas_pixel_writer,
-        );
+        )
     }
 }
 
@@ -79,7 +57,15 @@ mod tests {
 
     use float_cmp::assert_approx_eq;
 
-    use crate::{pixel_generators::MockPixelGenerator, row_pixel_writer::MockRowPixelWriter};
+    use crate::{
+        bucket::{BucketDirection, BucketSpacing},
+        color_wheel_shape::ColorWheelShape,
+        pixel_generators::{
+            ColorDimension, MockPixelGenerator, PixelGeneratorConfiguration, PixelGeneratorDomain,
+        },
+        ring_options::RingOptions,
+        row_pixel_writer::MockRowPixelWriter,
+    };
 
     use super::*;
 
@@ -96,14 +82,21 @@ mod tests {
             margin_size: 2,
             angle_buckets: 4,
             distance_buckets: 5,
+            angle_bucket_layout: Default::default(),
+            distance_bucket_layout: Default::default(),
+            shape: Default::default(),
+            radial_mapping: Default::default(),
             pixel_generators: vec![],
+            rings: vec![],
         };
 
         let renderer = DefaultRenderColorWheel {
             render_color_wheel_rows: render_color_wheel_rows.clone(),
         };
 
-        renderer.execute(&definition, &mut canvas_pixel_writer);
+        renderer
+            .execute(&definition, &mut canvas_pixel_writer)
+            .unwrap();
 
         let calls = render_color_wheel_rows.calls.borrow();
         assert_eq!(calls.len(), 0);
@@ -122,14 +115,21 @@ mod tests {
             margin_size: 2,
             angle_buckets: 4,
             distance_buckets: 5,
-            pixel_generators: vec![MockPixelGenerator::new(), MockPixelGenerator::new()],
+            angle_bucket_layout: Default::default(),
+            distance_bucket_layout: Default::default(),
+            shape: Default::default(),
+            radial_mapping: Default::default(),
+            pixel_generators: vec![pixel_generator(), pixel_generator()],
+            rings: vec![],
         };
 
         let renderer = DefaultRenderColorWheel {
             render_color_wheel_rows: render_color_wheel_rows.clone(),
         };
 
-        renderer.execute(&definition, &mut canvas_pixel_writer);
+        renderer
+            .execute(&definition, &mut canvas_pixel_writer)
+            .unwrap();
 
         let calls = render_color_wheel_rows.calls.borrow();
         assert_eq!(calls.len(), 1);
@@ -141,7 +141,217 @@ mod tests {
         assert_eq!(call.data.center_x, 7);
         assert_eq!(call.data.center_y, 7);
         assert_approx_eq!(f64, call.data.all_generators_size, 5.5);
-        assert_approx_eq!(f64, call.data.generator_size, 2.75);
+        assert_eq!(call.data.generator_sizes, vec![2.75, 2.75]);
+    }
+
+    #[test]
+    fn it_should_leave_room_for_inner_radius_and_ring_gaps() {
+        let mut canvas_pixel_writer = MockCanvasPixelWriter {};
+
+        let render_color_wheel_rows = Rc::new(MockRenderColorWheelRows {
+            calls: RefCell::new(vec![]),
+        });
+
+        let definition = ColorWheelDefinition {
+            image_size: 104,
+            margin_size: 2,
+            angle_buckets: 4,
+            distance_buckets: 5,
+            angle_bucket_layout: Default::default(),
+            distance_bucket_layout: Default::default(),
+            shape: ColorWheelShape {
+                inner_radius: 0.2,
+                ring_gap: 4,
+                ..Default::default()
+            },
+            radial_mapping: Default::default(),
+            pixel_generators: vec![pixel_generator(), pixel_generator()],
+            rings: vec![],
+        };
+
+        let renderer = DefaultRenderColorWheel {
+            render_color_wheel_rows: render_color_wheel_rows.clone(),
+        };
+
+        renderer
+            .execute(&definition, &mut canvas_pixel_writer)
+            .unwrap();
+
+        let calls = render_color_wheel_rows.calls.borrow();
+        let call = &calls[0];
+
+        assert_approx_eq!(f64, call.data.all_generators_size, 50.);
+        assert_approx_eq!(f64, call.data.inner_radius, 10.);
+        assert_approx_eq!(f64, call.data.ring_gap, 4.);
+        assert_eq!(call.data.generator_sizes, vec![18., 18.]);
+    }
+
+    const DOMAIN: PixelGeneratorDomain = PixelGeneratorDomain {
+        fixed: ColorDimension::new("saturation", 0., 1.),
+        default_fixed: 1.,
+        varying: ColorDimension::new("lightness", 0., 1.),
+    };
+
+    fn pixel_generator() -> MockPixelGenerator {
+        pixel_generator_with_fixed(0.5)
+    }
+
+    fn pixel_generator_with_fixed(fixed: f64) -> MockPixelGenerator {
+        let mut pixel_generator = MockPixelGenerator::new();
+        pixel_generator.expect_domain().return_const(DOMAIN);
+        pixel_generator.expect_fixed().return_const(fixed);
+        pixel_generator
+            .expect_configuration()
+            .return_const(PixelGeneratorConfiguration {
+                is_angle_inverted: false,
+                is_varying_dimension_inverted: false,
+                angle_bucket_direction: BucketDirection::Down,
+                angle_bucket_spacing: BucketSpacing::Uniform,
+                varying_dimension_bucket_direction: BucketDirection::Up,
+            });
+        pixel_generator
+    }
+
+    #[test]
+    fn it_should_size_rings_by_weight() {
+        let mut canvas_pixel_writer = MockCanvasPixelWriter {};
+
+        let render_color_wheel_rows = Rc::new(MockRenderColorWheelRows {
+            calls: RefCell::new(vec![]),
+        });
+
+        let definition = ColorWheelDefinition {
+            image_size: 84,
+            margin_size: 2,
+            angle_buckets: 4,
+            distance_buckets: 5,
+            angle_bucket_layout: Default::default(),
+            distance_bucket_layout: Default::default(),
+            shape: ColorWheelShape {
+                ring_gap: 4,
+                ..Default::default()
+            },
+            radial_mapping: Default::default(),
+            pixel_generators: vec![pixel_generator(), pixel_generator()],
+            rings: vec![RingOptions {
+                weight: 3.,
+                ..Default::default()
+            }],
+        };
+
+        let renderer = DefaultRenderColorWheel {
+            render_color_wheel_rows: render_color_wheel_rows.clone(),
+        };
+
+        renderer
+            .execute(&definition, &mut canvas_pixel_writer)
+            .unwrap();
+
+        let calls = render_color_wheel_rows.calls.borrow();
+        assert_eq!(calls[0].data.generator_sizes, vec![27., 9.]);
+    }
+
+    #[test]
+    fn when_rings_are_too_thin_it_should_return_error() {
+        let mut canvas_pixel_writer = MockCanvasPixelWriter {};
+
+        let render_color_wheel_rows = Rc::new(MockRenderColorWheelRows {
+            calls: RefCell::new(vec![]),
+        });
+
+        let definition = ColorWheelDefinition {
+            image_size: 15,
+            margin_size: 2,
+            angle_buckets: 4,
+            distance_buckets: 5,
+            angle_bucket_layout: Default::default(),
+            distance_bucket_layout: Default::default(),
+            shape: ColorWheelShape {
+                ring_gap: 6,
+                ..Default::default()
+            },
+            radial_mapping: Default::default(),
+            pixel_generators: vec![pixel_generator(), pixel_generator()],
+            rings: vec![],
+        };
+
+        let renderer = DefaultRenderColorWheel {
+            render_color_wheel_rows: render_color_wheel_rows.clone(),
+        };
+
+        assert_eq!(
+            renderer.execute(&definition, &mut canvas_pixel_writer),
+            Err(ColorWheelError::ImageTooSmall)
+        );
+        assert_eq!(render_color_wheel_rows.calls.borrow().len(), 0);
+    }
+
+    #[test]
+    fn when_fixed_value_is_out_of_range_it_should_return_error() {
+        let mut canvas_pixel_writer = MockCanvasPixelWriter {};
+
+        let render_color_wheel_rows = Rc::new(MockRenderColorWheelRows {
+            calls: RefCell::new(vec![]),
+        });
+
+        let definition = ColorWheelDefinition {
+            image_size: 15,
+            margin_size: 2,
+            angle_buckets: 4,
+            distance_buckets: 5,
+            angle_bucket_layout: Default::default(),
+            distance_bucket_layout: Default::default(),
+            shape: Default::default(),
+            radial_mapping: Default::default(),
+            pixel_generators: vec![pixel_generator(), pixel_generator_with_fixed(5.)],
+            rings: vec![],
+        };
+
+        let renderer = DefaultRenderColorWheel {
+            render_color_wheel_rows: render_color_wheel_rows.clone(),
+        };
+
+        assert_eq!(
+            renderer.execute(&definition, &mut canvas_pixel_writer),
+            Err(ColorWheelError::FixedValueOutOfRange {
+                name: "saturation",
+                value: 5.,
+                minimum: 0.,
+                maximum: 1.
+            })
+        );
+        assert_eq!(render_color_wheel_rows.calls.borrow().len(), 0);
+    }
+
+    #[test]
+    fn when_margin_is_larger_than_image_it_should_return_error() {
+        let mut canvas_pixel_writer = MockCanvasPixelWriter {};
+
+        let render_color_wheel_rows = Rc::new(MockRenderColorWheelRows {
+            calls: RefCell::new(vec![]),
+        });
+
+        let definition = ColorWheelDefinition {
+            image_size: 15,
+            margin_size: 10,
+            angle_buckets: 4,
+            distance_buckets: 5,
+            angle_bucket_layout: Default::default(),
+            distance_bucket_layout: Default::default(),
+            shape: Default::default(),
+            radial_mapping: Default::default(),
+            pixel_generators: vec![pixel_generator()],
+            rings: vec![],
+        };
+
+        let renderer = DefaultRenderColorWheel {
+            render_color_wheel_rows: render_color_wheel_rows.clone(),
+        };
+
+        assert_eq!(
+            renderer.execute(&definition, &mut canvas_pixel_writer),
+            Err(ColorWheelError::ImageTooSmall)
+        );
     }
 
     struct MockCanvasPixelWriter {}
@@ -172,14 +382,16 @@ mod tests {
             data: &RenderPixelData,
             _definition: &ColorWheelDefinition<TPixelGenerator>,
             _canvas_pixel_writer: &mut TCanvasPixelWriter,
-        ) where
+        ) -> Result<(), ColorWheelError>
+        where
             TCanvasPixelWriter: CanvasPixelWriter,
         {
             self.calls.borrow_mut().push(MockRenderColorWheelRowsCall {
                 image_width,
                 image_height,
-                data: *data,
+                data: data.clone(),
             });
+            Ok(())
         }
     }
 }
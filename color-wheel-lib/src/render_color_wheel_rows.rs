@@ -1,9 +1,12 @@
 use crate::{
     canvas_pixel_writer::CanvasPixelWriter,
     color_wheel_definition::ColorWheelDefinition,
+    color_wheel_error::ColorWheelError,
     pixel_generators::PixelGenerator,
     render_pixel::{RenderPixel, RenderPixelData},
+    render_thread_pool::RenderThreadPool,
 };
+#[cfg(feature = "rayon")]
 use rayon::prelude::*;
 
 pub trait RenderColorWheelRows {
@@ -14,7 +17,8 @@ pub trait RenderColorWheelRows {
         data: &RenderPixelData,
         definition: &ColorWheelDefinition<TPixelGenerator>,
         canvas_pixel_writer: &mut TCanvasPixelWriter,
-    ) where
+    ) -> Result<(), ColorWheelError>
+    where
         TCanvasPixelWriter: CanvasPixelWriter;
 }
 
@@ -23,6 +27,7 @@ where
     TRenderPixel: RenderPixel,
 {
     pub render_pixel: TRenderPixel,
+    pub thread_pool: RenderThreadPool,
 }
 
 impl<TRenderPixel> RenderColorWheelRows for DefaultRenderColorWheelRows<TRenderPixel>
@@ -36,26 +41,35 @@ where
         data: &RenderPixelData,
         definition: &ColorWheelDefinition<TPixelGenerator>,
         canvas_pixel_writer: &mut TCanvasPixelWriter,
-    ) where
+    ) -> Result<(), ColorWheelError>
+    where
         TCanvasPixelWriter: CanvasPixelWriter,
     {
         let mut rows = canvas_pixel_writer.rows_mut();
 
         if rows.len() < image_height as usize {
-            panic!(
-                "Expected color wheel image height was {} but only {} canvas rows were returned.",
-                image_height,
-                rows.len()
-            );
+            return Err(ColorWheelError::NotEnoughRows {
+                expected: image_height,
+                actual: rows.len(),
+            });
         }
 
-        // Use Rayon to parallelize the loop over the rows.
-        rows.par_iter_mut().enumerate().for_each(|(image_y, row)| {
-            for image_x in 0..image_width {
-                self.render_pixel
-                    .execute(image_x, image_y as u32, data, definition, row);
-            }
-        });
+        let render_row = |(image_y, row): (usize, &mut TCanvasPixelWriter::RowPixelWriter<'_>)| {
+            self.render_pixel
+                .execute_row(image_y as u32, image_width, data, definition, row)
+        };
+
+        // Use Rayon to parallelize the loop over the rows where it's available. Without it,
+        // for example on WebAssembly, the rows are rendered in order on the current thread.
+        self.thread_pool.install(|| {
+            #[cfg(feature = "rayon")]
+            let rows = rows.par_iter_mut();
+
+            #[cfg(not(feature = "rayon"))]
+            let rows = rows.iter_mut();
+
+            rows.enumerate().try_for_each(render_row)
+        })
     }
 }
 
@@ -86,9 +100,12 @@ mod tests {
 
         let renderer = DefaultRenderColorWheelRows {
             render_pixel: render_pixel.clone(),
+            thread_pool: RenderThreadPool::global(),
         };
 
-        renderer.execute(2, 2, &data, &definition, &mut canvas_pixel_writer);
+        renderer
+            .execute(2, 2, &data, &definition, &mut canvas_pixel_writer)
+            .unwrap();
 
         let calls = render_pixel.calls.lock().unwrap();
         assert_eq!(calls.len(), 4);
@@ -112,8 +129,7 @@ mod tests {
     }
 
     #[test]
-    #[should_panic]
-    fn when_not_enough_rows_it_should_panic() {
+    fn when_not_enough_rows_it_should_return_error() {
         let rows = (0..2)
             .map(|_| Arc::new(Mutex::new(MockRowPixelWriter::new())))
             .collect::<Vec<Arc<Mutex<MockRowPixelWriter>>>>();
@@ -127,9 +143,18 @@ mod tests {
         let definition = random_color_wheel_definition();
         let data = random_render_pixel_data();
 
-        let renderer = DefaultRenderColorWheelRows { render_pixel };
+        let renderer = DefaultRenderColorWheelRows {
+            render_pixel,
+            thread_pool: RenderThreadPool::global(),
+        };
 
-        renderer.execute(2, 3, &data, &definition, &mut canvas_pixel_writer);
+        assert_eq!(
+            renderer.execute(2, 3, &data, &definition, &mut canvas_pixel_writer),
+            Err(ColorWheelError::NotEnoughRows {
+                expected: 3,
+                actual: 2
+            })
+        );
     }
 
     fn random_render_pixel_data() -> RenderPixelData {
@@ -137,7 +162,11 @@ mod tests {
             center_x: 7,
             center_y: 7,
             all_generators_size: 5.5,
-            generator_size: 2.75,
+            generator_sizes: vec![2.75, 2.75],
+            inner_radius: 0.,
+            ring_gap: 0.,
+            radial_perceptual_scales: vec![],
+            angle_perceptual_scales: vec![],
         }
     }
 
@@ -147,7 +176,12 @@ mod tests {
             margin_size: 2,
             angle_buckets: 4,
             distance_buckets: 5,
+            angle_bucket_layout: Default::default(),
+            distance_bucket_layout: Default::default(),
+            shape: Default::default(),
+            radial_mapping: Default::default(),
             pixel_generators: vec![MockPixelGenerator::new(), MockPixelGenerator::new()],
+            rings: vec![],
         }
     }
 
@@ -160,8 +194,14 @@ mod tests {
         }
     }
     impl RowPixelWriter for Arc<Mutex<MockRowPixelWriter>> {
-        fn write_pixel(&mut self, x: u32, y: u32, _pixel: crate::pixel::Pixel) {
+        fn write_pixel(
+            &mut self,
+            x: u32,
+            y: u32,
+            _pixel: crate::pixel::Pixel,
+        ) -> Result<(), ColorWheelError> {
             self.lock().unwrap().calls.push((x, y));
+            Ok(())
         }
     }
 
@@ -192,13 +232,13 @@ mod tests {
             data: &RenderPixelData,
             _definition: &ColorWheelDefinition<TPixelGenerator>,
             pixel_writer: &mut TRowPixelWriter,
-        ) {
-            pixel_writer.write_pixel(image_x, image_y, Pixel::transparent());
+        ) -> Result<(), ColorWheelError> {
             self.calls.lock().unwrap().push(MockRenderPixelCall {
                 image_x,
                 image_y,
-                data: *data,
+                data: data.clone(),
             });
+            pixel_writer.write_pixel(image_x, image_y, Pixel::transparent())
         }
     }
 }
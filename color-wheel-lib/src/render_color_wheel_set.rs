@@ -1,6 +1,6 @@
 use crate::{
     canvas_pixel_writer::CanvasPixelWriter, canvas_pixel_writer_factory::CanvasPixelWriterFactory,
-    color_wheel_definition::ColorWheelDefinition,
+    color_wheel_definition::ColorWheelDefinition, color_wheel_error::ColorWheelError,
     offset_canvas_pixel_writer::OffsetCanvasPixelWriter, pixel_generators::PixelGenerator,
     render_color_wheel::RenderColorWheel,
 };
@@ -12,7 +12,7 @@ pub trait RenderColorWheelSet {
         &self,
         color_wheels: &[ColorWheelDefinition<TPixelGenerator>],
         spacing: u32,
-    ) -> Self::Result;
+    ) -> Result<Self::Result, ColorWheelError>;
 }
 
 pub struct DefaultRenderColorWheelSet<TRenderColorWheel, TCanvasPixelWriterFactory>
@@ -38,21 +38,21 @@ where
         &self,
         color_wheels: &[ColorWheelDefinition<TPixelGenerator>],
         spacing: u32,
-    ) -> TCanvasPixelWriterFactory::Result {
-        if color_wheels.is_empty() {
-            panic!("No color wheels to render.");
-        }
-
-        let color_wheels_count: u32 = color_wheels
-            .len()
-            .try_into()
-            .expect("Too many color wheels.");
+    ) -> Result<TCanvasPixelWriterFactory::Result, ColorWheelError> {
+        let Some(overall_height) = color_wheels.iter().map(|v| v.image_size).max() else {
+            return Err(ColorWheelError::NoColorWheels);
+        };
 
         // Color wheels are laid out horizontally, so we take the max size for the height
         // and the sum of the sizes with padding added for the width.
-        let overall_height = color_wheels.iter().map(|v| v.image_size).max().unwrap();
-        let overall_width = color_wheels.iter().map(|v| v.image_size).sum::<u32>()
-            + (spacing * (color_wheels_count - 1));
+        let overall_width = color_wheels
+            .iter()
+            .try_fold(0u32, |width, v| width.checked_add(v.image_size))
+            .and_then(|width| {
+                let spacing_count = u32::try_from(color_wheels.len() - 1).ok()?;
+                width.checked_add(spacing.checked_mul(spacing_count)?)
+            })
+            .ok_or(ColorWheelError::ImageTooLarge)?;
 
         let mut pixel_writer = self
             .pixel_writer_factory
@@ -67,11 +67,11 @@ where
             };
 
             self.render_color_wheel
-                .execute(color_wheel, &mut offset_pixel_writer);
+                .execute(color_wheel, &mut offset_pixel_writer)?;
             offset_x += color_wheel.image_size + spacing;
         }
 
-        pixel_writer
+        Ok(pixel_writer)
     }
 }
 
@@ -102,18 +102,28 @@ mod tests {
                 margin_size: 10,
                 angle_buckets: 36,
                 distance_buckets: 5,
+                angle_bucket_layout: Default::default(),
+                distance_bucket_layout: Default::default(),
+                shape: Default::default(),
+                radial_mapping: Default::default(),
                 pixel_generators: vec![MockPixelGenerator::new()],
+                rings: vec![],
             },
             ColorWheelDefinition {
                 image_size: 200,
                 margin_size: 20,
                 angle_buckets: 36,
                 distance_buckets: 5,
+                angle_bucket_layout: Default::default(),
+                distance_bucket_layout: Default::default(),
+                shape: Default::default(),
+                radial_mapping: Default::default(),
                 pixel_generators: vec![MockPixelGenerator::new()],
+                rings: vec![],
             },
         ];
 
-        render_color_wheel_set.execute(&color_wheels, 10);
+        render_color_wheel_set.execute(&color_wheels, 10).unwrap();
 
         assert_eq!(pixel_writer_factory.calls.take(), vec![(310, 200)]);
 
@@ -134,6 +144,49 @@ mod tests {
         );
     }
 
+    #[test]
+    fn when_no_color_wheels_it_should_return_error() {
+        let render_color_wheel_set = DefaultRenderColorWheelSet {
+            render_color_wheel: Rc::new(MockRenderColorWheel::default()),
+            pixel_writer_factory: Rc::new(MockPixelWriterFactory::default()),
+        };
+
+        let color_wheels: Vec<ColorWheelDefinition<MockPixelGenerator>> = vec![];
+
+        assert!(matches!(
+            render_color_wheel_set.execute(&color_wheels, 10),
+            Err(ColorWheelError::NoColorWheels)
+        ));
+    }
+
+    #[test]
+    fn when_color_wheels_are_too_wide_it_should_return_error() {
+        let pixel_writer_factory: Rc<MockPixelWriterFactory> = Default::default();
+        let render_color_wheel_set = DefaultRenderColorWheelSet {
+            render_color_wheel: Rc::new(MockRenderColorWheel::default()),
+            pixel_writer_factory: Rc::clone(&pixel_writer_factory),
+        };
+
+        let color_wheel = || ColorWheelDefinition {
+            image_size: u32::MAX / 2,
+            margin_size: 10,
+            angle_buckets: 0,
+            distance_buckets: 0,
+            angle_bucket_layout: Default::default(),
+            distance_bucket_layout: Default::default(),
+            shape: Default::default(),
+            radial_mapping: Default::default(),
+            pixel_generators: vec![MockPixelGenerator::new()],
+            rings: vec![],
+        };
+
+        assert!(matches!(
+            render_color_wheel_set.execute(&[color_wheel(), color_wheel()], 10),
+            Err(ColorWheelError::ImageTooLarge)
+        ));
+        assert!(pixel_writer_factory.calls.take().is_empty());
+    }
+
     struct MockCanvasPixelWriter {}
 
     impl CanvasPixelWriter for MockCanvasPixelWriter {
@@ -176,12 +229,13 @@ mod tests {
             &self,
             definition: &ColorWheelDefinition<TPixelGenerator>,
             pixel_writer: &mut OffsetCanvasPixelWriter<'canvas, TPixelWriter>,
-        ) {
+        ) -> Result<(), ColorWheelError> {
             self.calls.borrow_mut().push(RenderColorWheelCall {
                 wheel_size: definition.image_size,
                 offset_x: pixel_writer.offset_x,
                 offset_y: pixel_writer.offset_y,
             });
+            Ok(())
         }
     }
 }
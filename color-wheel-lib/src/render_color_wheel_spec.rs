@@ -0,0 +1,286 @@
+use crate::{
+    canvas::Canvas,
+    canvas_pixel_writer::{ColorCanvasPixelWriter, DefaultCanvasPixelWriter},
+    canvas_pixel_writer_factory::{
+        CanvasPixelWriterFactory, ColorCanvasPixelWriterFactory, DefaultCanvasPixelWriterFactory,
+    },
+    color_vision_deficiency::ColorVisionDeficiencySimulation,
+    color_wheel_error::ColorWheelError,
+    color_wheel_spec::ColorWheelSpec,
+    dither::dither,
+    get_pixel::DefaultGetPixel,
+    get_pixel_generator_and_variable_dimension::DefaultGetPixelGeneratorAndVariableDimension,
+    render_color_wheel::DefaultRenderColorWheel,
+    render_color_wheel_rows::DefaultRenderColorWheelRows,
+    render_color_wheel_set::{DefaultRenderColorWheelSet, RenderColorWheelSet},
+    render_pixel::DefaultRenderPixel,
+    resample::{downsample, downsample_canvas},
+};
+
+// Renders the spec's color wheels, including any simulations, to a canvas at the output size.
+pub fn render_color_wheel_spec(spec: &ColorWheelSpec) -> Result<Canvas, ColorWheelError> {
+    if let Some(method) = spec.dither {
+        let colors = render_color_wheel_spec_normalized(spec)?;
+        return Ok(dither(
+            colors.width,
+            colors.height,
+            &colors.data,
+            256,
+            method,
+        ));
+    }
+
+    spec.validate()?;
+
+    let mut pixel_writer = render_color_wheel_sets(
+        spec,
+        DefaultCanvasPixelWriterFactory {
+            background: spec.supersampled_background(),
+        },
+    )?;
+    simulate_canvas(spec, &mut pixel_writer);
+
+    if spec.supersampling == 1 {
+        return Ok(pixel_writer.canvas);
+    }
+
+    let canvas = pixel_writer.canvas;
+    Ok(spec
+        .thread_pool
+        .install(|| downsample_canvas(&canvas, spec.supersampling, spec.resample_filter)))
+}
+
+// As `render_color_wheel_spec`, keeping more precision than 8 bits per channel, as normalized,
+// gamma encoded RGBA. The spec's dither method is ignored.
+pub fn render_color_wheel_spec_normalized(
+    spec: &ColorWheelSpec,
+) -> Result<ColorCanvasPixelWriter, ColorWheelError> {
+    spec.validate()?;
+
+    // Without supersampling, the colors are rendered at full precision.
+    if spec.supersampling == 1 {
+        let mut colors = render_color_wheel_sets(
+            spec,
+            ColorCanvasPixelWriterFactory {
+                background: spec.supersampled_background(),
+            },
+        )?;
+        simulate_colors(spec, &mut colors);
+
+        return Ok(colors);
+    }
+
+    // Otherwise averaging the supersampled pixels gives us more precision than 8 bits.
+    let mut pixel_writer = render_color_wheel_sets(
+        spec,
+        DefaultCanvasPixelWriterFactory {
+            background: spec.supersampled_background(),
+        },
+    )?;
+    simulate_canvas(spec, &mut pixel_writer);
+
+    let canvas = pixel_writer.canvas;
+    Ok(ColorCanvasPixelWriter {
+        width: canvas.width() / spec.supersampling,
+        height: canvas.height() / spec.supersampling,
+        data: spec
+            .thread_pool
+            .install(|| downsample(&canvas, spec.supersampling, spec.resample_filter)),
+    })
+}
+
+fn render_color_wheel_sets<TCanvasPixelWriterFactory>(
+    spec: &ColorWheelSpec,
+    pixel_writer_factory: TCanvasPixelWriterFactory,
+) -> Result<TCanvasPixelWriterFactory::Result, ColorWheelError>
+where
+    TCanvasPixelWriterFactory: CanvasPixelWriterFactory,
+    TCanvasPixelWriterFactory::Result: 'static,
+{
+    // When simulating color vision deficiencies, each set of color wheels is rendered
+    // once per simulation and laid out side by side.
+    let color_wheel_definitions = (0..spec.set_count())
+        .flat_map(|_| spec.color_wheel_definitions())
+        .collect::<Vec<_>>();
+
+    let render_color_wheel_set = DefaultRenderColorWheelSet {
+        render_color_wheel: DefaultRenderColorWheel {
+            render_color_wheel_rows: DefaultRenderColorWheelRows {
+                render_pixel: DefaultRenderPixel {
+                    get_pixel_generator_and_variable_dimension:
+                        DefaultGetPixelGeneratorAndVariableDimension {},
+                    get_pixel: DefaultGetPixel {},
+                },
+                thread_pool: spec.thread_pool.clone(),
+            },
+        },
+        pixel_writer_factory,
+    };
+
+    render_color_wheel_set.execute(&color_wheel_definitions, 0)
+}
+
+fn simulate_canvas(spec: &ColorWheelSpec, pixel_writer: &mut DefaultCanvasPixelWriter) {
+    let canvas = &mut pixel_writer.canvas;
+    let height = canvas.height();
+
+    for (simulation, offset_x, set_width) in simulated_sets(spec, canvas.width()) {
+        canvas.map_pixels(offset_x, 0, set_width, height, |pixel| {
+            simulation.simulate(pixel)
+        });
+    }
+}
+
+// As `simulate_canvas`, keeping the colors at full precision.
+fn simulate_colors(spec: &ColorWheelSpec, pixel_writer: &mut ColorCanvasPixelWriter) {
+    let width = pixel_writer.width as usize;
+
+    for (simulation, offset_x, set_width) in simulated_sets(spec, pixel_writer.width) {
+        for row in pixel_writer.data.chunks_exact_mut(width * 4) {
+            let set = &mut row[offset_x as usize * 4..(offset_x + set_width) as usize * 4];
+
+            for rgba in set.chunks_exact_mut(4) {
+                let [red, green, blue] =
+                    simulation.simulate_normalized([rgba[0], rgba[1], rgba[2]]);
+                rgba[..3].copy_from_slice(&[red, green, blue]);
+            }
+        }
+    }
+}
+
+// Each simulation with the x offset and width of the set of color wheels it applies to.
+fn simulated_sets(
+    spec: &ColorWheelSpec,
+    width: u32,
+) -> impl Iterator<Item = (ColorVisionDeficiencySimulation, u32, u32)> + '_ {
+    let set_width = width / spec.set_count();
+    let first_simulated_set = u32::from(spec.compare_simulations);
+
+    spec.simulations
+        .iter()
+        .enumerate()
+        .map(move |(index, deficiency)| {
+            let simulation = ColorVisionDeficiencySimulation {
+                deficiency: *deficiency,
+                severity: spec.simulation_severity,
+            };
+
+            let offset_x = (first_simulated_set + index as u32) * set_width;
+            (simulation, offset_x, set_width)
+        })
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{
+        color_vision_deficiency::ColorVisionDeficiency, dither::DitherMethod, pixel::Pixel,
+        pixel_generators::HsvFixedValuePixelGenerator,
+    };
+
+    #[cfg(feature = "rayon")]
+    use crate::render_thread_pool::RenderThreadPool;
+
+    use super::*;
+
+    fn spec() -> ColorWheelSpec {
+        ColorWheelSpec {
+            diameter: 21,
+            margin: 2,
+            supersampling: 2,
+            ..ColorWheelSpec::new(HsvFixedValuePixelGenerator::REGISTRATION)
+        }
+    }
+
+    #[test]
+    fn it_should_render_at_the_output_size() {
+        let canvas = render_color_wheel_spec(&spec()).unwrap();
+
+        assert_eq!((canvas.width(), canvas.height()), (25, 25));
+        assert_eq!(canvas.get_pixel(0, 0), Pixel::rgba(0, 0, 0, 0));
+        assert_eq!(canvas.get_pixel(12, 12).data[3], 255);
+    }
+
+    #[test]
+    fn it_should_simulate_each_set_after_the_comparison() {
+        let canvas = render_color_wheel_spec(&ColorWheelSpec {
+            simulations: vec![ColorVisionDeficiency::Achromatopsia],
+            compare_simulations: true,
+            ..spec()
+        })
+        .unwrap();
+
+        assert_eq!(canvas.width(), 50);
+
+        // The top of the wheel is red, which achromatopsia turns grey.
+        let original = canvas.get_pixel(12, 4);
+        let simulated = canvas.get_pixel(37, 4);
+        assert!(original.data[0] > original.data[1]);
+        assert_eq!(simulated.data[0], simulated.data[1]);
+    }
+
+    #[test]
+    fn it_should_dither_without_supersampling() {
+        let canvas = render_color_wheel_spec(&ColorWheelSpec {
+            supersampling: 1,
+            dither: Some(DitherMethod::Bayer),
+            ..spec()
+        })
+        .unwrap();
+
+        assert_eq!((canvas.width(), canvas.height()), (25, 25));
+    }
+
+    #[test]
+    fn it_should_keep_precision_beyond_eight_bits() {
+        for supersampling in [1, 2] {
+            let spec = ColorWheelSpec {
+                supersampling,
+                ..spec()
+            };
+            let canvas = render_color_wheel_spec(&spec).unwrap();
+            let colors = render_color_wheel_spec_normalized(&spec).unwrap();
+
+            assert_eq!((colors.width, colors.height), (25, 25));
+            assert_eq!(colors.data.len(), canvas.data().len());
+            for (color, channel) in colors.data.iter().zip(canvas.data()) {
+                assert_eq!((color * 255.).round() as u8, *channel);
+            }
+            assert!(colors
+                .data
+                .iter()
+                .any(|color| (color * 255.).fract().abs() > 0.01));
+        }
+    }
+
+    #[cfg(feature = "rayon")]
+    #[test]
+    fn it_should_render_the_same_image_with_the_spec_thread_pool() {
+        let with_thread_pool = ColorWheelSpec {
+            thread_pool: RenderThreadPool::with_threads(3).unwrap(),
+            ..spec()
+        };
+
+        assert_eq!(
+            render_color_wheel_spec(&with_thread_pool).unwrap().data(),
+            render_color_wheel_spec(&spec()).unwrap().data()
+        );
+        assert_eq!(
+            render_color_wheel_spec_normalized(&with_thread_pool)
+                .unwrap()
+                .data,
+            render_color_wheel_spec_normalized(&spec()).unwrap().data
+        );
+    }
+
+    #[test]
+    fn it_should_reject_invalid_specs() {
+        assert_eq!(
+            render_color_wheel_spec(&ColorWheelSpec {
+                fixed: vec![],
+                ..spec()
+            })
+            .err(),
+            Some(ColorWheelError::NoColorWheels)
+        );
+    }
+}
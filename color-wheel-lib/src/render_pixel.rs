@@ -1,16 +1,131 @@
 use crate::{
-    color_wheel_definition::ColorWheelDefinition, get_angle_degrees::get_angle_degrees,
+    bucket::BucketSpacing,
+    color::Color,
+    color_wheel_definition::ColorWheelDefinition,
+    color_wheel_error::ColorWheelError,
     get_pixel::GetPixel,
-    get_pixel_generator_and_variable_dimension::GetPixelGeneratorAndVariableDimension,
-    pixel_generators::PixelGenerator, row_pixel_writer::RowPixelWriter,
+    get_pixel_generator_and_variable_dimension::{
+        GetPixelGeneratorAndVariableDimension, PixelGeneratorAndVariableDimension,
+    },
+    perceptual_scale::PerceptualScale,
+    pixel::Pixel,
+    pixel_generators::PixelGenerator,
+    radial_mapping::RadialMapping,
+    row_pixel_writer::RowPixelWriter,
 };
 
-#[derive(Debug, Clone, Copy, PartialEq)]
+#[derive(Debug, Clone, PartialEq)]
 pub struct RenderPixelData {
     pub center_x: u32,
     pub center_y: u32,
     pub all_generators_size: f64,
-    pub generator_size: f64,
+    // The width of each pixel generator's ring.
+    pub generator_sizes: Vec<f64>,
+    pub inner_radius: f64,
+    pub ring_gap: f64,
+
+    // One per pixel generator when using the perceptual radial mapping, otherwise empty.
+    pub radial_perceptual_scales: Vec<PerceptualScale>,
+
+    // One per pixel generator, for those with perceptual angle bucket spacing.
+    pub angle_perceptual_scales: Vec<Option<PerceptualScale>>,
+}
+
+impl RenderPixelData {
+    // Lays out the rings of a color wheel with at least one pixel generator.
+    pub fn new<TPixelGenerator: PixelGenerator>(
+        definition: &ColorWheelDefinition<TPixelGenerator>,
+    ) -> Result<Self, ColorWheelError> {
+        for pixel_generator in &definition.pixel_generators {
+            pixel_generator
+                .domain()
+                .validate_fixed(pixel_generator.fixed())?;
+        }
+
+        let image_width = definition.image_size;
+        let image_height = definition.image_size;
+
+        let wheel_diameter = image_width
+            .min(image_height)
+            .checked_sub(definition.margin_size * 2)
+            .ok_or(ColorWheelError::ImageTooSmall)?;
+
+        let center_x = image_width / 2;
+        let center_y = image_height / 2;
+
+        let all_generators_size = wheel_diameter as f64 / 2.;
+
+        let generator_count = definition.pixel_generators.len() as f64;
+        let inner_radius = all_generators_size * definition.shape.inner_radius.clamp(0., 1.);
+        let ring_gap = definition.shape.ring_gap as f64;
+
+        let available_size = all_generators_size - inner_radius - ring_gap * (generator_count - 1.);
+        let ring_weights = (0..definition.pixel_generators.len())
+            .map(|index| definition.ring(index).weight.max(0.))
+            .collect::<Vec<_>>();
+        let total_weight = ring_weights.iter().sum::<f64>();
+
+        let generator_sizes = ring_weights
+            .iter()
+            .map(|weight| available_size * weight / total_weight)
+            .collect::<Vec<_>>();
+        if total_weight <= 0.
+            || generator_sizes
+                .iter()
+                .any(|generator_size| *generator_size < 1.)
+        {
+            return Err(ColorWheelError::ImageTooSmall);
+        }
+
+        let radial_perceptual_scales = match definition.radial_mapping {
+            RadialMapping::Perceptual => definition
+                .pixel_generators
+                .iter()
+                .map(PerceptualScale::variable_dimension)
+                .collect(),
+            _ => vec![],
+        };
+
+        let angle_perceptual_scales = definition
+            .pixel_generators
+            .iter()
+            .enumerate()
+            .map(|(index, pixel_generator)| {
+                let is_perceptual = definition
+                    .angle_bucket_layout
+                    .bucket_count(definition.ring_angle_buckets(index))
+                    > 0
+                    && pixel_generator.configuration().angle_bucket_spacing
+                        == BucketSpacing::Perceptual;
+                is_perceptual.then(|| PerceptualScale::angle(pixel_generator))
+            })
+            .collect();
+
+        Ok(RenderPixelData {
+            center_x,
+            center_y,
+            all_generators_size,
+            generator_sizes,
+            inner_radius,
+            ring_gap,
+            radial_perceptual_scales,
+            angle_perceptual_scales,
+        })
+    }
+}
+
+// A pixel's color and where it falls on the color wheel.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct RenderedPixel {
+    pub pixel: Pixel,
+    pub pixel_generator_index: usize,
+
+    // Clockwise from the top, before rotation.
+    pub angle_degrees: f64,
+    pub distance_from_center: f64,
+
+    // Before bucketing, from 0 to 1.
+    pub variable_dimension: f64,
 }
 
 pub trait RenderPixel: Sync {
@@ -21,8 +136,34 @@ pub trait RenderPixel: Sync {
         data: &RenderPixelData,
         definition: &ColorWheelDefinition<TPixelGenerator>,
         pixel_writer: &mut TRowPixelWriter,
-    ) where
+    ) -> Result<(), ColorWheelError>
+    where
         TRowPixelWriter: for<'canvas> RowPixelWriter;
+
+    // Renders every pixel in a row, as `execute` does for each.
+    fn execute_row<TPixelGenerator: PixelGenerator, TRowPixelWriter>(
+        &self,
+        image_y: u32,
+        image_width: u32,
+        data: &RenderPixelData,
+        definition: &ColorWheelDefinition<TPixelGenerator>,
+        pixel_writer: &mut TRowPixelWriter,
+    ) -> Result<(), ColorWheelError>
+    where
+        TRowPixelWriter: for<'canvas> RowPixelWriter,
+    {
+        (0..image_width)
+            .try_for_each(|image_x| self.execute(image_x, image_y, data, definition, pixel_writer))
+    }
+}
+
+// Consecutive pixels of a row in the same ring, whose pixels are got together.
+struct PixelRun<'a, TPixelGenerator: PixelGenerator> {
+    pixel_generator: &'a TPixelGenerator,
+    pixel_generator_index: usize,
+    image_xs: Vec<u32>,
+    angles_degrees: Vec<f64>,
+    variable_dimensions: Vec<f64>,
 }
 
 pub struct DefaultRenderPixel<TGetPixelGeneratorAndVariableDimension, TGetPixel>
@@ -47,45 +188,248 @@ where
         data: &RenderPixelData,
         definition: &ColorWheelDefinition<TPixelGenerator>,
         pixel_writer: &mut TRowPixelWriter,
-    ) where
+    ) -> Result<(), ColorWheelError>
+    where
         TRowPixelWriter: for<'canvas> RowPixelWriter,
     {
         let relative_x = image_x as f64 - data.center_x as f64;
         let relative_y = image_y as f64 - data.center_y as f64;
-        let distance_from_center = (relative_x.powi(2) + relative_y.powi(2)).sqrt();
 
-        if distance_from_center > data.all_generators_size {
-            return;
+        if TRowPixelWriter::FULL_PRECISION {
+            return match self.render_relative_color(relative_x, relative_y, data, definition) {
+                Some(color) => pixel_writer.write_color(image_x, image_y, color),
+                None => Ok(()),
+            };
         }
 
-        let pixel_generator_result = self.get_pixel_generator_and_variable_dimension.execute(
-            data.generator_size,
-            definition,
-            distance_from_center,
+        match self.render_relative_position(relative_x, relative_y, data, definition) {
+            Some(rendered_pixel) => {
+                pixel_writer.write_pixel(image_x, image_y, rendered_pixel.pixel)
+            }
+            None => Ok(()),
+        }
+    }
+
+    // Projects the whole row first, then gets the pixels for each run of pixels in the same ring
+    // together, so that pixel generators can convert several at once.
+    fn execute_row<TPixelGenerator: PixelGenerator, TRowPixelWriter>(
+        &self,
+        image_y: u32,
+        image_width: u32,
+        data: &RenderPixelData,
+        definition: &ColorWheelDefinition<TPixelGenerator>,
+        pixel_writer: &mut TRowPixelWriter,
+    ) -> Result<(), ColorWheelError>
+    where
+        TRowPixelWriter: for<'canvas> RowPixelWriter,
+    {
+        if TRowPixelWriter::FULL_PRECISION {
+            return (0..image_width).try_for_each(|image_x| {
+                self.execute(image_x, image_y, data, definition, pixel_writer)
+            });
+        }
+
+        let relative_xs = (0..image_width)
+            .map(|image_x| image_x as f64 - data.center_x as f64)
+            .collect::<Vec<_>>();
+        let relative_y = image_y as f64 - data.center_y as f64;
+        let projected_pixels = definition.shape.projection.project_row(
+            &relative_xs,
+            relative_y,
+            data.all_generators_size,
         );
 
-        if pixel_generator_result.is_none() {
-            return;
+        let mut run: Option<PixelRun<TPixelGenerator>> = None;
+        for (image_x, projected_pixel) in (0..image_width).zip(projected_pixels) {
+            let Some(projected_pixel) = projected_pixel else {
+                continue;
+            };
+            let Some(pixel_generator_result) = self.find_ring(
+                projected_pixel.angle_degrees,
+                projected_pixel.distance_from_center,
+                data,
+                definition,
+            ) else {
+                continue;
+            };
+
+            if let Some(previous_run) = run.take_if(|run| {
+                run.pixel_generator_index != pixel_generator_result.pixel_generator_index
+            }) {
+                self.render_run(previous_run, image_y, data, definition, pixel_writer)?;
+            }
+
+            let run = run.get_or_insert_with(|| PixelRun {
+                pixel_generator: pixel_generator_result.pixel_generator,
+                pixel_generator_index: pixel_generator_result.pixel_generator_index,
+                image_xs: vec![],
+                angles_degrees: vec![],
+                variable_dimensions: vec![],
+            });
+            run.image_xs.push(image_x);
+            run.angles_degrees
+                .push(definition.shape.rotate(projected_pixel.angle_degrees));
+            run.variable_dimensions
+                .push(pixel_generator_result.variable_dimension);
+        }
+
+        match run {
+            Some(run) => self.render_run(run, image_y, data, definition, pixel_writer),
+            None => Ok(()),
         }
+    }
+}
+
+impl<TGetPixelGeneratorAndVariableDimension, TGetPixel>
+    DefaultRenderPixel<TGetPixelGeneratorAndVariableDimension, TGetPixel>
+where
+    TGetPixelGeneratorAndVariableDimension: GetPixelGeneratorAndVariableDimension,
+    TGetPixel: GetPixel,
+{
+    // Renders the pixel at a position relative to the center of the wheel, without writing it.
+    // Returns `None` for positions outside of the wheel.
+    pub fn render_relative_position<TPixelGenerator: PixelGenerator>(
+        &self,
+        relative_x: f64,
+        relative_y: f64,
+        data: &RenderPixelData,
+        definition: &ColorWheelDefinition<TPixelGenerator>,
+    ) -> Option<RenderedPixel> {
+        let projected_pixel = definition.shape.projection.project(
+            relative_x,
+            relative_y,
+            data.all_generators_size,
+        )?;
+
+        self.render_polar_position(
+            projected_pixel.angle_degrees,
+            projected_pixel.distance_from_center,
+            data,
+            definition,
+        )
+    }
 
-        let angle_degrees = get_angle_degrees(0., 0., relative_x, relative_y);
+    // Renders the pixel at an angle and distance from the center of a polar wheel, whatever
+    // the wheel's projection.
+    pub fn render_polar_position<TPixelGenerator: PixelGenerator>(
+        &self,
+        angle_degrees: f64,
+        distance_from_center: f64,
+        data: &RenderPixelData,
+        definition: &ColorWheelDefinition<TPixelGenerator>,
+    ) -> Option<RenderedPixel> {
+        let pixel_generator_result =
+            self.find_ring(angle_degrees, distance_from_center, data, definition)?;
 
-        let pixel_generator_result = pixel_generator_result.unwrap();
-        let pixel_generator = pixel_generator_result.pixel_generator;
+        let pixel_generator_index = pixel_generator_result.pixel_generator_index;
         let variable_dimension = pixel_generator_result.variable_dimension;
 
         let pixel = self.get_pixel.execute(
-            pixel_generator,
+            pixel_generator_result.pixel_generator,
+            angle_perceptual_scale(data, pixel_generator_index),
+            definition.shape.rotate(angle_degrees),
+            variable_dimension,
+            pixel_generator_index,
+            definition,
+        );
+
+        Some(RenderedPixel {
+            pixel,
+            pixel_generator_index,
             angle_degrees,
+            distance_from_center,
             variable_dimension,
-            definition.angle_buckets,
-            definition.distance_buckets,
+        })
+    }
+
+    // As `render_relative_position`, but only the color, at full precision.
+    fn render_relative_color<TPixelGenerator: PixelGenerator>(
+        &self,
+        relative_x: f64,
+        relative_y: f64,
+        data: &RenderPixelData,
+        definition: &ColorWheelDefinition<TPixelGenerator>,
+    ) -> Option<Color> {
+        let projected_pixel = definition.shape.projection.project(
+            relative_x,
+            relative_y,
+            data.all_generators_size,
+        )?;
+
+        let pixel_generator_result = self.find_ring(
+            projected_pixel.angle_degrees,
+            projected_pixel.distance_from_center,
+            data,
+            definition,
+        )?;
+
+        Some(self.get_pixel.execute_color(
+            pixel_generator_result.pixel_generator,
+            angle_perceptual_scale(data, pixel_generator_result.pixel_generator_index),
+            definition.shape.rotate(projected_pixel.angle_degrees),
+            pixel_generator_result.variable_dimension,
+            pixel_generator_result.pixel_generator_index,
+            definition,
+        ))
+    }
+
+    // The ring at an angle and distance from the center, unless it's outside the wheel's arc.
+    fn find_ring<'a, TPixelGenerator: PixelGenerator>(
+        &self,
+        angle_degrees: f64,
+        distance_from_center: f64,
+        data: &RenderPixelData,
+        definition: &'a ColorWheelDefinition<TPixelGenerator>,
+    ) -> Option<PixelGeneratorAndVariableDimension<'a, TPixelGenerator>> {
+        if !definition.shape.contains_angle(angle_degrees) {
+            return None;
+        }
+
+        self.get_pixel_generator_and_variable_dimension.execute(
+            data,
+            definition,
+            distance_from_center,
+        )
+    }
+
+    fn render_run<TPixelGenerator: PixelGenerator, TRowPixelWriter>(
+        &self,
+        run: PixelRun<TPixelGenerator>,
+        image_y: u32,
+        data: &RenderPixelData,
+        definition: &ColorWheelDefinition<TPixelGenerator>,
+        pixel_writer: &mut TRowPixelWriter,
+    ) -> Result<(), ColorWheelError>
+    where
+        TRowPixelWriter: for<'canvas> RowPixelWriter,
+    {
+        let mut pixels = vec![Pixel::transparent(); run.image_xs.len()];
+        self.get_pixel.execute_batch(
+            run.pixel_generator,
+            angle_perceptual_scale(data, run.pixel_generator_index),
+            &run.angles_degrees,
+            &run.variable_dimensions,
+            run.pixel_generator_index,
+            definition,
+            &mut pixels,
         );
 
-        pixel_writer.write_pixel(image_x, image_y, pixel);
+        run.image_xs
+            .into_iter()
+            .zip(pixels)
+            .try_for_each(|(image_x, pixel)| pixel_writer.write_pixel(image_x, image_y, pixel))
     }
 }
 
+fn angle_perceptual_scale(
+    data: &RenderPixelData,
+    pixel_generator_index: usize,
+) -> Option<&PerceptualScale> {
+    data.angle_perceptual_scales
+        .get(pixel_generator_index)
+        .and_then(Option::as_ref)
+}
+
 #[cfg(test)]
 mod tests {
     use std::sync::{Arc, Mutex};
@@ -139,7 +483,11 @@ mod tests {
             center_x: 55,
             center_y: 55,
             all_generators_size: 50.,
-            generator_size: 25.,
+            generator_sizes: vec![25., 25.],
+            inner_radius: 0.,
+            ring_gap: 0.,
+            radial_perceptual_scales: vec![],
+            angle_perceptual_scales: vec![],
         };
 
         let color_wheel_definition = ColorWheelDefinition::<MockPixelGenerator> {
@@ -147,7 +495,12 @@ mod tests {
             margin_size: 5,
             angle_buckets: 10,
             distance_buckets: 10,
+            angle_bucket_layout: Default::default(),
+            distance_bucket_layout: Default::default(),
+            shape: Default::default(),
+            radial_mapping: Default::default(),
             pixel_generators: vec![MockPixelGenerator::new(), MockPixelGenerator::new()],
+            rings: vec![],
         };
 
         SetupData {
@@ -164,14 +517,45 @@ mod tests {
     fn when_outside_of_wheel_it_should_return() {
         let mut test = setup(-1, 0., Default::default());
 
-        test.target.execute(
-            1,
-            1,
-            &test.render_pixel_data,
-            &test.color_wheel_definition,
-            &mut test.pixel_writer,
+        test.target
+            .execute(
+                1,
+                1,
+                &test.render_pixel_data,
+                &test.color_wheel_definition,
+                &mut test.pixel_writer,
+            )
+            .unwrap();
+
+        assert_eq!(
+            test.get_pixel_generator_and_variable_dimension
+                .calls
+                .lock()
+                .unwrap()
+                .len(),
+            0
         );
 
+        assert_eq!(test.get_pixel.calls.lock().unwrap().len(), 0);
+    }
+
+    #[test]
+    fn when_outside_of_arc_it_should_return() {
+        let mut test = setup(0, 0., Default::default());
+        test.color_wheel_definition.shape.start_angle_degrees = 90.;
+        test.color_wheel_definition.shape.end_angle_degrees = 180.;
+
+        // Top right of the center, at 45 degrees.
+        test.target
+            .execute(
+                56,
+                54,
+                &test.render_pixel_data,
+                &test.color_wheel_definition,
+                &mut test.pixel_writer,
+            )
+            .unwrap();
+
         assert_eq!(
             test.get_pixel_generator_and_variable_dimension
                 .calls
@@ -188,13 +572,15 @@ mod tests {
     fn when_no_pixel_generator_returned_it_should_return() {
         let mut test = setup(-1, 0., Default::default());
 
-        test.target.execute(
-            56,
-            54,
-            &test.render_pixel_data,
-            &test.color_wheel_definition,
-            &mut test.pixel_writer,
-        );
+        test.target
+            .execute(
+                56,
+                54,
+                &test.render_pixel_data,
+                &test.color_wheel_definition,
+                &mut test.pixel_writer,
+            )
+            .unwrap();
 
         assert_eq!(
             test.get_pixel_generator_and_variable_dimension
@@ -211,7 +597,7 @@ mod tests {
             .lock()
             .unwrap()[0];
 
-        assert_eq!(call.generator_size, test.render_pixel_data.generator_size);
+        assert_eq!(call.data, test.render_pixel_data);
         assert_approx_eq!(f64, call.distance_from_center, std::f64::consts::SQRT_2);
 
         assert_eq!(test.get_pixel.calls.lock().unwrap().len(), 0);
@@ -226,15 +612,17 @@ mod tests {
             .expect_write_pixel()
             .with(eq(56), eq(54), eq(pixel))
             .once()
-            .return_const(());
-
-        test.target.execute(
-            56,
-            54,
-            &test.render_pixel_data,
-            &test.color_wheel_definition,
-            &mut test.pixel_writer,
-        );
+            .return_const(Ok(()));
+
+        test.target
+            .execute(
+                56,
+                54,
+                &test.render_pixel_data,
+                &test.color_wheel_definition,
+                &mut test.pixel_writer,
+            )
+            .unwrap();
 
         assert_eq!(test.get_pixel.calls.lock().unwrap().len(), 1);
 
@@ -251,8 +639,117 @@ mod tests {
         );
     }
 
+    #[test]
+    fn it_should_get_pixel_at_rotated_angle() {
+        let pixel = Pixel::rgb(1, 2, 3);
+        let mut test = setup(0, 123., pixel);
+        test.color_wheel_definition.shape.rotation_degrees = 90.;
+
+        test.pixel_writer
+            .expect_write_pixel()
+            .with(eq(56), eq(54), eq(pixel))
+            .once()
+            .return_const(Ok(()));
+
+        test.target
+            .execute(
+                56,
+                54,
+                &test.render_pixel_data,
+                &test.color_wheel_definition,
+                &mut test.pixel_writer,
+            )
+            .unwrap();
+
+        let call = &test.get_pixel.calls.lock().unwrap()[0];
+        assert_approx_eq!(f64, call.angle_degrees, 315.);
+    }
+
+    #[test]
+    fn when_pixel_writer_fails_it_should_return_error() {
+        let mut test = setup(0, 123., Pixel::rgb(1, 2, 3));
+
+        let error = ColorWheelError::WrongRow {
+            expected: 53,
+            actual: 54,
+        };
+        test.pixel_writer
+            .expect_write_pixel()
+            .return_const(Err(error.clone()));
+
+        let result = test.target.execute(
+            56,
+            54,
+            &test.render_pixel_data,
+            &test.color_wheel_definition,
+            &mut test.pixel_writer,
+        );
+
+        assert_eq!(result, Err(error));
+    }
+
+    #[test]
+    fn execute_row_should_write_the_pixels_in_the_wheel_in_order() {
+        let pixel = Pixel::rgb(1, 2, 3);
+        let mut test = setup(1, 0.5, pixel);
+        test.color_wheel_definition.shape.start_angle_degrees = 0.;
+        test.color_wheel_definition.shape.end_angle_degrees = 180.;
+
+        let written = Arc::new(Mutex::new(vec![]));
+        let written_clone = written.clone();
+        test.pixel_writer
+            .expect_write_pixel()
+            .returning(move |x, y, pixel| {
+                written_clone.lock().unwrap().push((x, y, pixel));
+                Ok(())
+            });
+
+        test.target
+            .execute_row(
+                54,
+                110,
+                &test.render_pixel_data,
+                &test.color_wheel_definition,
+                &mut test.pixel_writer,
+            )
+            .unwrap();
+
+        // The right half of the row, up to the edge of the wheel.
+        let expected = (55..=104).map(|x| (x, 54, pixel)).collect::<Vec<_>>();
+        assert_eq!(*written.lock().unwrap(), expected);
+
+        let calls = test.get_pixel.calls.lock().unwrap();
+        assert_eq!(calls.len(), expected.len());
+        assert_approx_eq!(f64, calls[1].angle_degrees, 45.);
+        assert_approx_eq!(f64, calls[1].variable_dimension, 0.5);
+    }
+
+    #[test]
+    fn when_pixel_writer_fails_execute_row_should_return_error() {
+        let mut test = setup(0, 123., Pixel::rgb(1, 2, 3));
+
+        let error = ColorWheelError::WrongRow {
+            expected: 53,
+            actual: 54,
+        };
+        test.pixel_writer
+            .expect_write_pixel()
+            .once()
+            .return_const(Err(error.clone()));
+
+        let result = test.target.execute_row(
+            54,
+            110,
+            &test.render_pixel_data,
+            &test.color_wheel_definition,
+            &mut test.pixel_writer,
+        );
+
+        assert_eq!(result, Err(error));
+    }
+
     struct MockGetPixelGeneratorAndVariableDimensionCall {
-        generator_size: f64,
+        data: RenderPixelData,
         distance_from_center: f64,
     }
 
@@ -265,7 +762,7 @@ mod tests {
     impl GetPixelGeneratorAndVariableDimension for Arc<MockGetPixelGeneratorAndVariableDimension> {
         fn execute<'a, TPixelGenerator: PixelGenerator>(
             &self,
-            generator_size: f64,
+            data: &RenderPixelData,
             definition: &'a ColorWheelDefinition<TPixelGenerator>,
             distance_from_center: f64,
         ) -> Option<PixelGeneratorAndVariableDimension<'a, TPixelGenerator>> {
@@ -273,7 +770,7 @@ mod tests {
                 .lock()
                 .unwrap()
                 .push(MockGetPixelGeneratorAndVariableDimensionCall {
-                    generator_size,
+                    data: data.clone(),
                     distance_from_center,
                 });
 
@@ -282,6 +779,7 @@ mod tests {
             }
 
             Some(PixelGeneratorAndVariableDimension {
+                pixel_generator_index: self.result_index as usize,
                 pixel_generator: &definition.pixel_generators[self.result_index as usize],
                 variable_dimension: self.variable_dimension,
             })
@@ -302,19 +800,41 @@ mod tests {
         fn execute<TPixelGenerator: PixelGenerator>(
             &self,
             _pixel_generator: &TPixelGenerator,
+            _angle_perceptual_scale: Option<&PerceptualScale>,
             angle_degrees: f64,
             variable_dimension: f64,
-            angle_buckets: u32,
-            distance_buckets: u32,
+            pixel_generator_index: usize,
+            definition: &ColorWheelDefinition<TPixelGenerator>,
         ) -> Pixel {
             self.calls.lock().unwrap().push(MockGetPixelCall {
                 angle_degrees,
                 variable_dimension,
-                angle_buckets,
-                distance_buckets,
+                angle_buckets: definition.ring_angle_buckets(pixel_generator_index),
+                distance_buckets: definition.ring_distance_buckets(pixel_generator_index),
             });
 
             self.result
         }
+
+        fn execute_color<TPixelGenerator: PixelGenerator>(
+            &self,
+            pixel_generator: &TPixelGenerator,
+            angle_perceptual_scale: Option<&PerceptualScale>,
+            angle_degrees: f64,
+            variable_dimension: f64,
+            pixel_generator_index: usize,
+            definition: &ColorWheelDefinition<TPixelGenerator>,
+        ) -> Color {
+            let pixel = self.execute(
+                pixel_generator,
+                angle_perceptual_scale,
+                angle_degrees,
+                variable_dimension,
+                pixel_generator_index,
+                definition,
+            );
+            let [red, green, blue, _] = pixel.data.map(|value| value as f64 / 255.);
+            Color::srgb(red, green, blue)
+        }
     }
 }
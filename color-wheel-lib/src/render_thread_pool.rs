@@ -0,0 +1,68 @@
+#[cfg(feature = "rayon")]
+use std::sync::Arc;
+
+#[cfg(feature = "rayon")]
+use rayon::{ThreadPool, ThreadPoolBuildError, ThreadPoolBuilder};
+
+// The threads which render rows in parallel. By default this is Rayon's global thread pool,
+// or the current thread when the `rayon` feature is disabled.
+// Each row is always rendered the same way, so the output doesn't depend on the threads used.
+#[derive(Clone, Debug, Default)]
+pub struct RenderThreadPool {
+    #[cfg(feature = "rayon")]
+    thread_pool: Option<Arc<ThreadPool>>,
+}
+
+impl RenderThreadPool {
+    pub fn global() -> Self {
+        Self::default()
+    }
+
+    #[cfg(feature = "rayon")]
+    pub fn new(thread_pool: Arc<ThreadPool>) -> Self {
+        Self {
+            thread_pool: Some(thread_pool),
+        }
+    }
+
+    #[cfg(feature = "rayon")]
+    pub fn with_threads(threads: usize) -> Result<Self, ThreadPoolBuildError> {
+        let thread_pool = ThreadPoolBuilder::new().num_threads(threads).build()?;
+        Ok(Self::new(Arc::new(thread_pool)))
+    }
+
+    // Runs the operation so that Rayon's parallel iterators inside it use these threads.
+    #[cfg(feature = "rayon")]
+    pub fn install<R: Send>(&self, operation: impl FnOnce() -> R + Send) -> R {
+        match &self.thread_pool {
+            Some(thread_pool) => thread_pool.install(operation),
+            None => operation(),
+        }
+    }
+
+    #[cfg(not(feature = "rayon"))]
+    pub fn install<R>(&self, operation: impl FnOnce() -> R) -> R {
+        operation()
+    }
+}
+
+#[cfg(all(test, feature = "rayon"))]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn it_should_run_operations_in_the_thread_pool() {
+        let thread_pool = RenderThreadPool::with_threads(3).unwrap();
+
+        assert_eq!(thread_pool.install(rayon::current_num_threads), 3);
+        assert!(thread_pool.install(rayon::current_thread_index).is_some());
+    }
+
+    #[test]
+    fn the_global_thread_pool_should_run_operations_on_the_current_thread() {
+        assert_eq!(
+            RenderThreadPool::global().install(rayon::current_thread_index),
+            rayon::current_thread_index()
+        );
+    }
+}
@@ -0,0 +1,306 @@
+use std::f64::consts::PI;
+
+#[cfg(feature = "rayon")]
+use rayon::prelude::*;
+
+use crate::{
+    canvas::Canvas,
+    linear_rgb::{linear_to_srgb, srgb_to_linear},
+    pixel::BYTES_PER_PIXEL,
+};
+
+// How the supersampled pixels around each output pixel are weighted.
+#[derive(Debug, Copy, Clone, PartialEq, Eq, Default)]
+pub enum ResampleFilter {
+    // Averages the pixels inside each output pixel.
+    Box,
+    #[default]
+    Hamming,
+    // Sharpest, but can ring next to hard edges.
+    Lanczos3,
+    // Mitchell-Netravali with B and C of 1/3, which balances blurring and ringing.
+    Mitchell,
+}
+
+impl ResampleFilter {
+    // How far the filter reaches either side of an output pixel's center, in output pixels.
+    fn support(self) -> f64 {
+        match self {
+            ResampleFilter::Box => 0.5,
+            ResampleFilter::Hamming => 1.,
+            ResampleFilter::Lanczos3 => 3.,
+            ResampleFilter::Mitchell => 2.,
+        }
+    }
+
+    fn weight(self, x: f64) -> f64 {
+        let x = x.abs();
+        if x >= self.support() {
+            return 0.;
+        }
+
+        match self {
+            ResampleFilter::Box => 1.,
+            ResampleFilter::Hamming => sinc(x) * (0.54 + 0.46 * (PI * x).cos()),
+            ResampleFilter::Lanczos3 => sinc(x) * sinc(x / 3.),
+            ResampleFilter::Mitchell => {
+                const B: f64 = 1. / 3.;
+                const C: f64 = 1. / 3.;
+                if x < 1. {
+                    ((12. - 9. * B - 6. * C) * x.powi(3)
+                        + (-18. + 12. * B + 6. * C) * x.powi(2)
+                        + (6. - 2. * B))
+                        / 6.
+                } else {
+                    ((-B - 6. * C) * x.powi(3)
+                        + (6. * B + 30. * C) * x.powi(2)
+                        + (-12. * B - 48. * C) * x
+                        + (8. * B + 24. * C))
+                        / 6.
+                }
+            }
+        }
+    }
+}
+
+fn sinc(x: f64) -> f64 {
+    if x == 0. {
+        1.
+    } else {
+        (PI * x).sin() / (PI * x)
+    }
+}
+
+// The source pixels which make up one output pixel along an axis, and their weights.
+struct Taps {
+    start: usize,
+    weights: Vec<f32>,
+}
+
+fn taps(source_size: u32, factor: u32, filter: ResampleFilter) -> Vec<Taps> {
+    let scale = factor as f64;
+    let support = filter.support() * scale;
+
+    (0..source_size / factor)
+        .map(|output| {
+            let center = (output as f64 + 0.5) * scale;
+            let start = (center - support).floor().max(0.) as usize;
+            let end = ((center + support).ceil() as usize).min(source_size as usize);
+
+            let weights = (start..end)
+                .map(|source| filter.weight((source as f64 + 0.5 - center) / scale))
+                .collect::<Vec<_>>();
+
+            // Taps past the edge of the image are left out, so the rest are scaled up to match.
+            let total = weights.iter().sum::<f64>();
+            Taps {
+                start,
+                weights: weights
+                    .into_iter()
+                    .map(|weight| (weight / total) as f32)
+                    .collect(),
+            }
+        })
+        .collect()
+}
+
+// Reduces a supersampled canvas by the given factor. Pixels are decoded to linear light and
+// multiplied by their alpha before filtering, so edges between saturated colors aren't darkened
+// and transparent pixels don't leak their color into their neighbors.
+// Returns normalized, gamma encoded RGBA, for quantizing or dithering to 8 bits per channel.
+pub fn downsample(canvas: &Canvas, factor: u32, filter: ResampleFilter) -> Vec<f64> {
+    let factor = factor.max(1);
+    let source_width = canvas.width() as usize;
+    let horizontal_taps = taps(canvas.width(), factor, filter);
+    let vertical_taps = taps(canvas.height(), factor, filter);
+    let output_width = horizontal_taps.len();
+
+    let linear = (0..=255)
+        .map(|value| srgb_to_linear(value as f64 / 255.) as f32)
+        .collect::<Vec<_>>();
+
+    // Filters each row horizontally, into premultiplied linear RGBA.
+    let filter_row = |(source_row, output_row): (&[u8], &mut [f32])| {
+        for (taps, output) in horizontal_taps
+            .iter()
+            .zip(output_row.chunks_exact_mut(BYTES_PER_PIXEL))
+        {
+            let sources = source_row[taps.start * BYTES_PER_PIXEL..].chunks_exact(BYTES_PER_PIXEL);
+            for (weight, source) in taps.weights.iter().zip(sources) {
+                let alpha = source[3] as f32 / 255.;
+                for channel in 0..3 {
+                    output[channel] += weight * alpha * linear[source[channel] as usize];
+                }
+                output[3] += weight * alpha;
+            }
+        }
+    };
+
+    let mut rows = vec![0f32; output_width * canvas.height() as usize * BYTES_PER_PIXEL];
+
+    #[cfg(feature = "rayon")]
+    let source_and_output_rows = canvas
+        .data()
+        .par_chunks_exact(source_width * BYTES_PER_PIXEL)
+        .zip(rows.par_chunks_exact_mut(output_width * BYTES_PER_PIXEL));
+
+    #[cfg(not(feature = "rayon"))]
+    let source_and_output_rows = canvas
+        .data()
+        .chunks_exact(source_width * BYTES_PER_PIXEL)
+        .zip(rows.chunks_exact_mut(output_width * BYTES_PER_PIXEL));
+
+    source_and_output_rows.for_each(filter_row);
+
+    // Filters the columns vertically, then divides by alpha and encodes back to sRGB.
+    let filter_column = |(taps, output_row): (&Taps, &mut [f64])| {
+        let mut sums = vec![0f32; output_width * BYTES_PER_PIXEL];
+        for (offset, weight) in taps.weights.iter().enumerate() {
+            let row_start = (taps.start + offset) * output_width * BYTES_PER_PIXEL;
+            let row = &rows[row_start..row_start + output_width * BYTES_PER_PIXEL];
+            for (sum, value) in sums.iter_mut().zip(row) {
+                *sum += weight * value;
+            }
+        }
+
+        for (sum, output) in sums
+            .chunks_exact(BYTES_PER_PIXEL)
+            .zip(output_row.chunks_exact_mut(BYTES_PER_PIXEL))
+        {
+            let alpha = (sum[3] as f64).clamp(0., 1.);
+            if alpha == 0. {
+                continue;
+            }
+
+            for channel in 0..3 {
+                output[channel] = linear_to_srgb((sum[channel] as f64 / alpha).clamp(0., 1.));
+            }
+            output[3] = alpha;
+        }
+    };
+
+    let mut output = vec![0.; output_width * vertical_taps.len() * BYTES_PER_PIXEL];
+
+    #[cfg(feature = "rayon")]
+    let taps_and_output_rows = vertical_taps
+        .par_iter()
+        .zip(output.par_chunks_exact_mut(output_width * BYTES_PER_PIXEL));
+
+    #[cfg(not(feature = "rayon"))]
+    let taps_and_output_rows = vertical_taps
+        .iter()
+        .zip(output.chunks_exact_mut(output_width * BYTES_PER_PIXEL));
+
+    taps_and_output_rows.for_each(filter_column);
+
+    output
+}
+
+// Reduces a supersampled canvas by the given factor, as `downsample`, rounding to 8 bits.
+pub fn downsample_canvas(canvas: &Canvas, factor: u32, filter: ResampleFilter) -> Canvas {
+    let factor = factor.max(1);
+    let data = downsample(canvas, factor, filter)
+        .into_iter()
+        .map(|value| (value * 255.).round() as u8)
+        .collect();
+
+    Canvas::from_data(canvas.width() / factor, canvas.height() / factor, data)
+}
+
+#[cfg(test)]
+mod tests {
+    use float_cmp::assert_approx_eq;
+
+    use crate::pixel::Pixel;
+
+    use super::*;
+
+    const FILTERS: [ResampleFilter; 4] = [
+        ResampleFilter::Box,
+        ResampleFilter::Hamming,
+        ResampleFilter::Lanczos3,
+        ResampleFilter::Mitchell,
+    ];
+
+    fn canvas_of(width: u32, height: u32, pixel: impl Fn(u32, u32) -> Pixel) -> Canvas {
+        let mut canvas = Canvas::new(width, height);
+        for y in 0..height {
+            for x in 0..width {
+                canvas.set_pixel(x, y, pixel(x, y));
+            }
+        }
+        canvas
+    }
+
+    #[test]
+    fn every_filter_should_keep_flat_colors() {
+        let canvas = canvas_of(12, 8, |_, _| Pixel::rgba(200, 100, 30, 255));
+
+        for filter in FILTERS {
+            let result = downsample_canvas(&canvas, 4, filter);
+
+            assert_eq!((result.width(), result.height()), (3, 2));
+            assert!(result
+                .iter_pixels()
+                .all(|pixel| pixel == Pixel::rgba(200, 100, 30, 255)));
+        }
+    }
+
+    #[test]
+    fn it_should_average_in_linear_light() {
+        let canvas = canvas_of(2, 2, |x, y| {
+            if (x + y) % 2 == 0 {
+                Pixel::rgb(255, 0, 0)
+            } else {
+                Pixel::rgb(0, 255, 0)
+            }
+        });
+
+        let result = downsample_canvas(&canvas, 2, ResampleFilter::Box);
+
+        // Averaging the encoded values would give 128, which is darker.
+        assert_eq!(result.get_pixel(0, 0), Pixel::rgb(188, 188, 0));
+    }
+
+    #[test]
+    fn transparent_pixels_should_not_darken_their_neighbors() {
+        let canvas = canvas_of(2, 1, |x, _| {
+            if x == 0 {
+                Pixel::rgb(255, 128, 0)
+            } else {
+                Pixel::transparent()
+            }
+        });
+
+        let result = downsample(&canvas, 2, ResampleFilter::Box);
+
+        for (actual, expected) in result.into_iter().zip([1., 128. / 255., 0., 0.5]) {
+            assert_approx_eq!(f64, actual, expected, epsilon = 0.0001);
+        }
+    }
+
+    #[test]
+    fn filters_should_sum_to_one_away_from_the_edges() {
+        for filter in FILTERS {
+            for taps in &taps(64, 4, filter)[4..12] {
+                let total = taps.weights.iter().sum::<f32>();
+                assert!((total - 1.).abs() < 0.0001);
+                assert_eq!(
+                    taps.weights.len(),
+                    (filter.support() * 2. * 4.).round() as usize
+                );
+            }
+        }
+    }
+
+    #[test]
+    fn sharper_filters_should_weight_the_center_more() {
+        let center_weight = |filter: ResampleFilter| {
+            let taps = &taps(64, 4, filter)[8];
+            taps.weights[taps.weights.len() / 2]
+        };
+
+        assert!(center_weight(ResampleFilter::Box) < center_weight(ResampleFilter::Hamming));
+        assert!(center_weight(ResampleFilter::Mitchell) < center_weight(ResampleFilter::Lanczos3));
+    }
+}
@@ -0,0 +1,21 @@
+// Options for one ring of an expanded color wheel, which is drawn by one pixel generator.
+// The pixel generator itself provides the ring's fixed value and configuration.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct RingOptions {
+    // The ring's width relative to the other rings.
+    pub weight: f64,
+
+    // Overrides the color wheel's bucket counts for this ring.
+    pub angle_buckets: Option<u32>,
+    pub distance_buckets: Option<u32>,
+}
+
+impl Default for RingOptions {
+    fn default() -> Self {
+        Self {
+            weight: 1.,
+            angle_buckets: None,
+            distance_buckets: None,
+        }
+    }
+}
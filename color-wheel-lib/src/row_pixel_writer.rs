@@ -1,8 +1,20 @@
-use crate::pixel::{Pixel, BYTES_PER_PIXEL};
+use crate::{
+    color::Color,
+    color_wheel_error::ColorWheelError,
+    pixel::{Pixel, BYTES_PER_PIXEL},
+};
 
 #[cfg_attr(test, mockall::automock)]
 pub trait RowPixelWriter {
-    fn write_pixel(&mut self, x: u32, y: u32, pixel: Pixel);
+    // Writers which keep colors at full precision are given each color with `write_color`,
+    // rather than rendering rows in batches of 8-bit pixels.
+    const FULL_PRECISION: bool = false;
+
+    fn write_pixel(&mut self, x: u32, y: u32, pixel: Pixel) -> Result<(), ColorWheelError>;
+
+    fn write_color(&mut self, x: u32, y: u32, color: Color) -> Result<(), ColorWheelError> {
+        self.write_pixel(x, y, color.to_pixel())
+    }
 }
 
 pub struct DefaultRowPixelWriter<'canvas> {
@@ -11,16 +23,51 @@ pub struct DefaultRowPixelWriter<'canvas> {
 }
 
 impl<'canvas> RowPixelWriter for DefaultRowPixelWriter<'canvas> {
-    fn write_pixel(&mut self, x: u32, y: u32, pixel: Pixel) {
+    fn write_pixel(&mut self, x: u32, y: u32, pixel: Pixel) -> Result<(), ColorWheelError> {
         if y != self.row_index {
-            panic!(
-                "RowPixelWriter asked to write to row {} when row {} was expected.",
-                y, self.row_index
-            );
+            return Err(ColorWheelError::WrongRow {
+                expected: self.row_index,
+                actual: y,
+            });
         }
 
         let index = BYTES_PER_PIXEL * x as usize;
         self.data[index..index + BYTES_PER_PIXEL].copy_from_slice(&pixel.data);
+        Ok(())
+    }
+}
+
+// Writes normalized, gamma encoded RGBA, for dithering down to 8 bits per channel afterwards.
+pub struct ColorRowPixelWriter<'canvas> {
+    pub row_index: u32,
+    pub data: &'canvas mut [f64],
+}
+
+impl<'canvas> ColorRowPixelWriter<'canvas> {
+    fn write(&mut self, x: u32, y: u32, rgba: [f64; 4]) -> Result<(), ColorWheelError> {
+        if y != self.row_index {
+            return Err(ColorWheelError::WrongRow {
+                expected: self.row_index,
+                actual: y,
+            });
+        }
+
+        let index = BYTES_PER_PIXEL * x as usize;
+        self.data[index..index + BYTES_PER_PIXEL].copy_from_slice(&rgba);
+        Ok(())
+    }
+}
+
+impl<'canvas> RowPixelWriter for ColorRowPixelWriter<'canvas> {
+    const FULL_PRECISION: bool = true;
+
+    fn write_pixel(&mut self, x: u32, y: u32, pixel: Pixel) -> Result<(), ColorWheelError> {
+        self.write(x, y, pixel.data.map(|value| value as f64 / 255.))
+    }
+
+    fn write_color(&mut self, x: u32, y: u32, color: Color) -> Result<(), ColorWheelError> {
+        let [red, green, blue] = color.to_srgb();
+        self.write(x, y, [red, green, blue, color.alpha.clamp(0., 1.)])
     }
 }
 
@@ -35,20 +82,45 @@ mod tests {
             data: &mut [0, 0, 0, 0, 0, 0, 0, 0],
         };
 
-        pixel_writer.write_pixel(0, 2, Pixel::rgb(1, 2, 3));
-        pixel_writer.write_pixel(1, 2, Pixel::rgb(4, 5, 6));
+        pixel_writer.write_pixel(0, 2, Pixel::rgb(1, 2, 3)).unwrap();
+        pixel_writer.write_pixel(1, 2, Pixel::rgb(4, 5, 6)).unwrap();
 
         assert_eq!(pixel_writer.data, &[1, 2, 3, 255, 4, 5, 6, 255]);
     }
 
     #[test]
-    #[should_panic]
     fn it_should_verify_row() {
         let mut pixel_writer = DefaultRowPixelWriter {
             row_index: 2,
             data: &mut [0, 0, 0, 0, 0, 0, 0, 0],
         };
 
-        pixel_writer.write_pixel(0, 1, Pixel::rgb(1, 2, 3));
+        assert_eq!(
+            pixel_writer.write_pixel(0, 1, Pixel::rgb(1, 2, 3)),
+            Err(ColorWheelError::WrongRow {
+                expected: 2,
+                actual: 1
+            })
+        );
+    }
+
+    #[test]
+    fn it_should_write_colors_at_full_precision() {
+        let mut pixel_writer = ColorRowPixelWriter {
+            row_index: 1,
+            data: &mut [0.; 8],
+        };
+
+        pixel_writer
+            .write_color(0, 1, Color::srgb(0.5001, 0.25, 1.).with_alpha(0.5))
+            .unwrap();
+        pixel_writer
+            .write_pixel(1, 1, Pixel::rgb(0, 51, 255))
+            .unwrap();
+
+        assert_eq!(pixel_writer.data, &[0.5001, 0.25, 1., 0.5, 0., 0.2, 1., 1.]);
+        assert!(pixel_writer
+            .write_color(0, 0, Color::srgb(0., 0., 0.))
+            .is_err());
     }
 }
@@ -1,23 +1,38 @@
 use color_wheel_lib::{
-    bucket::BucketDirection,
+    background::Background,
+    bucket::{BucketDirection, BucketSpacing},
     canvas_pixel_writer::DefaultCanvasPixelWriter,
-    canvas_pixel_writer_factory::DefaultCanvasPixelWriterFactory,
+    canvas_pixel_writer_factory::{ColorCanvasPixelWriterFactory, DefaultCanvasPixelWriterFactory},
     color_wheel_definition::ColorWheelDefinition,
+    color_wheel_error::ColorWheelError,
+    color_wheel_projection::ColorWheelProjection,
+    color_wheel_shape::ColorWheelShape,
     get_pixel::DefaultGetPixel,
     get_pixel_generator_and_variable_dimension::DefaultGetPixelGeneratorAndVariableDimension,
     pixel::Pixel,
     pixel_generators::{
         HslFixedLightnessPixelGenerator, HslFixedSaturationPixelGenerator,
-        HsvFixedSaturationPixelGenerator, PixelGeneratorConfiguration,
+        HsvFixedSaturationPixelGenerator, HsvFixedValuePixelGenerator,
+        OklabFixedLightnessPixelGenerator, PixelGenerator, PixelGeneratorConfiguration,
     },
+    radial_mapping::RadialMapping,
     render_color_wheel::DefaultRenderColorWheel,
     render_color_wheel_rows::DefaultRenderColorWheelRows,
     render_color_wheel_set::DefaultRenderColorWheelSet,
     render_color_wheel_set::RenderColorWheelSet,
-    render_pixel::DefaultRenderPixel,
+    render_pixel::{DefaultRenderPixel, RenderPixel, RenderPixelData},
+    render_thread_pool::RenderThreadPool,
+    ring_options::RingOptions,
+    row_pixel_writer::RowPixelWriter,
 };
 
 fn setup() -> impl RenderColorWheelSet<Result = DefaultCanvasPixelWriter> {
+    setup_with_thread_pool(RenderThreadPool::global())
+}
+
+fn setup_with_thread_pool(
+    thread_pool: RenderThreadPool,
+) -> impl RenderColorWheelSet<Result = DefaultCanvasPixelWriter> {
     DefaultRenderColorWheelSet {
         render_color_wheel: DefaultRenderColorWheel {
             render_color_wheel_rows: DefaultRenderColorWheelRows {
@@ -26,9 +41,12 @@ fn setup() -> impl RenderColorWheelSet<Result = DefaultCanvasPixelWriter> {
                         DefaultGetPixelGeneratorAndVariableDimension {},
                     get_pixel: DefaultGetPixel {},
                 },
+                thread_pool,
             },
         },
-        pixel_writer_factory: DefaultCanvasPixelWriterFactory {},
+        pixel_writer_factory: DefaultCanvasPixelWriterFactory {
+            background: Background::Transparent,
+        },
     }
 }
 
@@ -45,6 +63,7 @@ fn hsv_fixed_saturation_nested() {
         is_angle_inverted: false,
         is_varying_dimension_inverted: false,
         angle_bucket_direction: BucketDirection::Down,
+        angle_bucket_spacing: BucketSpacing::Uniform,
         varying_dimension_bucket_direction: BucketDirection::Down,
     };
 
@@ -53,6 +72,10 @@ fn hsv_fixed_saturation_nested() {
         margin_size: 0,
         angle_buckets: 18,
         distance_buckets: 12,
+        angle_bucket_layout: Default::default(),
+        distance_bucket_layout: Default::default(),
+        shape: Default::default(),
+        radial_mapping: Default::default(),
         pixel_generators: vec![
             HsvFixedSaturationPixelGenerator {
                 saturation: 0.25,
@@ -67,9 +90,10 @@ fn hsv_fixed_saturation_nested() {
                 configuration,
             },
         ],
+        rings: vec![],
     };
 
-    let pixel_writer = render_color_wheel_set.execute(&[definition], 0);
+    let pixel_writer = render_color_wheel_set.execute(&[definition], 0).unwrap();
 
     assert_eq!(pixel_writer.canvas.get_pixel(15, 1), Pixel::rgb(170, 0, 0));
     assert_eq!(
@@ -100,6 +124,7 @@ fn hsl_fixed_saturation_adjacent() {
         is_angle_inverted: false,
         is_varying_dimension_inverted: true,
         angle_bucket_direction: BucketDirection::Down,
+        angle_bucket_spacing: BucketSpacing::Uniform,
         varying_dimension_bucket_direction: BucketDirection::Down,
     };
 
@@ -108,10 +133,15 @@ fn hsl_fixed_saturation_adjacent() {
         margin_size: 0,
         angle_buckets: 18,
         distance_buckets: 12,
+        angle_bucket_layout: Default::default(),
+        distance_bucket_layout: Default::default(),
+        shape: Default::default(),
+        radial_mapping: Default::default(),
         pixel_generators: vec![HslFixedSaturationPixelGenerator {
             saturation: 1.0,
             configuration,
         }],
+        rings: vec![],
     };
 
     let definition2 = ColorWheelDefinition {
@@ -119,10 +149,15 @@ fn hsl_fixed_saturation_adjacent() {
         margin_size: 0,
         angle_buckets: 18,
         distance_buckets: 12,
+        angle_bucket_layout: Default::default(),
+        distance_bucket_layout: Default::default(),
+        shape: Default::default(),
+        radial_mapping: Default::default(),
         pixel_generators: vec![HslFixedSaturationPixelGenerator {
             saturation: 0.5,
             configuration,
         }],
+        rings: vec![],
     };
 
     let definition3 = ColorWheelDefinition {
@@ -130,13 +165,20 @@ fn hsl_fixed_saturation_adjacent() {
         margin_size: 0,
         angle_buckets: 18,
         distance_buckets: 12,
+        angle_bucket_layout: Default::default(),
+        distance_bucket_layout: Default::default(),
+        shape: Default::default(),
+        radial_mapping: Default::default(),
         pixel_generators: vec![HslFixedSaturationPixelGenerator {
             saturation: 0.25,
             configuration,
         }],
+        rings: vec![],
     };
 
-    let pixel_writer = render_color_wheel_set.execute(&[definition1, definition2, definition3], 1);
+    let pixel_writer = render_color_wheel_set
+        .execute(&[definition1, definition2, definition3], 1)
+        .unwrap();
 
     assert_eq!(pixel_writer.canvas.get_pixel(15, 1), Pixel::rgb(85, 0, 0));
     assert_eq!(pixel_writer.canvas.get_pixel(47, 1), Pixel::rgb(64, 21, 21));
@@ -153,6 +195,7 @@ fn hsl_fixed_lightness_adjacent_margin() {
         is_angle_inverted: false,
         is_varying_dimension_inverted: false,
         angle_bucket_direction: BucketDirection::Down,
+        angle_bucket_spacing: BucketSpacing::Uniform,
         varying_dimension_bucket_direction: BucketDirection::Down,
     };
 
@@ -161,10 +204,15 @@ fn hsl_fixed_lightness_adjacent_margin() {
         margin_size: 2,
         angle_buckets: 18,
         distance_buckets: 12,
+        angle_bucket_layout: Default::default(),
+        distance_bucket_layout: Default::default(),
+        shape: Default::default(),
+        radial_mapping: Default::default(),
         pixel_generators: vec![HslFixedLightnessPixelGenerator {
             lightness: 0.6,
             configuration,
         }],
+        rings: vec![],
     };
 
     let definition2 = ColorWheelDefinition {
@@ -172,13 +220,20 @@ fn hsl_fixed_lightness_adjacent_margin() {
         margin_size: 4,
         angle_buckets: 18,
         distance_buckets: 12,
+        angle_bucket_layout: Default::default(),
+        distance_bucket_layout: Default::default(),
+        shape: Default::default(),
+        radial_mapping: Default::default(),
         pixel_generators: vec![HslFixedLightnessPixelGenerator {
             lightness: 0.4,
             configuration,
         }],
+        rings: vec![],
     };
 
-    let pixel_writer = render_color_wheel_set.execute(&[definition1, definition2], 1);
+    let pixel_writer = render_color_wheel_set
+        .execute(&[definition1, definition2], 1)
+        .unwrap();
 
     assert_eq!(pixel_writer.canvas.get_pixel(15, 1), Pixel::transparent());
     assert_eq!(
@@ -190,3 +245,263 @@ fn hsl_fixed_lightness_adjacent_margin() {
 
     // write(pixel_writer);
 }
+
+// Rows may be rendered on any thread in any order, but the image must always be the same.
+#[cfg(feature = "rayon")]
+#[test]
+fn output_should_not_depend_on_the_number_of_threads() {
+    let configuration = PixelGeneratorConfiguration {
+        is_angle_inverted: false,
+        is_varying_dimension_inverted: false,
+        angle_bucket_direction: BucketDirection::Center,
+        angle_bucket_spacing: BucketSpacing::Perceptual,
+        varying_dimension_bucket_direction: BucketDirection::Up,
+    };
+
+    let render = |thread_pool: RenderThreadPool| {
+        let definition = ColorWheelDefinition {
+            image_size: 257,
+            margin_size: 3,
+            angle_buckets: 0,
+            distance_buckets: 7,
+            angle_bucket_layout: Default::default(),
+            distance_bucket_layout: Default::default(),
+            shape: Default::default(),
+            radial_mapping: Default::default(),
+            pixel_generators: vec![
+                OklabFixedLightnessPixelGenerator {
+                    lightness: 0.7,
+                    configuration,
+                },
+                OklabFixedLightnessPixelGenerator {
+                    lightness: 0.4,
+                    configuration,
+                },
+            ],
+            rings: vec![],
+        };
+
+        setup_with_thread_pool(thread_pool)
+            .execute(&[definition], 0)
+            .unwrap()
+            .canvas
+            .eject_data()
+    };
+
+    let expected = render(RenderThreadPool::global());
+    for threads in [1, 2, 7] {
+        assert!(
+            render(RenderThreadPool::with_threads(threads).unwrap()) == expected,
+            "Rendering with {threads} threads changed the image."
+        );
+    }
+}
+
+// Only renders one pixel at a time, to check rendering whole rows against.
+struct RenderEachPixel<TRenderPixel>(TRenderPixel);
+
+impl<TRenderPixel: RenderPixel> RenderPixel for RenderEachPixel<TRenderPixel> {
+    fn execute<TPixelGenerator: PixelGenerator, TRowPixelWriter>(
+        &self,
+        image_x: u32,
+        image_y: u32,
+        data: &RenderPixelData,
+        definition: &ColorWheelDefinition<TPixelGenerator>,
+        pixel_writer: &mut TRowPixelWriter,
+    ) -> Result<(), ColorWheelError>
+    where
+        TRowPixelWriter: for<'canvas> RowPixelWriter,
+    {
+        self.0
+            .execute(image_x, image_y, data, definition, pixel_writer)
+    }
+}
+
+// Rows are rendered in batches with vectorized color conversions, which must give exactly the
+// same pixels as rendering each pixel on its own.
+#[test]
+fn rendering_rows_should_match_rendering_each_pixel() {
+    let configuration = |angle_bucket_spacing| PixelGeneratorConfiguration {
+        is_angle_inverted: false,
+        is_varying_dimension_inverted: true,
+        angle_bucket_direction: BucketDirection::Center,
+        angle_bucket_spacing,
+        varying_dimension_bucket_direction: BucketDirection::Up,
+    };
+
+    let definition = |shape: ColorWheelShape, angle_buckets, distance_buckets, radial_mapping| {
+        ColorWheelDefinition::<Box<dyn PixelGenerator>> {
+            image_size: 143,
+            margin_size: 2,
+            angle_buckets,
+            distance_buckets,
+            angle_bucket_layout: Default::default(),
+            distance_bucket_layout: Default::default(),
+            shape,
+            radial_mapping,
+            pixel_generators: vec![
+                Box::new(HsvFixedSaturationPixelGenerator {
+                    saturation: 0.8,
+                    configuration: configuration(BucketSpacing::Uniform),
+                }),
+                Box::new(HslFixedLightnessPixelGenerator {
+                    lightness: 0.6,
+                    configuration: configuration(BucketSpacing::Perceptual),
+                }),
+                Box::new(OklabFixedLightnessPixelGenerator {
+                    lightness: 0.7,
+                    configuration: configuration(BucketSpacing::Uniform),
+                }),
+                Box::new(HsvFixedValuePixelGenerator {
+                    value: 0.9,
+                    configuration: configuration(BucketSpacing::Uniform),
+                }),
+                Box::new(HslFixedSaturationPixelGenerator {
+                    saturation: 0.5,
+                    configuration: configuration(BucketSpacing::Perceptual),
+                }),
+            ],
+            rings: vec![
+                RingOptions::default(),
+                RingOptions {
+                    weight: 2.,
+                    angle_buckets: Some(9),
+                    distance_buckets: None,
+                },
+            ],
+        }
+    };
+
+    fn render(
+        render_pixel: impl RenderPixel,
+        definition: ColorWheelDefinition<Box<dyn PixelGenerator>>,
+    ) -> Vec<u8> {
+        DefaultRenderColorWheelSet {
+            render_color_wheel: DefaultRenderColorWheel {
+                render_color_wheel_rows: DefaultRenderColorWheelRows {
+                    render_pixel,
+                    thread_pool: RenderThreadPool::global(),
+                },
+            },
+            pixel_writer_factory: DefaultCanvasPixelWriterFactory {
+                background: Background::Transparent,
+            },
+        }
+        .execute(&[definition], 0)
+        .unwrap()
+        .canvas
+        .eject_data()
+    }
+
+    let default_render_pixel = || DefaultRenderPixel {
+        get_pixel_generator_and_variable_dimension: DefaultGetPixelGeneratorAndVariableDimension {},
+        get_pixel: DefaultGetPixel {},
+    };
+
+    let shapes = [
+        ColorWheelShape::default(),
+        ColorWheelShape {
+            inner_radius: 0.3,
+            ring_gap: 2,
+            start_angle_degrees: 300.,
+            end_angle_degrees: 200.,
+            rotation_degrees: 45.,
+            ..Default::default()
+        },
+        ColorWheelShape {
+            projection: ColorWheelProjection::UnrolledSquare,
+            ..Default::default()
+        },
+        ColorWheelShape {
+            projection: ColorWheelProjection::UnrolledTriangleInRing,
+            rotation_degrees: 90.,
+            ..Default::default()
+        },
+    ];
+
+    for shape in shapes {
+        for (angle_buckets, distance_buckets, radial_mapping) in [
+            (0, 0, RadialMapping::Linear),
+            (12, 5, RadialMapping::Sqrt),
+            (24, 0, RadialMapping::Perceptual),
+        ] {
+            let rows = render(
+                default_render_pixel(),
+                definition(shape, angle_buckets, distance_buckets, radial_mapping),
+            );
+            let each_pixel = render(
+                RenderEachPixel(default_render_pixel()),
+                definition(shape, angle_buckets, distance_buckets, radial_mapping),
+            );
+
+            assert!(
+                rows == each_pixel,
+                "Rendering rows changed the image for {shape:?} with {radial_mapping:?}."
+            );
+        }
+    }
+}
+
+// Rendering at full precision is for dithering, and must otherwise give the same colors.
+#[test]
+fn full_precision_rendering_should_round_to_the_rendered_pixels() {
+    let definition = || ColorWheelDefinition {
+        image_size: 101,
+        margin_size: 4,
+        angle_buckets: 0,
+        distance_buckets: 0,
+        angle_bucket_layout: Default::default(),
+        distance_bucket_layout: Default::default(),
+        shape: Default::default(),
+        radial_mapping: Default::default(),
+        pixel_generators: vec![HslFixedSaturationPixelGenerator {
+            saturation: 0.7,
+            configuration: PixelGeneratorConfiguration {
+                is_angle_inverted: false,
+                is_varying_dimension_inverted: false,
+                angle_bucket_direction: BucketDirection::Down,
+                angle_bucket_spacing: BucketSpacing::Uniform,
+                varying_dimension_bucket_direction: BucketDirection::Down,
+            },
+        }],
+        rings: vec![],
+    };
+
+    let render_color_wheel = || DefaultRenderColorWheel {
+        render_color_wheel_rows: DefaultRenderColorWheelRows {
+            render_pixel: DefaultRenderPixel {
+                get_pixel_generator_and_variable_dimension:
+                    DefaultGetPixelGeneratorAndVariableDimension {},
+                get_pixel: DefaultGetPixel {},
+            },
+            thread_pool: RenderThreadPool::global(),
+        },
+    };
+
+    let background = Background::Solid(Pixel::rgb(10, 20, 30));
+    let pixels = DefaultRenderColorWheelSet {
+        render_color_wheel: render_color_wheel(),
+        pixel_writer_factory: DefaultCanvasPixelWriterFactory { background },
+    }
+    .execute(&[definition()], 0)
+    .unwrap()
+    .canvas
+    .eject_data();
+
+    let colors = DefaultRenderColorWheelSet {
+        render_color_wheel: render_color_wheel(),
+        pixel_writer_factory: ColorCanvasPixelWriterFactory { background },
+    }
+    .execute(&[definition()], 0)
+    .unwrap()
+    .data;
+
+    assert_eq!(colors.len(), pixels.len());
+    assert!(colors
+        .iter()
+        .zip(&pixels)
+        .all(|(color, pixel)| (color * 255.).round() as u8 == *pixel));
+    assert!(colors
+        .iter()
+        .any(|color| (color * 255.).fract().abs() > 0.01));
+}
@@ -0,0 +1,99 @@
+use color_wheel_lib::{
+    pixel::Pixel,
+    pixel_generators::{hsl_to_pixel, hsv_to_pixel, oklab_to_pixel},
+};
+use numpy::{
+    ndarray::{ArrayD, IxDyn},
+    IntoPyArray, PyArrayDyn, PyReadonlyArrayDyn,
+};
+use pyo3::{exceptions::PyValueError, prelude::*};
+
+// Converts arrays of hue in degrees, saturation and lightness to an array of uint8 RGB
+// colors, with a trailing dimension of 3.
+#[pyfunction]
+pub fn hsl_to_rgb<'py>(
+    py: Python<'py>,
+    hue: PyReadonlyArrayDyn<'py, f64>,
+    saturation: PyReadonlyArrayDyn<'py, f64>,
+    lightness: PyReadonlyArrayDyn<'py, f64>,
+) -> PyResult<&'py PyArrayDyn<u8>> {
+    convert(py, hue, saturation, lightness, hsl_to_pixel)
+}
+
+// Converts arrays of hue in degrees, saturation and value to an array of uint8 RGB colors.
+#[pyfunction]
+pub fn hsv_to_rgb<'py>(
+    py: Python<'py>,
+    hue: PyReadonlyArrayDyn<'py, f64>,
+    saturation: PyReadonlyArrayDyn<'py, f64>,
+    value: PyReadonlyArrayDyn<'py, f64>,
+) -> PyResult<&'py PyArrayDyn<u8>> {
+    convert(py, hue, saturation, value, hsv_to_pixel)
+}
+
+// Converts arrays of OKLab lightness, a and b to an array of uint8 sRGB colors.
+#[pyfunction]
+pub fn oklab_to_rgb<'py>(
+    py: Python<'py>,
+    lightness: PyReadonlyArrayDyn<'py, f64>,
+    a: PyReadonlyArrayDyn<'py, f64>,
+    b: PyReadonlyArrayDyn<'py, f64>,
+) -> PyResult<&'py PyArrayDyn<u8>> {
+    convert(py, lightness, a, b, oklab_to_pixel)
+}
+
+fn convert<'py>(
+    py: Python<'py>,
+    first: PyReadonlyArrayDyn<'py, f64>,
+    second: PyReadonlyArrayDyn<'py, f64>,
+    third: PyReadonlyArrayDyn<'py, f64>,
+    to_pixel: fn(f64, f64, f64) -> Pixel,
+) -> PyResult<&'py PyArrayDyn<u8>> {
+    let (first, second, third) = (first.as_array(), second.as_array(), third.as_array());
+
+    if first.shape() != second.shape() || first.shape() != third.shape() {
+        return Err(PyValueError::new_err(
+            "All color components must have the same shape",
+        ));
+    }
+
+    let mut shape = first.shape().to_vec();
+    shape.push(3);
+
+    let data = convert_components(
+        first
+            .iter()
+            .zip(second.iter())
+            .zip(third.iter())
+            .map(|((first, second), third)| (*first, *second, *third)),
+        to_pixel,
+    );
+
+    let colors = ArrayD::from_shape_vec(IxDyn(&shape), data)
+        .map_err(|error| PyValueError::new_err(error.to_string()))?;
+    Ok(colors.into_pyarray(py))
+}
+
+fn convert_components(
+    components: impl Iterator<Item = (f64, f64, f64)>,
+    to_pixel: fn(f64, f64, f64) -> Pixel,
+) -> Vec<u8> {
+    components
+        .flat_map(|(first, second, third)| {
+            let pixel = to_pixel(first, second, third);
+            [pixel.data[0], pixel.data[1], pixel.data[2]]
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn it_should_convert_each_set_of_components() {
+        let data = convert_components([(0., 1., 0.5), (120., 1., 0.5)].into_iter(), hsl_to_pixel);
+
+        assert_eq!(data, vec![255, 0, 0, 0, 255, 0]);
+    }
+}
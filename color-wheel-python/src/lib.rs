@@ -0,0 +1,15 @@
+use pyo3::prelude::*;
+
+mod color_conversions;
+mod render;
+
+// The `color_wheel` Python module.
+#[pymodule]
+fn color_wheel(_py: Python, module: &PyModule) -> PyResult<()> {
+    module.add("COLOR_WHEEL_TYPES", render::color_wheel_types())?;
+    module.add_function(wrap_pyfunction!(render::render, module)?)?;
+    module.add_function(wrap_pyfunction!(color_conversions::hsl_to_rgb, module)?)?;
+    module.add_function(wrap_pyfunction!(color_conversions::hsv_to_rgb, module)?)?;
+    module.add_function(wrap_pyfunction!(color_conversions::oklab_to_rgb, module)?)?;
+    Ok(())
+}
@@ -0,0 +1,237 @@
+use color_wheel_lib::{
+    color_wheel_projection::ColorWheelProjection,
+    color_wheel_ring::ColorWheelRing,
+    color_wheel_shape::ColorWheelShape,
+    color_wheel_spec::ColorWheelSpec,
+    pixel::BYTES_PER_PIXEL,
+    pixel_generators::{PixelGeneratorRegistration, PixelGeneratorRegistry},
+    render_color_wheel_spec::{render_color_wheel_spec, render_color_wheel_spec_normalized},
+    resample::ResampleFilter,
+    ring_options::RingOptions,
+};
+use numpy::{ndarray::Array3, IntoPyArray};
+use pyo3::{exceptions::PyValueError, prelude::*, types::PyDict};
+
+// The color wheel types, named as on the command line.
+pub fn color_wheel_types() -> Vec<&'static str> {
+    PixelGeneratorRegistry::default()
+        .registrations()
+        .iter()
+        .map(|registration| registration.name)
+        .collect()
+}
+
+// Renders the color wheels to an `(height, width, 4)` RGBA array, either uint8 or float32
+// with channels from 0 to 1 at more than 8 bits of precision. Options are named and default as on the command line, and each
+// ring is a dict with a `fixed` value and optional `weight`, `angular_buckets` and
+// `radial_buckets`.
+#[pyfunction]
+#[pyo3(signature = (
+    color_wheel_type,
+    *,
+    diameter = 980,
+    margin = 10,
+    fixed = None,
+    angular_buckets = 0,
+    radial_buckets = 0,
+    expand = false,
+    reverse_radial_colors = false,
+    projection = "polar",
+    inner_radius = 0.,
+    start_angle = 0.,
+    end_angle = 360.,
+    rotation = 0.,
+    ring_gap = 0,
+    radial_mapping = "linear",
+    rings = None,
+    supersampling = 4,
+    resample_filter = "hamming",
+    dtype = "uint8"
+))]
+#[allow(clippy::too_many_arguments)]
+pub fn render(
+    py: Python,
+    color_wheel_type: &str,
+    diameter: u32,
+    margin: u32,
+    fixed: Option<Vec<f64>>,
+    angular_buckets: u32,
+    radial_buckets: u32,
+    expand: bool,
+    reverse_radial_colors: bool,
+    projection: &str,
+    inner_radius: f64,
+    start_angle: f64,
+    end_angle: f64,
+    rotation: f64,
+    ring_gap: u32,
+    radial_mapping: &str,
+    rings: Option<Vec<&PyDict>>,
+    supersampling: u32,
+    resample_filter: &str,
+    dtype: &str,
+) -> PyResult<PyObject> {
+    let registration = registration(color_wheel_type).map_err(PyValueError::new_err)?;
+    let defaults = ColorWheelSpec::new(registration);
+
+    let spec = ColorWheelSpec {
+        diameter,
+        margin,
+        fixed: fixed.unwrap_or(defaults.fixed.clone()),
+        expand,
+        rings: rings
+            .unwrap_or_default()
+            .into_iter()
+            .map(extract_ring)
+            .collect::<PyResult<_>>()?,
+        angle_buckets: angular_buckets,
+        distance_buckets: radial_buckets,
+        is_varying_dimension_inverted: reverse_radial_colors,
+        shape: ColorWheelShape {
+            projection: parse_projection(projection).map_err(PyValueError::new_err)?,
+            inner_radius,
+            start_angle_degrees: start_angle,
+            end_angle_degrees: end_angle,
+            ring_gap,
+            rotation_degrees: rotation,
+        },
+        radial_mapping: radial_mapping.parse().map_err(PyValueError::new_err)?,
+        supersampling,
+        resample_filter: parse_resample_filter(resample_filter).map_err(PyValueError::new_err)?,
+        ..defaults
+    };
+    spec.validate()
+        .map_err(|error| PyValueError::new_err(error.to_string()))?;
+
+    if dtype != "uint8" && dtype != "float32" {
+        return Err(PyValueError::new_err(
+            "dtype must be \"uint8\" or \"float32\"",
+        ));
+    }
+
+    // Rendering doesn't touch Python objects, so other Python threads can run meanwhile.
+    if dtype == "float32" {
+        let colors = py
+            .allow_threads(|| render_color_wheel_spec_normalized(&spec))
+            .map_err(|error| PyValueError::new_err(error.to_string()))?;
+
+        let shape = (
+            colors.height as usize,
+            colors.width as usize,
+            BYTES_PER_PIXEL,
+        );
+        let channels = colors
+            .data
+            .into_iter()
+            .map(|channel| channel as f32)
+            .collect();
+        let pixels = Array3::from_shape_vec(shape, channels)
+            .map_err(|error| PyValueError::new_err(error.to_string()))?;
+
+        return Ok(pixels.into_pyarray(py).to_object(py));
+    }
+
+    let canvas = py
+        .allow_threads(|| render_color_wheel_spec(&spec))
+        .map_err(|error| PyValueError::new_err(error.to_string()))?;
+
+    let shape = (
+        canvas.height() as usize,
+        canvas.width() as usize,
+        BYTES_PER_PIXEL,
+    );
+    let pixels = Array3::from_shape_vec(shape, canvas.eject_data())
+        .map_err(|error| PyValueError::new_err(error.to_string()))?;
+
+    Ok(pixels.into_pyarray(py).to_object(py))
+}
+
+fn registration(color_wheel_type: &str) -> Result<PixelGeneratorRegistration, String> {
+    PixelGeneratorRegistry::default()
+        .get(color_wheel_type)
+        .copied()
+        .ok_or_else(|| {
+            format!(
+                "Unknown color wheel type \"{color_wheel_type}\", expected one of {}",
+                color_wheel_types().join(", ")
+            )
+        })
+}
+
+fn parse_projection(projection: &str) -> Result<ColorWheelProjection, String> {
+    match projection {
+        "polar" => Ok(ColorWheelProjection::Polar),
+        "unrolled-square" => Ok(ColorWheelProjection::UnrolledSquare),
+        "unrolled-strip" => Ok(ColorWheelProjection::UnrolledStrip),
+        "unrolled-triangle-in-ring" => Ok(ColorWheelProjection::UnrolledTriangleInRing),
+        _ => Err(format!(
+            "Unknown projection \"{projection}\", expected one of polar, unrolled-square, unrolled-strip or unrolled-triangle-in-ring"
+        )),
+    }
+}
+
+fn parse_resample_filter(resample_filter: &str) -> Result<ResampleFilter, String> {
+    match resample_filter {
+        "box" => Ok(ResampleFilter::Box),
+        "hamming" => Ok(ResampleFilter::Hamming),
+        "lanczos3" => Ok(ResampleFilter::Lanczos3),
+        "mitchell" => Ok(ResampleFilter::Mitchell),
+        _ => Err(format!(
+            "Unknown resample filter \"{resample_filter}\", expected one of box, hamming, lanczos3 or mitchell"
+        )),
+    }
+}
+
+fn extract_ring(ring: &PyDict) -> PyResult<ColorWheelRing> {
+    let fixed = ring
+        .get_item("fixed")?
+        .ok_or_else(|| PyValueError::new_err("Each ring needs a fixed value"))?
+        .extract()?;
+    let mut options = RingOptions::default();
+
+    for (key, value) in ring {
+        match key.extract::<&str>()? {
+            "fixed" => {}
+            "weight" => options.weight = value.extract()?,
+            "angular_buckets" => options.angle_buckets = value.extract()?,
+            "radial_buckets" => options.distance_buckets = value.extract()?,
+            key => {
+                return Err(PyValueError::new_err(format!(
+                    "Unknown ring option \"{key}\", expected fixed, weight, angular_buckets or radial_buckets"
+                )))
+            }
+        }
+    }
+
+    Ok(ColorWheelRing {
+        options,
+        ..ColorWheelRing::new(fixed)
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn it_should_find_every_color_wheel_type() {
+        for color_wheel_type in color_wheel_types() {
+            assert!(registration(color_wheel_type).is_ok());
+        }
+        assert!(registration("rgb").is_err());
+    }
+
+    #[test]
+    fn it_should_parse_options_as_on_the_command_line() {
+        assert_eq!(
+            parse_projection("unrolled-strip"),
+            Ok(ColorWheelProjection::UnrolledStrip)
+        );
+        assert!(parse_projection("square").is_err());
+        assert_eq!(
+            parse_resample_filter("lanczos3"),
+            Ok(ResampleFilter::Lanczos3)
+        );
+        assert!(parse_resample_filter("bilinear").is_err());
+    }
+}
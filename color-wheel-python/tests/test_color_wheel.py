@@ -0,0 +1,116 @@
+import numpy as np
+import pytest
+
+import color_wheel
+
+
+def test_it_should_render_an_rgba_array():
+    pixels = color_wheel.render("hsl-fixed-lightness", diameter=21, margin=2)
+
+    assert pixels.shape == (25, 25, 4)
+    assert pixels.dtype == np.uint8
+    # Corners are outside the wheel and transparent, the center is opaque.
+    assert pixels[0, 0, 3] == 0
+    assert pixels[12, 12, 3] == 255
+
+
+def test_it_should_render_wheels_side_by_side():
+    pixels = color_wheel.render("hsv-fixed-value", diameter=21, margin=2, fixed=[0.2, 0.6, 1.0])
+    assert pixels.shape == (25, 75, 4)
+
+
+def test_it_should_render_expanded_wheels_as_one():
+    pixels = color_wheel.render("hsv-fixed-value", diameter=21, margin=2, fixed=[0.2, 1.0], expand=True)
+    assert pixels.shape == (25, 25, 4)
+
+
+def test_it_should_render_rings_as_one_wheel():
+    pixels = color_wheel.render(
+        "oklab-fixed-lightness",
+        diameter=21,
+        margin=2,
+        rings=[{"fixed": 0.5}, {"fixed": 0.8, "weight": 2, "angular_buckets": 12}],
+        ring_gap=1,
+        radial_mapping="sqrt",
+    )
+    assert pixels.shape == (25, 25, 4)
+
+
+def test_it_should_render_the_shape_and_supersampling_options():
+    pixels = color_wheel.render(
+        "hsl-fixed-lightness",
+        diameter=21,
+        margin=0,
+        projection="unrolled-strip",
+        inner_radius=0.25,
+        start_angle=270,
+        end_angle=90,
+        rotation=45,
+        supersampling=2,
+        resample_filter="lanczos3",
+    )
+    assert pixels.shape == (21, 21, 4)
+
+
+def test_it_should_render_float32_channels():
+    uint8 = color_wheel.render("oklab-fixed-lightness", diameter=21, margin=0)
+    float32 = color_wheel.render("oklab-fixed-lightness", diameter=21, margin=0, dtype="float32")
+
+    assert float32.dtype == np.float32
+    assert float32.min() >= 0.0 and float32.max() <= 1.0
+    # The channels keep their precision rather than being the uint8 image scaled down.
+    np.testing.assert_allclose(float32 * 255.0, uint8, atol=0.501)
+    assert not np.allclose(float32, uint8 / 255.0, atol=1e-6)
+
+
+@pytest.mark.parametrize("color_wheel_type", color_wheel.COLOR_WHEEL_TYPES)
+def test_it_should_render_every_type(color_wheel_type):
+    pixels = color_wheel.render(color_wheel_type, diameter=11, margin=0, angular_buckets=6, radial_buckets=3)
+    assert pixels.shape == (11, 11, 4)
+
+
+@pytest.mark.parametrize(
+    "arguments",
+    [
+        {"color_wheel_type": "rgb"},
+        {"color_wheel_type": "hsl-fixed-lightness", "diameter": 1},
+        {"color_wheel_type": "hsl-fixed-lightness", "fixed": []},
+        {"color_wheel_type": "hsl-fixed-lightness", "angular_buckets": 5000},
+        {"color_wheel_type": "hsl-fixed-lightness", "dtype": "float64"},
+        {"color_wheel_type": "hsl-fixed-lightness", "projection": "square"},
+        {"color_wheel_type": "hsl-fixed-lightness", "radial_mapping": "cubic"},
+        {"color_wheel_type": "hsl-fixed-lightness", "resample_filter": "bilinear"},
+        {"color_wheel_type": "hsl-fixed-lightness", "supersampling": 9},
+        {"color_wheel_type": "hsl-fixed-lightness", "start_angle": 90, "end_angle": 90},
+        {"color_wheel_type": "hsl-fixed-lightness", "rings": [{"weight": 2}]},
+        {"color_wheel_type": "hsl-fixed-lightness", "rings": [{"fixed": 0.5, "size": 2}]},
+    ],
+)
+def test_it_should_reject_invalid_arguments(arguments):
+    with pytest.raises(ValueError):
+        color_wheel.render(**arguments)
+
+
+def test_it_should_convert_hsl_to_rgb():
+    rgb = color_wheel.hsl_to_rgb(np.array([0.0, 120.0, 240.0]), np.ones(3), np.full(3, 0.5))
+
+    assert rgb.dtype == np.uint8
+    np.testing.assert_array_equal(rgb, [[255, 0, 0], [0, 255, 0], [0, 0, 255]])
+
+
+def test_it_should_convert_hsv_to_rgb_keeping_the_input_shape():
+    hue = np.array([[0.0, 60.0], [180.0, 300.0]])
+    rgb = color_wheel.hsv_to_rgb(hue, np.ones((2, 2)), np.ones((2, 2)))
+
+    assert rgb.shape == (2, 2, 3)
+    np.testing.assert_array_equal(rgb[1, 0], [0, 255, 255])
+
+
+def test_it_should_convert_oklab_to_rgb():
+    rgb = color_wheel.oklab_to_rgb(np.array([0.0, 1.0]), np.zeros(2), np.zeros(2))
+    np.testing.assert_array_equal(rgb, [[0, 0, 0], [255, 255, 255]])
+
+
+def test_it_should_reject_mismatched_shapes():
+    with pytest.raises(ValueError):
+        color_wheel.hsl_to_rgb(np.zeros(2), np.zeros(3), np.zeros(3))
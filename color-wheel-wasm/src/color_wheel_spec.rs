@@ -0,0 +1,242 @@
+use color_wheel_lib::{
+    color_wheel_projection::ColorWheelProjection,
+    color_wheel_ring::ColorWheelRing,
+    color_wheel_shape::ColorWheelShape,
+    color_wheel_spec,
+    pixel_generators::{
+        HslFixedLightnessPixelGenerator, HslFixedSaturationPixelGenerator,
+        HsvFixedSaturationPixelGenerator, HsvFixedValuePixelGenerator,
+        OklabFixedChromaPixelGenerator, OklabFixedLightnessPixelGenerator,
+        PixelGeneratorRegistration,
+    },
+    radial_mapping::RadialMapping,
+    resample::ResampleFilter,
+    ring_options::RingOptions,
+};
+use wasm_bindgen::prelude::*;
+
+#[wasm_bindgen]
+#[derive(Copy, Clone, PartialEq, Eq, Debug)]
+pub enum ColorWheelType {
+    HslFixedSaturation,
+    HslFixedLightness,
+    HsvFixedSaturation,
+    HsvFixedValue,
+    OklabFixedLightness,
+    OklabFixedChroma,
+}
+
+impl ColorWheelType {
+    // The registered pixel generator for the type, which gives its valid fixed values.
+    pub fn registration(self) -> PixelGeneratorRegistration {
+        match self {
+            ColorWheelType::HslFixedSaturation => HslFixedSaturationPixelGenerator::REGISTRATION,
+            ColorWheelType::HslFixedLightness => HslFixedLightnessPixelGenerator::REGISTRATION,
+            ColorWheelType::HsvFixedSaturation => HsvFixedSaturationPixelGenerator::REGISTRATION,
+            ColorWheelType::HsvFixedValue => HsvFixedValuePixelGenerator::REGISTRATION,
+            ColorWheelType::OklabFixedLightness => OklabFixedLightnessPixelGenerator::REGISTRATION,
+            ColorWheelType::OklabFixedChroma => OklabFixedChromaPixelGenerator::REGISTRATION,
+        }
+    }
+}
+
+#[wasm_bindgen]
+#[derive(Copy, Clone, PartialEq, Eq, Debug)]
+pub enum ProjectionType {
+    Polar,
+    UnrolledSquare,
+    UnrolledStrip,
+    UnrolledTriangleInRing,
+}
+
+impl From<ProjectionType> for ColorWheelProjection {
+    fn from(value: ProjectionType) -> Self {
+        match value {
+            ProjectionType::Polar => ColorWheelProjection::Polar,
+            ProjectionType::UnrolledSquare => ColorWheelProjection::UnrolledSquare,
+            ProjectionType::UnrolledStrip => ColorWheelProjection::UnrolledStrip,
+            ProjectionType::UnrolledTriangleInRing => ColorWheelProjection::UnrolledTriangleInRing,
+        }
+    }
+}
+
+#[wasm_bindgen]
+#[derive(Copy, Clone, PartialEq, Eq, Debug)]
+pub enum ResampleFilterType {
+    Box,
+    Hamming,
+    Lanczos3,
+    Mitchell,
+}
+
+impl From<ResampleFilterType> for ResampleFilter {
+    fn from(value: ResampleFilterType) -> Self {
+        match value {
+            ResampleFilterType::Box => ResampleFilter::Box,
+            ResampleFilterType::Hamming => ResampleFilter::Hamming,
+            ResampleFilterType::Lanczos3 => ResampleFilter::Lanczos3,
+            ResampleFilterType::Mitchell => ResampleFilter::Mitchell,
+        }
+    }
+}
+
+// Describes the color wheels to render, with the same defaults as the command line.
+#[wasm_bindgen]
+#[derive(Clone, PartialEq, Debug)]
+pub struct ColorWheelSpec {
+    pub color_wheel_type: ColorWheelType,
+
+    // Diameter of each color wheel and the margin around it, in pixels.
+    pub diameter: u32,
+    pub margin: u32,
+
+    // Zero gives a smooth output.
+    pub angular_buckets: u32,
+    pub radial_buckets: u32,
+
+    pub reverse_radial_colors: bool,
+
+    // Renders each fixed value as a ring around the previous one, rather than side by side.
+    pub expand: bool,
+
+    pub projection: ProjectionType,
+
+    // Radius of the hole in the middle of the wheel, from 0 to 1.
+    pub inner_radius: f64,
+
+    // The visible arc in degrees clockwise from the top, and the rotation of the colors within it.
+    pub start_angle: f64,
+    pub end_angle: f64,
+    pub rotation: f64,
+
+    // Gap in pixels between each ring.
+    pub ring_gap: u32,
+
+    // Each pixel is rendered from this many pixels squared, from 1 to 8.
+    pub supersampling: u32,
+    pub resample_filter: ResampleFilterType,
+
+    fixed: Vec<f64>,
+    radial_mapping: RadialMapping,
+    rings: Vec<ColorWheelRing>,
+}
+
+#[wasm_bindgen]
+impl ColorWheelSpec {
+    #[wasm_bindgen(constructor)]
+    pub fn new(color_wheel_type: ColorWheelType) -> ColorWheelSpec {
+        let defaults = color_wheel_spec::ColorWheelSpec::new(color_wheel_type.registration());
+
+        ColorWheelSpec {
+            color_wheel_type,
+            diameter: defaults.diameter,
+            margin: defaults.margin,
+            angular_buckets: defaults.angle_buckets,
+            radial_buckets: defaults.distance_buckets,
+            reverse_radial_colors: defaults.is_varying_dimension_inverted,
+            expand: defaults.expand,
+            projection: ProjectionType::Polar,
+            inner_radius: defaults.shape.inner_radius,
+            start_angle: defaults.shape.start_angle_degrees,
+            end_angle: defaults.shape.end_angle_degrees,
+            rotation: defaults.shape.rotation_degrees,
+            ring_gap: defaults.shape.ring_gap,
+            supersampling: defaults.supersampling,
+            resample_filter: ResampleFilterType::Hamming,
+            fixed: defaults.fixed,
+            radial_mapping: defaults.radial_mapping,
+            rings: defaults.rings,
+        }
+    }
+
+    // Fixed values at which to render, as a `Float64Array` in JavaScript.
+    #[wasm_bindgen(getter)]
+    pub fn fixed(&self) -> Vec<f64> {
+        self.fixed.clone()
+    }
+
+    #[wasm_bindgen(setter)]
+    pub fn set_fixed(&mut self, fixed: Vec<f64>) {
+        self.fixed = fixed;
+    }
+
+    // Parses `linear`, `sqrt`, `gamma:N` or `perceptual`, as on the command line.
+    #[wasm_bindgen(js_name = setRadialMapping)]
+    pub fn set_radial_mapping(&mut self, radial_mapping: &str) -> Result<(), JsError> {
+        self.radial_mapping = radial_mapping
+            .parse()
+            .map_err(|message: String| JsError::new(&message))?;
+        Ok(())
+    }
+
+    // Adds a ring, which renders a single wheel of rings rather than the fixed values.
+    // Bucket counts which aren't given use the spec's own.
+    #[wasm_bindgen(js_name = addRing)]
+    pub fn add_ring(
+        &mut self,
+        fixed: f64,
+        weight: f64,
+        angular_buckets: Option<u32>,
+        radial_buckets: Option<u32>,
+    ) {
+        self.rings.push(ColorWheelRing {
+            options: RingOptions {
+                weight,
+                angle_buckets: angular_buckets,
+                distance_buckets: radial_buckets,
+            },
+            ..ColorWheelRing::new(fixed)
+        });
+    }
+
+    #[wasm_bindgen(js_name = clearRings)]
+    pub fn clear_rings(&mut self) {
+        self.rings.clear();
+    }
+
+    // Size of each color wheel including its margin.
+    #[wasm_bindgen(js_name = wheelSize)]
+    pub fn wheel_size(&self) -> u32 {
+        self.to_color_wheel_spec().wheel_size()
+    }
+
+    // Width of the rendered image, for creating an `ImageData`.
+    #[wasm_bindgen(js_name = imageWidth)]
+    pub fn image_width(&self) -> u32 {
+        self.to_color_wheel_spec().image_width()
+    }
+
+    #[wasm_bindgen(js_name = imageHeight)]
+    pub fn image_height(&self) -> u32 {
+        self.to_color_wheel_spec().image_height()
+    }
+}
+
+impl ColorWheelSpec {
+    pub(crate) fn to_color_wheel_spec(&self) -> color_wheel_spec::ColorWheelSpec {
+        let defaults = color_wheel_spec::ColorWheelSpec::new(self.color_wheel_type.registration());
+
+        color_wheel_spec::ColorWheelSpec {
+            diameter: self.diameter,
+            margin: self.margin,
+            fixed: self.fixed.clone(),
+            expand: self.expand,
+            rings: self.rings.clone(),
+            angle_buckets: self.angular_buckets,
+            distance_buckets: self.radial_buckets,
+            is_varying_dimension_inverted: self.reverse_radial_colors,
+            shape: ColorWheelShape {
+                projection: self.projection.into(),
+                inner_radius: self.inner_radius,
+                start_angle_degrees: self.start_angle,
+                end_angle_degrees: self.end_angle,
+                ring_gap: self.ring_gap,
+                rotation_degrees: self.rotation,
+            },
+            radial_mapping: self.radial_mapping,
+            supersampling: self.supersampling,
+            resample_filter: self.resample_filter.into(),
+            ..defaults
+        }
+    }
+}
@@ -0,0 +1,5 @@
+mod color_wheel_spec;
+mod render_color_wheels;
+
+pub use color_wheel_spec::{ColorWheelSpec, ColorWheelType, ProjectionType, ResampleFilterType};
+pub use render_color_wheels::render_color_wheels;
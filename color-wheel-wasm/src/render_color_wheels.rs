@@ -0,0 +1,13 @@
+use color_wheel_lib::render_color_wheel_spec::render_color_wheel_spec;
+use wasm_bindgen::{prelude::*, Clamped};
+
+use crate::color_wheel_spec::ColorWheelSpec;
+
+// Renders the color wheels as RGBA pixels, which JavaScript receives as a `Uint8ClampedArray`
+// ready for `new ImageData(pixels, spec.imageWidth())`.
+#[wasm_bindgen(js_name = renderColorWheels)]
+pub fn render_color_wheels(spec: &ColorWheelSpec) -> Result<Clamped<Vec<u8>>, JsError> {
+    let canvas = render_color_wheel_spec(&spec.to_color_wheel_spec())?;
+
+    Ok(Clamped(canvas.eject_data()))
+}
@@ -0,0 +1,68 @@
+// Run with `wasm-pack test --node color-wheel-wasm`.
+#![cfg(target_arch = "wasm32")]
+
+use color_wheel_wasm::{render_color_wheels, ColorWheelSpec, ColorWheelType};
+use wasm_bindgen_test::*;
+
+fn pixel(data: &[u8], spec: &ColorWheelSpec, x: u32, y: u32) -> [u8; 4] {
+    let index = ((y * spec.image_width() + x) * 4) as usize;
+    data[index..index + 4].try_into().unwrap()
+}
+
+#[wasm_bindgen_test]
+fn it_should_render_rgba_pixels() {
+    let mut spec = ColorWheelSpec::new(ColorWheelType::HslFixedLightness);
+    spec.diameter = 31;
+    spec.margin = 0;
+    spec.supersampling = 1;
+
+    let data = render_color_wheels(&spec).unwrap().0;
+
+    assert_eq!(data.len(), 31 * 31 * 4);
+    assert_eq!(pixel(&data, &spec, 0, 0), [0, 0, 0, 0]);
+    assert_eq!(pixel(&data, &spec, 15, 15), [128, 128, 128, 255]);
+    // Nearly saturated red at the top edge of the wheel.
+    assert_eq!(pixel(&data, &spec, 15, 0), [251, 4, 4, 255]);
+}
+
+#[wasm_bindgen_test]
+fn it_should_render_fixed_values_side_by_side() {
+    let mut spec = ColorWheelSpec::new(ColorWheelType::HsvFixedValue);
+    spec.diameter = 21;
+    spec.margin = 2;
+    spec.set_fixed(vec![1., 0.5]);
+
+    let data = render_color_wheels(&spec).unwrap().0;
+
+    assert_eq!(spec.image_width(), 50);
+    assert_eq!(spec.image_height(), 25);
+    assert_eq!(data.len(), 50 * 25 * 4);
+}
+
+#[wasm_bindgen_test]
+fn it_should_render_rings_as_one_wheel() {
+    let mut spec = ColorWheelSpec::new(ColorWheelType::OklabFixedLightness);
+    spec.diameter = 21;
+    spec.margin = 2;
+    spec.start_angle = 270.;
+    spec.end_angle = 90.;
+    spec.set_radial_mapping("sqrt").unwrap();
+    spec.add_ring(0.5, 1., None, None);
+    spec.add_ring(0.8, 2., Some(12), None);
+
+    let data = render_color_wheels(&spec).unwrap().0;
+
+    assert_eq!(spec.image_width(), 25);
+    assert_eq!(data.len(), 25 * 25 * 4);
+    // The bottom half is outside the arc.
+    assert_eq!(pixel(&data, &spec, 12, 20), [0, 0, 0, 0]);
+}
+
+#[wasm_bindgen_test]
+fn it_should_reject_invalid_specs() {
+    let mut spec = ColorWheelSpec::new(ColorWheelType::HsvFixedValue);
+    spec.diameter = 2;
+
+    assert!(render_color_wheels(&spec).is_err());
+    assert!(spec.set_radial_mapping("cubic").is_err());
+}